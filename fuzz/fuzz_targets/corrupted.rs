@@ -0,0 +1,10 @@
+#![no_main]
+
+use hamming_rs::CorruptedBuffer;
+use libfuzzer_sys::fuzz_target;
+
+// Valid encodings with arbitrary bit errors must decode or fail cleanly
+fuzz_target!(|buf: CorruptedBuffer| {
+    let codec = buf.spec.build();
+    let _ = codec.decode(&buf.encoded());
+});
@@ -0,0 +1,17 @@
+#![no_main]
+
+use hamming_rs::{CodeSpec, HammingCode, LinkProfile, Secded7264, SeekableIndex};
+use libfuzzer_sys::fuzz_target;
+
+// Decoding untrusted bytes must never panic, whatever the codec
+fuzz_target!(|input: (CodeSpec, Vec<u8>)| {
+    let (spec, encoded) = input;
+    let codec = spec.build();
+    let _ = codec.decode(&encoded);
+    let _ = codec.decode_terminated(&encoded);
+    let _ = Secded7264.decode(&encoded);
+    let _ = LinkProfile::radio().receive(&encoded);
+    if let Ok(index) = SeekableIndex::read(&encoded) {
+        let _ = index.read_range(&encoded, 0..index.data_len());
+    }
+});
@@ -0,0 +1,85 @@
+//! `#[derive(HammingProtect)]` for hamming-rs, re-exported by its `derive`
+//! feature
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields, Index, parse_macro_input, parse_quote};
+
+/// Implement `hamming_rs::AsBytes` for a struct by writing and reading its
+/// fields in declaration order
+///
+/// Every field must implement `AsBytes`: integers, floats, `bool`, arrays of
+/// them and other structs deriving `HammingProtect`. The struct can then be
+/// kept in a `Protected` or encoded with `AsBytes::to_encoded`.
+#[proc_macro_derive(HammingProtect)]
+pub fn derive_hamming_protect(input: TokenStream) -> TokenStream {
+    let mut input = parse_macro_input!(input as DeriveInput);
+    let body = match &input.data {
+        Data::Struct(data) => impl_fields(&data.fields),
+        _ => {
+            return syn::Error::new_spanned(&input.ident, "HammingProtect only supports structs")
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    for param in input.generics.type_params_mut() {
+        param.bounds.push(parse_quote!(::hamming_rs::AsBytes));
+    }
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let (write, read) = body;
+
+    quote! {
+        impl #impl_generics ::hamming_rs::AsBytes for #name #type_generics #where_clause {
+            fn write_bytes(&self, out: &mut ::std::vec::Vec<u8>) {
+                #write
+            }
+
+            fn read_bytes(bytes: &mut &[u8]) -> ::core::option::Option<Self> {
+                #read
+            }
+        }
+    }
+    .into()
+}
+
+// Bodies of `write_bytes` and `read_bytes` for the fields
+fn impl_fields(fields: &Fields) -> (TokenStream2, TokenStream2) {
+    let read_field = quote!(::hamming_rs::AsBytes::read_bytes(bytes)?);
+    match fields {
+        Fields::Named(fields) => {
+            let names: Vec<_> = fields
+                .named
+                .iter()
+                .filter_map(|f| f.ident.as_ref())
+                .collect();
+            (
+                quote! { #(::hamming_rs::AsBytes::write_bytes(&self.#names, out);)* },
+                quote! { ::core::option::Option::Some(Self { #(#names: #read_field,)* }) },
+            )
+        }
+        Fields::Unnamed(fields) => {
+            let indices: Vec<Index> = (0..fields.unnamed.len()).map(Index::from).collect();
+            let values: Vec<_> = indices
+                .iter()
+                .map(|i| format_ident!("field{}", i))
+                .collect();
+            (
+                quote! { #(::hamming_rs::AsBytes::write_bytes(&self.#indices, out);)* },
+                quote! {
+                    #(let #values = #read_field;)*
+                    ::core::option::Option::Some(Self(#(#values),*))
+                },
+            )
+        }
+        Fields::Unit => (
+            quote! { let _ = out; },
+            quote! {
+                let _ = bytes;
+                ::core::option::Option::Some(Self)
+            },
+        ),
+    }
+}
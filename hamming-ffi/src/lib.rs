@@ -0,0 +1,24 @@
+//! C and WebAssembly artifacts for `hamming-rs`
+//!
+//! `hamming-rs` is built as an rlib only, so it stays buildable for
+//! bare-metal targets and with `defmt`, neither of which can link a
+//! `staticlib` or `cdylib`. This crate links it into `libhamming.a`/
+//! `libhamming.so`, declared in `include/hamming.h`, or with the `wasm`
+//! feature into a wasm-bindgen module.
+//!
+//! The C functions need neither std nor an allocator: without the default
+//! `std` feature this crate is `no_std` and brings its own panic handler,
+//! so `libhamming.a` links into C firmware as it is.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use hamming_rs::ffi::*;
+#[cfg(feature = "wasm")]
+pub use hamming_rs::wasm::*;
+
+// The C API does not panic; should it ever, halt rather than unwind into C
+#[cfg(not(feature = "std"))]
+#[panic_handler]
+fn panic(_: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
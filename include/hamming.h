@@ -0,0 +1,91 @@
+#ifndef HAMMING_H
+#define HAMMING_H
+
+/* Generated by cbindgen from src/ffi.rs -- do not edit by hand. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+/**
+ * Code identifier for Hamming(7,4)
+ */
+#define HAMMING_CODE_74 74
+
+/**
+ * Code identifier for Hamming(15,11)
+ */
+#define HAMMING_CODE_1511 1511
+
+#define HAMMING_OK 0
+
+#define HAMMING_ERR_INVALID_LENGTH -1
+
+#define HAMMING_ERR_UNCORRECTABLE -2
+
+#define HAMMING_ERR_BUFFER_TOO_SMALL -3
+
+#define HAMMING_ERR_NULL_POINTER -4
+
+#define HAMMING_ERR_UNKNOWN_CODE -5
+
+#define HAMMING_ERR_OTHER -6
+
+/**
+ * Number of bytes `hamming_encode` produces for `data_len` input bytes,
+ * or 0 for an unknown code or a size that overflows `size_t`
+ */
+size_t hamming_encoded_len(uint32_t code, size_t data_len);
+
+/**
+ * Encode `data_len` bytes into `out`
+ *
+ * On success or `HAMMING_ERR_BUFFER_TOO_SMALL`, `*out_len` receives the
+ * number of bytes required.
+ *
+ * # Safety
+ *
+ * `data` must be valid for `data_len` reads, `out` for `out_cap` writes and
+ * `out_len` must point to writable memory.
+ */
+int32_t hamming_encode(uint32_t code,
+                       const uint8_t *data,
+                       size_t data_len,
+                       uint8_t *out,
+                       size_t out_cap,
+                       size_t *out_len);
+
+/**
+ * Decode `encoded_len` bytes into `out`, correcting single-bit errors
+ *
+ * On success or `HAMMING_ERR_BUFFER_TOO_SMALL`, `*out_len` receives the
+ * number of bytes required.
+ *
+ * # Safety
+ *
+ * `encoded` must be valid for `encoded_len` reads, `out` for `out_cap`
+ * writes and `out_len` must point to writable memory.
+ */
+int32_t hamming_decode(uint32_t code,
+                       const uint8_t *encoded,
+                       size_t encoded_len,
+                       uint8_t *out,
+                       size_t out_cap,
+                       size_t *out_len);
+
+/**
+ * Correct single-bit errors in an encoded buffer in place
+ *
+ * `*corrected_bits` (if not NULL) receives the number of bits flipped.
+ * The spare top bit of each codeword is left as it was, and so is the
+ * whole buffer if any codeword is uncorrectable.
+ *
+ * # Safety
+ *
+ * `encoded` must be valid for `encoded_len` reads and writes.
+ */
+int32_t hamming_correct(uint32_t code,
+                        uint8_t *encoded,
+                        size_t encoded_len,
+                        size_t *corrected_bits);
+
+#endif  /* HAMMING_H */
@@ -0,0 +1,7 @@
+/* Memory layout for the `embedded` example on a Cortex-M0+ part; adjust to
+   the target device */
+MEMORY
+{
+  FLASH : ORIGIN = 0x00000000, LENGTH = 256K
+  RAM : ORIGIN = 0x20000000, LENGTH = 32K
+}
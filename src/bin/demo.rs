@@ -35,21 +35,22 @@ fn main() -> io::Result<()> {
                 let mut bits_input = String::new();
                 stdin.read_line(&mut bits_input)?;
 
-                if let Ok(data_bits) = bits_input.trim().parse::<usize>() {
-                    current_hamming = Box::new(Hamming::new(data_bits));
-                    println!("Switched to Hamming with {} data bits", data_bits);
-                } else {
-                    println!("Invalid number");
+                match bits_input.trim().parse::<usize>().map(Hamming::try_new) {
+                    Ok(Ok(hamming)) => {
+                        println!("Switched to Hamming with {} data bits", hamming.data_bits());
+                        current_hamming = Box::new(hamming);
+                    }
+                    Ok(Err(_)) => println!("Unsupported number of data bits"),
+                    Err(_) => println!("Invalid number"),
                 }
             }
             "" => continue,
             text => {
                 // Encode the text
-                let data = text.as_bytes(); // Here's the &[u8] conversion!
-                println!("\nOriginal: \"{}\" ({} bytes)", text, data.len());
-                println!("Bytes: {:02X?}", data);
+                println!("\nOriginal: \"{}\" ({} bytes)", text, text.len());
+                println!("Bytes: {:02X?}", text.as_bytes());
 
-                let encoded = current_hamming.encode(data);
+                let encoded = current_hamming.encode_from(text);
                 println!("\nEncoded: {} bytes", encoded.len());
                 println!("Bytes: {:02X?}", encoded);
 
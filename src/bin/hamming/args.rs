@@ -0,0 +1,115 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Command-line error, reported with the usage text
+#[derive(Debug)]
+pub struct UsageError(pub String);
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for UsageError {}
+
+/// Arguments of one subcommand
+///
+/// Options are taken out by name (`--name value`, `--name=value` or a bare
+/// `--name` flag); whatever is left must be positional.
+pub struct Args {
+    args: Vec<String>,
+}
+
+impl Args {
+    pub fn new(args: Vec<String>) -> Self {
+        Self { args }
+    }
+
+    /// Remove `--name value` or `--name=value`
+    pub fn value(&mut self, name: &str) -> Result<Option<String>, UsageError> {
+        let flag = format!("--{name}");
+        let prefix = format!("--{name}=");
+
+        let Some(i) = self
+            .args
+            .iter()
+            .position(|a| *a == flag || a.starts_with(&prefix))
+        else {
+            return Ok(None);
+        };
+
+        let arg = self.args.remove(i);
+        if let Some(value) = arg.strip_prefix(&prefix) {
+            return Ok(Some(value.to_string()));
+        }
+        if i < self.args.len() {
+            Ok(Some(self.args.remove(i)))
+        } else {
+            Err(UsageError(format!("{flag} needs a value")))
+        }
+    }
+
+    /// Remove a bare flag given by any of its spellings, e.g. `["-v", "--verbose"]`
+    pub fn flag(&mut self, spellings: &[&str]) -> bool {
+        let before = self.args.len();
+        self.args.retain(|a| !spellings.contains(&a.as_str()));
+        self.args.len() != before
+    }
+
+    /// Remove `--name N`, parsed as a number, or return `default`
+    pub fn number<T: FromStr>(&mut self, name: &str, default: T) -> Result<T, UsageError> {
+        match self.value(name)? {
+            None => Ok(default),
+            Some(v) => v
+                .parse()
+                .map_err(|_| UsageError(format!("invalid --{name} {v}"))),
+        }
+    }
+
+    /// Remove every occurrence of a repeatable option
+    pub fn values(&mut self, name: &str) -> Result<Vec<String>, UsageError> {
+        let mut values = Vec::new();
+        while let Some(value) = self.value(name)? {
+            values.push(value);
+        }
+        Ok(values)
+    }
+
+    /// The remaining positional arguments; leftover options are an error
+    pub fn finish(self) -> Result<Vec<String>, UsageError> {
+        match self.args.iter().find(|a| a.starts_with('-') && a.len() > 1) {
+            Some(option) => Err(UsageError(format!("unknown option {option}"))),
+            None => Ok(self.args),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Args {
+        Args::new(line.split_whitespace().map(String::from).collect())
+    }
+
+    #[test]
+    fn test_values_and_positionals() {
+        let mut a = args("in.bin --code 1511 -v out.ecc --format=rust");
+        assert!(a.flag(&["-v", "--verbose"]));
+        assert!(!a.flag(&["-q"]));
+        assert_eq!(a.value("code").unwrap().as_deref(), Some("1511"));
+        assert_eq!(a.value("format").unwrap().as_deref(), Some("rust"));
+        assert_eq!(a.value("missing").unwrap(), None);
+        assert_eq!(a.number("missing", 7u32).unwrap(), 7);
+        assert!(a.values("missing").unwrap().is_empty());
+        assert_eq!(a.finish().unwrap(), vec!["in.bin", "out.ecc"]);
+    }
+
+    #[test]
+    fn test_bad_options() {
+        assert!(args("--code").value("code").is_err());
+        assert!(args("file --bogus").finish().is_err());
+        assert!(args("--points x").number("points", 7u32).is_err());
+    }
+}
@@ -0,0 +1,148 @@
+use crate::args::{Args, UsageError};
+use crate::{CliResult, parse_code};
+use hamming_rs::{BinarySymmetricChannel, HammingCode};
+use std::fmt::Write;
+
+pub const USAGE: &str = "hamming ber --code CODE[,CODE...] [--ber MIN..MAX] [--points N] [--bytes N] [--seed S] [--format csv|tsv]";
+
+/// Measure residual error rates of codes over a sweep of channel bit error
+/// rates
+///
+/// Every decode unit (see `HammingCode::chunk_sizes`) is decoded on its own.
+/// `residual_ber` counts wrong bits in the units that were delivered,
+/// `rejected` is the fraction of units refused as uncorrectable.
+pub fn run(mut args: Args) -> CliResult {
+    let codes = args
+        .value("code")?
+        .unwrap_or_else(|| "74,1511".into())
+        .split(',')
+        .map(|code| Ok((code.to_string(), parse_code(code)?)))
+        .collect::<Result<Vec<_>, UsageError>>()?;
+    let rates = sweep(
+        &args.value("ber")?.unwrap_or_else(|| "1e-4..1e-1".into()),
+        args.number("points", 7)?,
+    )?;
+    let bytes = args.number("bytes", 1 << 16)?;
+    let seed = args.number("seed", 1)?;
+    let separator = match args.value("format")?.as_deref() {
+        None | Some("csv") => ",",
+        Some("tsv") => "\t",
+        Some(other) => return Err(UsageError(format!("unknown format {other}")).into()),
+    };
+    if !args.finish()?.is_empty() {
+        return Err(UsageError("unexpected argument".into()).into());
+    }
+
+    // Random payload: every bit of a zeroed buffer flipped with p = 1/2
+    let mut data = vec![0u8; bytes];
+    BinarySymmetricChannel::new(0.5, seed).corrupt(&mut data);
+
+    let mut out = ["code", "ber", "residual_ber", "rejected"].join(separator);
+    out.push('\n');
+    for (name, spec) in &codes {
+        let code = spec.build();
+        for (i, &ber) in rates.iter().enumerate() {
+            let mut channel = BinarySymmetricChannel::new(ber, seed.wrapping_add(i as u64 + 1));
+            let (residual, rejected) = measure(code.as_ref(), &data, &mut channel);
+            let _ = writeln!(
+                out,
+                "{name}{separator}{ber:.3e}{separator}{residual:.4e}{separator}{rejected:.4e}"
+            );
+        }
+    }
+    print!("{out}");
+    Ok(())
+}
+
+// `(residual_ber, rejected)` for `data` sent through `channel`
+fn measure(
+    code: &dyn HammingCode,
+    data: &[u8],
+    channel: &mut BinarySymmetricChannel,
+) -> (f64, f64) {
+    let (data_unit, encoded_unit) = code.chunk_sizes();
+    let mut encoded = code.encode(data);
+    channel.corrupt(&mut encoded);
+
+    let (mut delivered_bits, mut wrong_bits) = (0u64, 0u64);
+    let (mut units, mut rejected) = (0u64, 0u64);
+    for (sent, received) in data.chunks(data_unit).zip(encoded.chunks(encoded_unit)) {
+        units += 1;
+        match code.decode(received) {
+            Ok(decoded) => {
+                delivered_bits += sent.len() as u64 * 8;
+                wrong_bits += sent
+                    .iter()
+                    .zip(&decoded)
+                    .map(|(a, b)| (a ^ b).count_ones() as u64)
+                    .sum::<u64>();
+            }
+            Err(_) => rejected += 1,
+        }
+    }
+
+    let ratio = |a: u64, b: u64| if b == 0 { 0.0 } else { a as f64 / b as f64 };
+    (ratio(wrong_bits, delivered_bits), ratio(rejected, units))
+}
+
+/// Channel error rates for `MIN..MAX` (log-spaced, `points` values) or a
+/// single rate
+fn sweep(range: &str, points: usize) -> Result<Vec<f64>, UsageError> {
+    let invalid = || UsageError(format!("invalid --ber {range}"));
+    let rate = |s: &str| {
+        s.parse::<f64>()
+            .ok()
+            .filter(|r| *r > 0.0 && *r <= 1.0)
+            .ok_or_else(invalid)
+    };
+
+    let Some((min, max)) = range.split_once("..") else {
+        return Ok(vec![rate(range)?]);
+    };
+    let (min, max) = (rate(min)?, rate(max)?);
+    if min > max || points == 0 {
+        return Err(invalid());
+    }
+    if points == 1 {
+        return Ok(vec![min]);
+    }
+    let step = (max / min).ln() / (points - 1) as f64;
+    Ok((0..points).map(|i| min * (step * i as f64).exp()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hamming_rs::CodeSpec;
+
+    #[test]
+    fn test_sweep() {
+        let rates = sweep("1e-4..1e-1", 4).unwrap();
+        assert_eq!(rates.len(), 4);
+        for (rate, expected) in rates.iter().zip([1e-4, 1e-3, 1e-2, 1e-1]) {
+            assert!((rate / expected - 1.0).abs() < 1e-9);
+        }
+        assert_eq!(sweep("0.01", 7).unwrap(), vec![0.01]);
+        assert!(sweep("1e-1..1e-4", 4).is_err());
+        assert!(sweep("0..1e-4", 4).is_err());
+    }
+
+    #[test]
+    fn test_measure() {
+        let data: Vec<u8> = (0..=255).collect();
+        let code = CodeSpec::Hamming1511.build();
+        let clean = measure(
+            code.as_ref(),
+            &data,
+            &mut BinarySymmetricChannel::new(0.0, 1),
+        );
+        assert_eq!(clean, (0.0, 0.0));
+
+        let noisy = measure(
+            code.as_ref(),
+            &data,
+            &mut BinarySymmetricChannel::new(0.05, 1),
+        );
+        assert!(noisy.0 > 0.0 && noisy.0 < 0.05);
+    }
+}
@@ -0,0 +1,293 @@
+use crate::args::{Args, UsageError};
+use crate::format::DataFormat;
+use crate::json::{Json, object};
+use crate::noise::Noise;
+use crate::progress::Progress;
+use crate::stages::Stages;
+use crate::{CliResult, parse_code};
+use hamming_rs::{
+    BlockEvent, BlockOutcome, CodeSpec, CopyStats, HammingCode, HammingError, TERMINATOR,
+    decode_copy_terminated, encode_copy_terminated,
+};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Cursor, Read, Seek, SeekFrom, Write};
+
+pub const ENCODE_USAGE: &str = "hamming encode --code CODE [--json] [--interleave DEPTH] [--scramble POLY,SEED] [--progress] [--resume] [--in-format F] [--out-format F] [INPUT|-] [OUTPUT|-]";
+pub const DECODE_USAGE: &str = "hamming decode --code CODE [-v] [--json] [--interleave DEPTH] [--scramble POLY,SEED] [--progress] [--resume] [--in-format F] [--out-format F] [--corrupt ber=B[,seed=S]] [--flip BYTE:BIT]... [--burst START,LEN]... [INPUT|-] [OUTPUT|-]";
+
+type Copy = fn(&mut dyn Read, &mut dyn Write, &dyn HammingCode) -> io::Result<CopyStats>;
+
+/// What one run did, for the closing report
+struct Summary {
+    spec: CodeSpec,
+    stats: CopyStats,
+    injected: usize,
+    resumed_at: u64,
+}
+
+impl Summary {
+    // The report as one JSON object, written to stderr like the text one
+    fn json(&self, command: &str) -> Json {
+        object([
+            ("command", command.into()),
+            ("code", self.spec.to_string().into()),
+            ("bytes_read", self.stats.bytes_read.into()),
+            ("bytes_written", self.stats.bytes_written.into()),
+            ("corrected_bits", self.stats.corrected_bits.into()),
+            ("injected_bits", self.injected.into()),
+            ("resumed_at", self.resumed_at.into()),
+        ])
+    }
+}
+
+/// Encode INPUT (default stdin) to OUTPUT (default stdout)
+///
+/// The data is followed by a [`TERMINATOR`] byte, so `decode` gives back
+/// exactly the input, whatever the code's block size.
+pub fn encode(mut args: Args) -> CliResult {
+    let json = args.flag(&["--json"]);
+    let summary = run(
+        args,
+        Noise::default(),
+        false,
+        json,
+        |input, output, code| encode_copy_terminated(input, output, code),
+    )?;
+    if json {
+        eprintln!("{}", summary.json("encode"));
+    }
+    Ok(())
+}
+
+/// Decode INPUT to OUTPUT, reporting corrected bits on stderr
+///
+/// The padding and terminator written by `encode` are stripped.
+/// `--corrupt`, `--flip` and `--burst` inject errors into the encoded input first;
+/// `-v` lists every block on stderr.
+pub fn decode(mut args: Args) -> CliResult {
+    let json = args.flag(&["--json"]);
+    let noise = Noise::from_args(&mut args)?;
+    let copy: Copy = if args.flag(&["-v", "--verbose"]) {
+        decode_verbose
+    } else {
+        |input, output, code| decode_copy_terminated(input, output, code)
+    };
+    let summary = run(args, noise, true, json, copy)?;
+    if json {
+        eprintln!("{}", summary.json("decode"));
+    } else if summary.stats.corrected_bits > 0 {
+        eprintln!(
+            "hamming: corrected {} bit errors",
+            summary.stats.corrected_bits
+        );
+    }
+    Ok(())
+}
+
+/// Code INPUT to OUTPUT with `copy`
+///
+/// `--interleave` and `--scramble` wrap the code in the corresponding link
+/// stages. Binary input without them is streamed. `--resume` keeps the whole
+/// units already in OUTPUT and continues from the matching input offset, so
+/// an interrupted run over a large file need not start over. With `json`
+/// the progress notes on stderr are left to the caller's report.
+fn run(
+    mut args: Args,
+    mut noise: Noise,
+    decoding: bool,
+    json: bool,
+    copy: Copy,
+) -> Result<Summary, Box<dyn std::error::Error>> {
+    let code = args
+        .value("code")?
+        .ok_or_else(|| UsageError("missing --code".into()))?;
+    let spec = parse_code(&code)?;
+    let in_format = DataFormat::parse(args.value("in-format")?.as_deref())?;
+    let out_format = DataFormat::parse(args.value("out-format")?.as_deref())?;
+    let stages = Stages::from_args(&mut args)?;
+    let progress = args.flag(&["--progress"]);
+    let resume = args.flag(&["--resume"]);
+    let positional = args.finish()?;
+
+    let (input, output) = match positional.as_slice() {
+        [] => ("-", "-"),
+        [input] => (input.as_str(), "-"),
+        [input, output] => (input.as_str(), output.as_str()),
+        _ => return Err(UsageError("too many arguments".into()).into()),
+    };
+    let streaming = in_format == DataFormat::Binary && noise.is_empty() && stages.is_empty();
+    if resume && (input == "-" || output == "-" || !streaming || out_format != DataFormat::Binary) {
+        return Err(UsageError("--resume needs binary INPUT and OUTPUT files".into()).into());
+    }
+
+    let code = spec.build();
+    let mut start = 0;
+    let mut writer: Box<dyn Write> = match output {
+        "-" => Box::new(BufWriter::new(io::stdout().lock())),
+        path if resume => {
+            let (data_unit, encoded_unit) = code.chunk_sizes();
+            let (in_unit, out_unit) = if decoding {
+                (encoded_unit, data_unit)
+            } else {
+                (data_unit, encoded_unit)
+            };
+
+            // Keep the whole units already written and drop a partial one
+            let mut file = OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(false)
+                .open(path)?;
+            let units = file.metadata()?.len() / out_unit as u64;
+            file.set_len(units * out_unit as u64)?;
+            file.seek(SeekFrom::End(0))?;
+            start = units * in_unit as u64;
+            if start > 0 && !json {
+                eprintln!("hamming: resuming at input byte {start}");
+            }
+            Box::new(BufWriter::new(file))
+        }
+        path => Box::new(BufWriter::new(File::create(path)?)),
+    };
+    let (mut reader, total): (Box<dyn Read>, _) = match input {
+        "-" => (Box::new(io::stdin().lock()), None),
+        path => {
+            let mut file = File::open(path)?;
+            let len = file.metadata()?.len();
+            file.seek(SeekFrom::Start(start))?;
+            (Box::new(file), Some(len))
+        }
+    };
+    if progress {
+        reader = Box::new(Progress::new(reader, start, total));
+    }
+    // Encoded output past the end of the input already holds the terminator
+    if !decoding && total.is_some_and(|len| start > len) {
+        return Ok(Summary {
+            spec,
+            stats: CopyStats::default(),
+            injected: 0,
+            resumed_at: start,
+        });
+    }
+
+    // Text formats, noise and link stages are applied in memory
+    let mut injected = 0;
+    if !streaming {
+        let mut text = Vec::new();
+        reader.read_to_end(&mut text)?;
+        let mut data = in_format
+            .decode(&text)
+            .map_err(|err| format!("{}: {err}", if input == "-" { "stdin" } else { input }))?;
+        if !noise.is_empty() {
+            injected = noise.apply(&mut data);
+            if !json {
+                eprintln!("hamming: injected {injected} bit errors");
+            }
+        }
+        reader = Box::new(Cursor::new(stages.before(data, decoding)));
+    }
+    let stats = if out_format == DataFormat::Binary && stages.is_empty() {
+        copy(&mut reader, &mut writer, code.as_ref())?
+    } else {
+        let mut data = Vec::new();
+        let stats = copy(&mut reader, &mut data, code.as_ref())?;
+        let data = stages.after(data, decoding);
+        writer.write_all(&out_format.encode(&data))?;
+        stats
+    };
+    writer.flush()?;
+    Ok(Summary {
+        spec,
+        stats,
+        injected,
+        resumed_at: start,
+    })
+}
+
+// Decode in one piece, printing the received bits (codeword position 0
+// first), syndrome and outcome of every block
+fn decode_verbose(
+    input: &mut dyn Read,
+    output: &mut dyn Write,
+    code: &dyn HammingCode,
+) -> io::Result<CopyStats> {
+    let mut encoded = Vec::new();
+    input.read_to_end(&mut encoded)?;
+    let mut stats = CopyStats {
+        bytes_read: encoded.len() as u64,
+        ..CopyStats::default()
+    };
+
+    let result = code.decode_observed(&encoded, &mut |event: &BlockEvent| {
+        let received: String = (event.bit_offset..event.bit_offset + code.block_size())
+            .map(
+                |bit| match encoded.get(bit / 8).map(|b| b >> (bit % 8) & 1) {
+                    Some(1) => '1',
+                    _ => '0',
+                },
+            )
+            .collect();
+        let outcome = match event.outcome {
+            BlockOutcome::Clean => "clean".to_string(),
+            BlockOutcome::Corrected { bit } => {
+                stats.corrected_bits += 1;
+                format!("corrected bit {bit}")
+            }
+            BlockOutcome::Uncorrectable => "uncorrectable".to_string(),
+        };
+        eprintln!(
+            "block {:>6}  received {received}  syndrome {:#06x}  {outcome}",
+            event.block, event.syndrome
+        );
+    });
+
+    let mut data = result?;
+    while data.last() == Some(&0) {
+        data.pop();
+    }
+    if data.pop() != Some(TERMINATOR) {
+        return Err(HammingError::MissingTerminator.into());
+    }
+    output.write_all(&data)?;
+    stats.bytes_written = data.len() as u64;
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SAMPLE_CODES;
+
+    fn args(args: &[&str]) -> Args {
+        Args::new(args.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn test_file_round_trip() {
+        let dir = std::env::temp_dir();
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 31 + 7) as u8).collect();
+        let input = dir.join(format!("hamming-cli-{}.bin", std::process::id()));
+        std::fs::write(&input, &data).unwrap();
+
+        for code in SAMPLE_CODES {
+            let encoded = input.with_extension("ecc");
+            let decoded = input.with_extension("out");
+            let (input, encoded, decoded) = (
+                input.to_str().unwrap(),
+                encoded.to_str().unwrap(),
+                decoded.to_str().unwrap(),
+            );
+            encode(args(&["--code", code, input, encoded])).unwrap();
+            decode(args(&["--code", code, encoded, decoded])).unwrap();
+            assert_eq!(std::fs::read(decoded).unwrap(), data, "code {code}");
+
+            decode(args(&["--code", code, "-v", encoded, decoded])).unwrap();
+            assert_eq!(std::fs::read(decoded).unwrap(), data, "code {code} -v");
+
+            std::fs::remove_file(encoded).unwrap();
+            std::fs::remove_file(decoded).unwrap();
+        }
+        std::fs::remove_file(input).unwrap();
+    }
+}
@@ -0,0 +1,138 @@
+use crate::args::{Args, UsageError};
+use crate::{CliResult, parse_code};
+use hamming_rs::BlockOutcome;
+use std::fmt::Write;
+
+pub const USAGE: &str = "hamming compare ORIGINAL DECODED [--encoded ENCODED --code CODE]";
+
+// Entries listed per section before the rest is summarized
+const MAX_LINES: usize = 20;
+
+/// Post-mortem: where the decoder corrected ENCODED, and whether DECODED
+/// matches ORIGINAL
+///
+/// Exits with an error when the files differ.
+pub fn run(mut args: Args) -> CliResult {
+    let encoded = args.value("encoded")?;
+    let code = args.value("code")?;
+    let [original, decoded] = <[String; 2]>::try_from(args.finish()?)
+        .map_err(|_| UsageError("expected ORIGINAL and DECODED".into()))?;
+
+    let mut out = String::new();
+    if let Some(path) = encoded {
+        let code = code.ok_or_else(|| UsageError("--encoded needs --code".into()))?;
+        let codec = parse_code(&code)?.build();
+        let encoded = std::fs::read(&path)?;
+
+        let mut lines = Vec::new();
+        let result = codec.decode_observed(&encoded, &mut |event| {
+            let data_bits = event.block * codec.data_bits();
+            let data_bytes = data_bits / 8..(data_bits + codec.data_bits()).div_ceil(8);
+            match event.outcome {
+                BlockOutcome::Clean => {}
+                BlockOutcome::Corrected { bit } => lines.push(format!(
+                    "  block {}: corrected bit {bit} (encoded byte {}), data bytes {data_bytes:?}",
+                    event.block,
+                    (event.bit_offset + bit) / 8
+                )),
+                BlockOutcome::Uncorrectable => lines.push(format!(
+                    "  block {}: uncorrectable, syndrome {:#x}, data bytes {data_bytes:?}",
+                    event.block, event.syndrome
+                )),
+            }
+        });
+        let _ = writeln!(out, "{path} ({code}): {} corrections", lines.len());
+        list(&mut out, &lines);
+        if let Err(err) = result {
+            let _ = writeln!(out, "  decoding stopped: {err}");
+        }
+    }
+
+    let original_data = std::fs::read(&original)?;
+    let decoded_data = std::fs::read(&decoded)?;
+    let (matches, report) = compare(&original_data, &decoded_data);
+    let _ = writeln!(out, "{original} vs {decoded}:");
+    out += &report;
+    print!("{out}");
+
+    if matches {
+        Ok(())
+    } else {
+        Err(format!("{decoded} does not match {original}").into())
+    }
+}
+
+// Byte-level differences, allowing zero padding at the end of `decoded`
+fn compare(original: &[u8], decoded: &[u8]) -> (bool, String) {
+    let mut out = String::new();
+    let diffs: Vec<String> = original
+        .iter()
+        .zip(decoded)
+        .enumerate()
+        .filter(|(_, (a, b))| a != b)
+        .map(|(offset, (a, b))| {
+            format!(
+                "  offset {offset:#x}: {a:02x} -> {b:02x} (bits {:08b} differ)",
+                a ^ b
+            )
+        })
+        .collect();
+
+    let mut matches = diffs.is_empty();
+    let _ = writeln!(out, "  {} differing bytes", diffs.len());
+    list(&mut out, &diffs);
+
+    match decoded.get(original.len()..) {
+        Some(extra) if extra.iter().all(|&b| b == 0) => {
+            if !extra.is_empty() {
+                let _ = writeln!(out, "  decoded has {} bytes of zero padding", extra.len());
+            }
+        }
+        Some(extra) => {
+            matches = false;
+            let _ = writeln!(out, "  decoded has {} extra bytes", extra.len());
+        }
+        None => {
+            matches = false;
+            let _ = writeln!(
+                out,
+                "  decoded is {} bytes short",
+                original.len() - decoded.len()
+            );
+        }
+    }
+    let _ = writeln!(
+        out,
+        "  result: {}",
+        if matches { "MATCH" } else { "MISMATCH" }
+    );
+    (matches, out)
+}
+
+fn list(out: &mut String, lines: &[String]) {
+    for line in lines.iter().take(MAX_LINES) {
+        let _ = writeln!(out, "{line}");
+    }
+    if lines.len() > MAX_LINES {
+        let _ = writeln!(out, "  ... {} more", lines.len() - MAX_LINES);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare() {
+        let (matches, report) = compare(b"abc", b"abc\0\0");
+        assert!(matches);
+        assert!(report.contains("2 bytes of zero padding"));
+
+        let (matches, report) = compare(b"abc", b"abd");
+        assert!(!matches);
+        assert!(report.contains("offset 0x2: 63 -> 64 (bits 00000111 differ)"));
+
+        assert!(!compare(b"abc", b"ab").0);
+        assert!(!compare(b"abc", b"abcd").0);
+    }
+}
@@ -0,0 +1,21 @@
+use crate::args::{Args, UsageError};
+use crate::format::DataFormat;
+use crate::{CliResult, parse_code};
+use hamming_rs::BlockDiagram;
+
+pub const USAGE: &str = "hamming explain --code CODE [--in-format F] [DATA]";
+
+/// Print the block layout of a code, and the blocks DATA encodes to
+pub fn run(mut args: Args) -> CliResult {
+    let spec = parse_code(&args.value("code")?.unwrap_or_else(|| "74".into()))?;
+    let format = DataFormat::parse(args.value("in-format")?.as_deref())?;
+    let positional = args.finish()?;
+
+    let data = match positional.as_slice() {
+        [] => Vec::new(),
+        [data] => format.decode(data.as_bytes())?,
+        _ => return Err(UsageError("too many arguments".into()).into()),
+    };
+    print!("{}", BlockDiagram::new(&spec)?.render(&data));
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use crate::args::UsageError;
+use std::fmt::Write;
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Representation of data read or written by `encode`/`decode`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataFormat {
+    Binary,
+    Hex,
+    Base64,
+}
+
+impl DataFormat {
+    /// Parse a `--in-format`/`--out-format` value, defaulting to binary
+    pub fn parse(format: Option<&str>) -> Result<Self, UsageError> {
+        match format {
+            None | Some("binary") => Ok(DataFormat::Binary),
+            Some("hex") => Ok(DataFormat::Hex),
+            Some("base64") => Ok(DataFormat::Base64),
+            Some(other) => Err(UsageError(format!("unknown data format {other}"))),
+        }
+    }
+
+    /// Bytes from their text form; whitespace is ignored
+    pub fn decode(self, text: &[u8]) -> Result<Vec<u8>, String> {
+        let digits: Vec<u8> = text
+            .iter()
+            .copied()
+            .filter(|c| !c.is_ascii_whitespace())
+            .collect();
+        match self {
+            DataFormat::Binary => Ok(text.to_vec()),
+            DataFormat::Hex => decode_hex(&digits),
+            DataFormat::Base64 => decode_base64(&digits),
+        }
+    }
+
+    /// Text form of bytes, newline terminated
+    pub fn encode(self, data: &[u8]) -> Vec<u8> {
+        let mut text = match self {
+            DataFormat::Binary => return data.to_vec(),
+            DataFormat::Hex => data.iter().fold(String::new(), |mut hex, b| {
+                let _ = write!(hex, "{b:02x}");
+                hex
+            }),
+            DataFormat::Base64 => encode_base64(data),
+        };
+        text.push('\n');
+        text.into_bytes()
+    }
+}
+
+fn decode_hex(digits: &[u8]) -> Result<Vec<u8>, String> {
+    let (pairs, rest) = digits.as_chunks::<2>();
+    if !rest.is_empty() {
+        return Err("odd number of hex digits".into());
+    }
+    pairs
+        .iter()
+        .map(|pair| {
+            let digit = |c: u8| (c as char).to_digit(16).ok_or("invalid hex digit");
+            Ok((digit(pair[0])? << 4 | digit(pair[1])?) as u8)
+        })
+        .collect()
+}
+
+fn encode_base64(data: &[u8]) -> String {
+    let mut text = String::new();
+    for chunk in data.chunks(3) {
+        let bytes = [0, 1, 2].map(|i| chunk.get(i).copied().unwrap_or(0));
+        let group = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                text.push(BASE64[(group >> (18 - 6 * i)) as usize & 63] as char);
+            } else {
+                text.push('=');
+            }
+        }
+    }
+    text
+}
+
+fn decode_base64(digits: &[u8]) -> Result<Vec<u8>, String> {
+    let (groups, rest) = digits.as_chunks::<4>();
+    if !rest.is_empty() {
+        return Err("base64 length is not a multiple of 4".into());
+    }
+
+    let mut data = Vec::with_capacity(groups.len() * 3);
+    for (n, group) in groups.iter().enumerate() {
+        let padding = group.iter().rev().take_while(|&&c| c == b'=').count();
+        if padding > 2 || (padding > 0 && n + 1 != groups.len()) {
+            return Err("misplaced base64 padding".into());
+        }
+        let mut value = 0u32;
+        for &c in &group[..4 - padding] {
+            let digit = BASE64
+                .iter()
+                .position(|&b| b == c)
+                .ok_or("invalid base64 character")?;
+            value = value << 6 | digit as u32;
+        }
+        value <<= 6 * padding;
+        data.extend_from_slice(&value.to_be_bytes()[1..4 - padding]);
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        for data in [&b""[..], b"f", b"fo", b"foo", b"foob", &[0x00, 0xff, 0x7e]] {
+            for format in [DataFormat::Binary, DataFormat::Hex, DataFormat::Base64] {
+                assert_eq!(format.decode(&format.encode(data)).unwrap(), data);
+            }
+        }
+        assert_eq!(DataFormat::Base64.encode(b"foob"), b"Zm9vYg==\n");
+        assert_eq!(DataFormat::Hex.encode(b"\x07\x2a"), b"072a\n");
+    }
+
+    #[test]
+    fn test_invalid_text() {
+        assert_eq!(
+            DataFormat::Hex.decode(b"07 2A\n").unwrap(),
+            vec![0x07, 0x2a]
+        );
+        assert!(DataFormat::Hex.decode(b"072").is_err());
+        assert!(DataFormat::Hex.decode(b"0g").is_err());
+        assert!(DataFormat::Hex.decode(b"+f").is_err());
+        assert!(DataFormat::Base64.decode(b"Zm9").is_err());
+        assert!(DataFormat::Base64.decode(b"Zg==Zg==").is_err());
+        assert!(DataFormat::Base64.decode(b"Zm9!").is_err());
+    }
+}
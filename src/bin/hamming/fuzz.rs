@@ -0,0 +1,152 @@
+use crate::args::{Args, UsageError};
+use crate::{CliResult, parse_code};
+use hamming_rs::{BinarySymmetricChannel, HammingCode};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const USAGE: &str =
+    "hamming fuzz [--iterations N] [--seed S] [--max-bytes N] [--code CODE[,CODE...]]";
+
+// Codes round-tripped when no --code is given
+const DEFAULT_CODES: &str = "74,1511,general:4,general:11,general:26,general:57,secded:8,secded:64";
+
+/// Round-trip random payloads with random correctable errors through each
+/// code
+///
+/// Iteration `i` draws everything from seed `S + i`, so a mismatch is
+/// reproduced by `--seed S+i --iterations 1` with the same code. Without
+/// `--seed` a fresh one is taken from the clock and printed.
+pub fn run(mut args: Args) -> CliResult {
+    let iterations: u64 = args.number("iterations", 1000)?;
+    let max_bytes: usize = args.number("max-bytes", 256)?;
+    let seed = match args.value("seed")? {
+        Some(seed) => seed
+            .parse()
+            .map_err(|_| UsageError(format!("invalid --seed {seed}")))?,
+        None => {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default();
+            let seed = now.as_nanos() as u64;
+            println!("seed {seed}");
+            seed
+        }
+    };
+    let codes = args
+        .value("code")?
+        .unwrap_or_else(|| DEFAULT_CODES.into())
+        .split(',')
+        .map(parse_code)
+        .collect::<Result<Vec<_>, _>>()?;
+    if !args.finish()?.is_empty() {
+        return Err(UsageError("unexpected argument".into()).into());
+    }
+
+    let mut mismatches = 0;
+    for spec in &codes {
+        let (code, name) = (spec.build(), spec.to_string());
+        let mut flipped = 0;
+        for i in 0..iterations {
+            let iteration_seed = seed.wrapping_add(i);
+            match round_trip(code.as_ref(), max_bytes, iteration_seed) {
+                Ok(bits) => flipped += bits,
+                Err(err) => {
+                    mismatches += 1;
+                    println!(
+                        "{name:<12} MISMATCH {err}; reproduce with: hamming fuzz --code {spec} --seed {iteration_seed} --iterations 1 --max-bytes {max_bytes}"
+                    );
+                }
+            }
+        }
+        println!("{name:<12} {iterations} iterations, {flipped} injected bit errors");
+    }
+
+    if mismatches > 0 {
+        return Err(format!("{mismatches} round trips failed").into());
+    }
+    Ok(())
+}
+
+/// One iteration: a random payload of up to `max_bytes`, encoded plain and
+/// terminated, with at most one flipped bit in each block. Returns the
+/// number of flipped bits.
+fn round_trip(code: &dyn HammingCode, max_bytes: usize, seed: u64) -> Result<usize, String> {
+    let mut channel = BinarySymmetricChannel::new(0.5, seed);
+    let len = below(&mut channel, max_bytes + 1);
+    let mut data = vec![0u8; len];
+    channel.corrupt(&mut data);
+
+    let mut encoded = code.encode(&data);
+    let mut flipped = inject(code, &mut encoded, &mut channel);
+    let decoded = code
+        .decode(&encoded)
+        .map_err(|err| format!("decode of {len} bytes: {err}"))?;
+    // Decoding may add zero padding up to the last block
+    let (payload, padding) = decoded.split_at(len.min(decoded.len()));
+    if payload != data || padding.iter().any(|&b| b != 0) {
+        return Err(format!("decode of {len} bytes returned different data"));
+    }
+
+    let mut terminated = code.encode_terminated(&data);
+    flipped += inject(code, &mut terminated, &mut channel);
+    match code.decode_terminated(&terminated) {
+        Ok(decoded) if decoded == data => Ok(flipped),
+        Ok(_) => Err(format!(
+            "decode_terminated of {len} bytes returned different data"
+        )),
+        Err(err) => Err(format!("decode_terminated of {len} bytes: {err}")),
+    }
+}
+
+// Flip one random bit in a random half of the blocks of `encoded`
+fn inject(
+    code: &dyn HammingCode,
+    encoded: &mut [u8],
+    channel: &mut BinarySymmetricChannel,
+) -> usize {
+    let (n, stride) = (code.block_size(), code.block_stride());
+    let mut flipped = 0;
+    for block in 0..encoded.len() * 8 / stride {
+        if below(channel, 2) == 1 {
+            let offset = block * stride;
+            flipped += channel.flip_distinct(encoded, offset..offset + n, 1);
+        }
+    }
+    flipped
+}
+
+// Uniform-ish value in `0..n` from the channel's bits
+fn below(channel: &mut BinarySymmetricChannel, n: usize) -> usize {
+    let mut bytes = [0u8; 8];
+    channel.corrupt(&mut bytes);
+    (u64::from_le_bytes(bytes) % n.max(1) as u64) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hamming_rs::CodeSpec;
+
+    #[test]
+    fn test_round_trips() {
+        for spec in DEFAULT_CODES.split(',') {
+            let code = spec.parse::<CodeSpec>().unwrap().build();
+            let flipped: usize = (0..20)
+                .map(|seed| round_trip(code.as_ref(), 64, seed).unwrap())
+                .sum();
+            assert!(flipped > 0, "{spec}");
+        }
+    }
+
+    #[test]
+    fn test_iteration_reproducible() {
+        let code = CodeSpec::Hamming1511.build();
+        let mut a = BinarySymmetricChannel::new(0.5, 9);
+        let mut b = BinarySymmetricChannel::new(0.5, 9);
+        let mut data = vec![0u8; 30];
+        let mut other = data.clone();
+        inject(code.as_ref(), &mut data, &mut a);
+        inject(code.as_ref(), &mut other, &mut b);
+        assert_eq!(data, other);
+        assert!(data.iter().any(|&b| b != 0));
+    }
+}
@@ -0,0 +1,24 @@
+use crate::args::{Args, UsageError};
+use crate::{CliResult, parse_code};
+use hamming_rs::{HdlLanguage, ParityEquations};
+
+pub const USAGE: &str = "hamming hdl --code CODE [--lang verilog|vhdl] [OUTPUT]";
+
+/// Print (or write to OUTPUT) an HDL encoder and decoder for a code
+pub fn run(mut args: Args) -> CliResult {
+    let spec = parse_code(&args.value("code")?.unwrap_or_else(|| "74".into()))?;
+    let language = match args.value("lang")?.as_deref() {
+        None | Some("verilog") => HdlLanguage::Verilog,
+        Some("vhdl") => HdlLanguage::Vhdl,
+        Some(other) => return Err(UsageError(format!("unknown language {other}")).into()),
+    };
+    let positional = args.finish()?;
+
+    let source = ParityEquations::new(&spec)?.render(language);
+    match positional.as_slice() {
+        [] => print!("{source}"),
+        [path] => std::fs::write(path, source)?,
+        _ => return Err(UsageError("too many arguments".into()).into()),
+    }
+    Ok(())
+}
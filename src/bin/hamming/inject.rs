@@ -0,0 +1,163 @@
+use crate::args::{Args, UsageError};
+use crate::json::Json;
+use crate::{CliResult, parse_code};
+use hamming_rs::{BinarySymmetricChannel, BlockOutcome, HammingCode};
+use std::io::Read;
+
+pub const USAGE: &str = "hamming inject --code CODE --errors K [--seed S] [--json] [INPUT|-]";
+
+/// What happened to a block carrying injected errors
+#[derive(Debug, Default, PartialEq)]
+struct Tally {
+    /// Decoded to the original data
+    corrected: u64,
+    /// Rejected as uncorrectable
+    detected: u64,
+    /// "Corrected" to the wrong data
+    miscorrected: u64,
+    /// Passed as clean with the wrong data
+    undetected: u64,
+}
+
+/// Encode INPUT, flip K distinct random bits in every block and report how
+/// the decoder handled each block
+pub fn run(mut args: Args) -> CliResult {
+    let spec = parse_code(
+        &args
+            .value("code")?
+            .ok_or_else(|| UsageError("missing --code".into()))?,
+    )?;
+    let errors: usize = args
+        .value("errors")?
+        .ok_or_else(|| UsageError("missing --errors".into()))?
+        .parse()
+        .map_err(|_| UsageError("invalid --errors".into()))?;
+    let json = args.flag(&["--json"]);
+    let seed: u64 = match args.value("seed")? {
+        None => 1,
+        Some(seed) => seed
+            .parse()
+            .map_err(|_| UsageError(format!("invalid --seed {seed}")))?,
+    };
+
+    let mut data = Vec::new();
+    match args.finish()?.as_slice() {
+        [] => drop(std::io::stdin().read_to_end(&mut data)?),
+        [path] if path == "-" => drop(std::io::stdin().read_to_end(&mut data)?),
+        [path] => data = std::fs::read(path)?,
+        _ => return Err(UsageError("too many arguments".into()).into()),
+    }
+
+    let code = spec.build();
+    let mut channel = BinarySymmetricChannel::new(0.0, seed);
+    let tally = experiment(code.as_ref(), &data, errors, &mut channel);
+    let blocks = tally.corrected + tally.detected + tally.miscorrected + tally.undetected;
+    let percent = |n: u64| {
+        if blocks == 0 {
+            0.0
+        } else {
+            n as f64 * 100.0 / blocks as f64
+        }
+    };
+
+    let outcomes = [
+        ("corrected", tally.corrected),
+        ("detected", tally.detected),
+        ("miscorrected", tally.miscorrected),
+        ("undetected", tally.undetected),
+    ];
+    if json {
+        let mut fields = vec![
+            ("code", spec.to_string().into()),
+            ("errors", errors.into()),
+            ("blocks", blocks.into()),
+        ];
+        fields.extend(outcomes.map(|(label, n)| (label, n.into())));
+        println!("{}", Json::Object(fields));
+        return Ok(());
+    }
+
+    println!("code {spec}, {errors} errors in each of {blocks} blocks");
+    for (label, n) in outcomes {
+        println!("  {label:<13}{n:>10}  {:6.2}%", percent(n));
+    }
+    Ok(())
+}
+
+// Every block is decoded in a copy of its decode unit (see
+// `HammingCode::chunk_sizes`) with only that block corrupted, so one
+// uncorrectable block does not hide the others
+fn experiment(
+    code: &dyn HammingCode,
+    data: &[u8],
+    errors: usize,
+    channel: &mut BinarySymmetricChannel,
+) -> Tally {
+    let encoded = code.encode(data);
+    let (n, k, stride) = (code.block_size(), code.data_bits(), code.block_stride());
+    let (_, unit) = code.chunk_sizes();
+    let unit_blocks = unit * 8 / stride;
+    let blocks = (data.len() * 8).div_ceil(k);
+    let bit = |bytes: &[u8], i: usize| bytes.get(i / 8).is_some_and(|b| b >> (i % 8) & 1 == 1);
+
+    let mut tally = Tally::default();
+    for block in 0..blocks {
+        let (start, index) = (block / unit_blocks * unit, block % unit_blocks);
+        let mut received = encoded
+            .get(start..(start + unit).min(encoded.len()))
+            .unwrap_or_default()
+            .to_vec();
+        let offset = index * stride;
+        channel.flip_distinct(&mut received, offset..offset + n, errors);
+
+        let mut outcome = BlockOutcome::Clean;
+        let result = code.decode_observed(&received, &mut |event| {
+            if event.block == index {
+                outcome = event.outcome;
+            }
+        });
+        let intact = |decoded: &[u8]| {
+            (0..k).all(|i| bit(decoded, index * k + i) == bit(data, block * k + i))
+        };
+        match (result, outcome) {
+            (Err(_), _) => tally.detected += 1,
+            (Ok(decoded), _) if intact(&decoded) => tally.corrected += 1,
+            (Ok(_), BlockOutcome::Clean) => tally.undetected += 1,
+            (Ok(_), _) => tally.miscorrected += 1,
+        }
+    }
+    tally
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SAMPLE_CODES;
+    use hamming_rs::CodeSpec;
+
+    fn run_experiment(spec: &str, errors: usize) -> Tally {
+        let data: Vec<u8> = (0..=255).collect();
+        let code = spec.parse::<CodeSpec>().unwrap().build();
+        experiment(
+            code.as_ref(),
+            &data,
+            errors,
+            &mut BinarySymmetricChannel::new(0.0, 9),
+        )
+    }
+
+    #[test]
+    fn test_experiment() {
+        // Single errors are always corrected
+        for spec in SAMPLE_CODES {
+            let tally = run_experiment(spec, 1);
+            assert_eq!(tally.detected + tally.miscorrected + tally.undetected, 0);
+        }
+
+        // A perfect code miscorrects every double error, SEC-DED detects them
+        let tally = run_experiment("74", 2);
+        assert_eq!(tally.miscorrected, 512);
+        let tally = run_experiment("secded:64", 2);
+        assert_eq!(tally.detected, 32);
+    }
+}
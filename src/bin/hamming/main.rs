@@ -0,0 +1,119 @@
+//! Command-line front end: `hamming <command> [options]`
+
+mod args;
+mod ber;
+mod codec;
+mod compare;
+mod explain;
+mod format;
+mod fuzz;
+mod hdl;
+mod inject;
+mod json;
+mod matrix;
+mod noise;
+mod progress;
+mod proxy;
+mod selftest;
+mod sidecar;
+mod stages;
+mod syndromes;
+mod tables;
+
+use args::{Args, UsageError};
+use hamming_rs::CodeSpec;
+use std::process::ExitCode;
+
+pub type CliResult = Result<(), Box<dyn std::error::Error>>;
+
+/// Parse a `--code` value: `74`, `1511`, `general:<data bits>` or
+/// `secded:<data bits>`
+pub fn parse_code(code: &str) -> Result<CodeSpec, UsageError> {
+    code.parse()
+        .map_err(|_| UsageError(format!("unknown code {code}")))
+}
+
+/// One `--code` value of each kind, for the command tests
+#[cfg(test)]
+const SAMPLE_CODES: [&str; 4] = ["74", "1511", "general:26", "secded:64"];
+
+const COMMANDS: &[&str] = &[
+    codec::ENCODE_USAGE,
+    codec::DECODE_USAGE,
+    compare::USAGE,
+    tables::USAGE,
+    syndromes::USAGE,
+    hdl::USAGE,
+    matrix::USAGE,
+    explain::USAGE,
+    ber::USAGE,
+    inject::USAGE,
+    selftest::USAGE,
+    fuzz::USAGE,
+    proxy::USAGE,
+    sidecar::PROTECT_USAGE,
+    sidecar::REPAIR_USAGE,
+];
+
+fn usage() -> String {
+    COMMANDS
+        .iter()
+        .fold(String::from("usage:\n"), |usage, command| {
+            usage + "  " + command + "\n"
+        })
+}
+
+fn main() -> ExitCode {
+    let mut argv = std::env::args().skip(1);
+    let command = argv.next().unwrap_or_default();
+    let argv: Vec<String> = argv.collect();
+    // Commands taking --json also want their errors as JSON
+    let json = argv.iter().any(|arg| arg == "--json");
+    let args = Args::new(argv);
+
+    let result = match command.as_str() {
+        "encode" => codec::encode(args),
+        "decode" => codec::decode(args),
+        "compare" => compare::run(args),
+        "tables" => tables::run(args),
+        "syndromes" => syndromes::run(args),
+        "hdl" => hdl::run(args),
+        "matrix" => matrix::run(args),
+        "explain" => explain::run(args),
+        "ber" => ber::run(args),
+        "inject" => inject::run(args),
+        "selftest" => selftest::run(args),
+        "fuzz" => fuzz::run(args),
+        "proxy" => proxy::run(args),
+        "protect" => sidecar::protect(args),
+        "repair" => sidecar::repair(args),
+        "help" | "--help" | "-h" => {
+            print!("{}", usage());
+            return ExitCode::SUCCESS;
+        }
+        "" => Err(UsageError("missing command".into()).into()),
+        other => Err(UsageError(format!("unknown command {other}")).into()),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) if json => {
+            let usage = err.is::<UsageError>();
+            let error = json::object([("error", err.to_string().into()), ("usage", usage.into())]);
+            eprintln!("{error}");
+            if usage {
+                ExitCode::from(2)
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Err(err) if err.is::<UsageError>() => {
+            eprintln!("hamming: {err}\n{}", usage());
+            ExitCode::from(2)
+        }
+        Err(err) => {
+            eprintln!("hamming: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use crate::args::{Args, UsageError};
+use crate::{CliResult, parse_code};
+use hamming_rs::CodeMatrices;
+
+pub const USAGE: &str = "hamming matrix --code CODE";
+
+/// Print the generator and parity-check matrices of a code
+pub fn run(mut args: Args) -> CliResult {
+    let spec = parse_code(&args.value("code")?.unwrap_or_else(|| "74".into()))?;
+    if !args.finish()?.is_empty() {
+        return Err(UsageError("unexpected argument".into()).into());
+    }
+    print!("{}", CodeMatrices::new(&spec)?.render());
+    Ok(())
+}
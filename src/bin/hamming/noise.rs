@@ -0,0 +1,132 @@
+use crate::args::{Args, UsageError};
+use hamming_rs::{BinarySymmetricChannel, flip_bit};
+
+/// Errors injected into encoded input before decoding
+#[derive(Debug, Default)]
+pub struct Noise {
+    channel: Option<BinarySymmetricChannel>,
+    flips: Vec<(usize, u32)>,
+    /// First bit and length of each run of flipped bits
+    bursts: Vec<(usize, usize)>,
+}
+
+impl Noise {
+    /// Take `--corrupt ber=B[,seed=S]` and any number of `--flip BYTE:BIT`
+    /// and `--burst START,LEN`
+    pub fn from_args(args: &mut Args) -> Result<Self, UsageError> {
+        let channel = args
+            .value("corrupt")?
+            .map(|spec| parse_corrupt(&spec))
+            .transpose()?;
+        let flips = args
+            .values("flip")?
+            .iter()
+            .map(|flip| parse_flip(flip))
+            .collect::<Result<_, _>>()?;
+        let bursts = args
+            .values("burst")?
+            .iter()
+            .map(|burst| parse_burst(burst))
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            channel,
+            flips,
+            bursts,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.channel.is_none() && self.flips.is_empty() && self.bursts.is_empty()
+    }
+
+    /// Corrupt `data`, returning the number of flipped bits
+    pub fn apply(&mut self, data: &mut [u8]) -> usize {
+        for &(byte, bit) in &self.flips {
+            flip_bit(data, byte, bit);
+        }
+        // Bits past the end of the data are not flipped or counted
+        let (total_bits, mut burst_bits) = (data.len() * 8, 0);
+        for &(start, len) in &self.bursts {
+            for bit in start..start.saturating_add(len).min(total_bits) {
+                flip_bit(data, bit / 8, (bit % 8) as u32);
+                burst_bits += 1;
+            }
+        }
+        let random = self
+            .channel
+            .as_mut()
+            .map_or(0, |channel| channel.corrupt(data));
+        self.flips.len() + burst_bits + random
+    }
+}
+
+fn parse_corrupt(spec: &str) -> Result<BinarySymmetricChannel, UsageError> {
+    let invalid = || UsageError(format!("invalid --corrupt {spec}"));
+    let (mut ber, mut seed) = (None, 0);
+    for field in spec.split(',') {
+        match field.split_once('=') {
+            Some(("ber", value)) => ber = Some(value.parse::<f64>().map_err(|_| invalid())?),
+            Some(("seed", value)) => seed = value.parse().map_err(|_| invalid())?,
+            _ => return Err(invalid()),
+        }
+    }
+    match ber {
+        Some(ber) if (0.0..=1.0).contains(&ber) => Ok(BinarySymmetricChannel::new(ber, seed)),
+        _ => Err(invalid()),
+    }
+}
+
+fn parse_flip(flip: &str) -> Result<(usize, u32), UsageError> {
+    flip.split_once(':')
+        .and_then(|(byte, bit)| Some((byte.parse().ok()?, bit.parse().ok()?)))
+        .filter(|&(_, bit)| bit < 8)
+        .ok_or_else(|| UsageError(format!("invalid --flip {flip}, expected BYTE:BIT")))
+}
+
+// `START,LEN`: LEN bits from bit offset START (LSB-first within bytes)
+fn parse_burst(burst: &str) -> Result<(usize, usize), UsageError> {
+    burst
+        .split_once(',')
+        .and_then(|(start, len)| Some((start.parse().ok()?, len.parse().ok()?)))
+        .filter(|&(_, len)| len > 0)
+        .ok_or_else(|| UsageError(format!("invalid --burst {burst}, expected START,LEN")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_noise() {
+        assert_eq!(parse_flip("12:7").unwrap(), (12, 7));
+        assert!(parse_flip("12:8").is_err());
+        assert!(parse_flip("12").is_err());
+
+        assert_eq!(parse_corrupt("ber=1e-3,seed=42").unwrap().ber(), 1e-3);
+        assert!(parse_corrupt("seed=42").is_err());
+        assert!(parse_corrupt("ber=2").is_err());
+        assert!(parse_corrupt("ber=0.1,rate=2").is_err());
+
+        assert_eq!(parse_burst("100,8").unwrap(), (100, 8));
+        assert!(parse_burst("100,0").is_err());
+        assert!(parse_burst("100").is_err());
+    }
+
+    #[test]
+    fn test_apply_flips() {
+        let mut args = Args::new(["--flip", "0:0", "--flip=2:7"].map(String::from).to_vec());
+        let mut noise = Noise::from_args(&mut args).unwrap();
+        let mut data = [0u8; 3];
+        assert_eq!(noise.apply(&mut data), 2);
+        assert_eq!(data, [0x01, 0x00, 0x80]);
+
+        let mut args = Args::new(
+            ["--burst", "4,8", "--burst=20,100"]
+                .map(String::from)
+                .to_vec(),
+        );
+        let mut noise = Noise::from_args(&mut args).unwrap();
+        assert_eq!(noise.apply(&mut data), 12);
+        assert_eq!(data, [0xF1, 0x0F, 0x70]);
+    }
+}
@@ -0,0 +1,93 @@
+use std::io::{self, Read};
+use std::time::{Duration, Instant};
+
+// Minimum time between progress updates
+const INTERVAL: Duration = Duration::from_millis(250);
+
+/// Reader that reports how much of its input has been consumed on stderr
+pub struct Progress<R> {
+    inner: R,
+    position: u64,
+    total: Option<u64>,
+    last: Instant,
+}
+
+impl<R: Read> Progress<R> {
+    /// `position` is where reading starts, `total` the input size if known
+    pub fn new(inner: R, position: u64, total: Option<u64>) -> Self {
+        Self {
+            inner,
+            position,
+            total,
+            last: Instant::now(),
+        }
+    }
+}
+
+/// Progress callback for the library's file functions, reporting on stderr
+/// like [`Progress`]
+pub fn callback() -> impl FnMut(u64, u64) {
+    let mut last = Instant::now();
+    move |done, total| {
+        if done == total {
+            report(done, Some(total), "\n");
+        } else if last.elapsed() >= INTERVAL {
+            last = Instant::now();
+            report(done, Some(total), "");
+        }
+    }
+}
+
+impl<R: Read> Read for Progress<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.position += n as u64;
+        if n == 0 {
+            report(self.position, self.total, "\n");
+        } else if self.last.elapsed() >= INTERVAL {
+            self.last = Instant::now();
+            report(self.position, self.total, "");
+        }
+        Ok(n)
+    }
+}
+
+fn report(position: u64, total: Option<u64>, end: &str) {
+    let done = human(position);
+    match total {
+        Some(total) if total > 0 => eprint!(
+            "\rhamming: {done} / {} ({}%){end}",
+            human(total),
+            position * 100 / total
+        ),
+        _ => eprint!("\rhamming: {done}{end}"),
+    }
+}
+
+// Byte count with a binary unit prefix
+fn human(bytes: u64) -> String {
+    let units = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < units.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", units[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_human() {
+        assert_eq!(human(512), "512 B");
+        assert_eq!(human(1536), "1.5 KiB");
+        assert_eq!(human(60 << 30), "60.0 GiB");
+    }
+}
@@ -0,0 +1,265 @@
+use crate::args::{Args, UsageError};
+use crate::{CliResult, parse_code};
+use hamming_rs::{BlockOutcome, CodeSpec, HammingCode};
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, UdpSocket};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+pub const USAGE: &str =
+    "hamming proxy --code CODE --listen ADDR --connect ADDR --encoded listen|connect [--udp]";
+
+// Largest payload carried by one TCP frame
+const MAX_FRAME: usize = u16::MAX as usize;
+// Largest UDP datagram
+const MAX_DATAGRAM: usize = 65536;
+// Largest UDP payload over IPv4
+const MAX_UDP_PAYLOAD: usize = 65507;
+
+/// Transparent FEC shim between two programs
+///
+/// Run one proxy at each end of a noisy link, each with `--encoded` naming
+/// the side facing the link. Traffic leaving on the encoded side is
+/// encoded, traffic arriving on it is corrected and decoded. Over TCP the
+/// encoded stream is a sequence of frames, an encoded little-endian `u16`
+/// length followed by the encoded payload; UDP datagrams are encoded one by
+/// one with an end-of-stream terminator; datagrams that cannot be decoded,
+/// encoded within the UDP size limit or sent are dropped with a message.
+pub fn run(mut args: Args) -> CliResult {
+    let mut required = |name: &str| {
+        args.value(name)?
+            .ok_or_else(|| UsageError(format!("missing --{name}")))
+    };
+    let spec = parse_code(&required("code")?)?;
+    let listen = required("listen")?;
+    let connect = required("connect")?;
+    let encoded_listen = match required("encoded")?.as_str() {
+        "listen" => true,
+        "connect" => false,
+        other => return Err(UsageError(format!("invalid --encoded {other}")).into()),
+    };
+    let udp = args.flag(&["--udp"]);
+    if !args.finish()?.is_empty() {
+        return Err(UsageError("unexpected argument".into()).into());
+    }
+
+    if udp {
+        udp_proxy(spec, &listen, &connect, encoded_listen)
+    } else {
+        tcp_proxy(spec, &listen, &connect, encoded_listen)
+    }
+}
+
+fn tcp_proxy(spec: CodeSpec, listen: &str, connect: &str, encoded_listen: bool) -> CliResult {
+    let listener = TcpListener::bind(listen)?;
+    eprintln!("hamming: proxying {listen} -> {connect} ({spec})");
+
+    for client in listener.incoming() {
+        let client = client?;
+        let upstream = match TcpStream::connect(connect) {
+            Ok(upstream) => upstream,
+            Err(err) => {
+                eprintln!("hamming: {connect}: {err}");
+                continue;
+            }
+        };
+        let (encoded, plain) = if encoded_listen {
+            (client, upstream)
+        } else {
+            (upstream, client)
+        };
+
+        let (encoded_in, plain_out) = (encoded.try_clone()?, plain.try_clone()?);
+        thread::spawn(move || pump(plain, encoded, |from, to| encode_frames(spec, from, to)));
+        thread::spawn(move || {
+            pump(encoded_in, plain_out, |from, to| {
+                decode_frames(spec, from, to)
+            })
+        });
+    }
+    Ok(())
+}
+
+// Run one direction of a connection, then close the write side
+fn pump(
+    mut from: TcpStream,
+    mut to: TcpStream,
+    copy: impl FnOnce(&mut TcpStream, &mut TcpStream) -> io::Result<()>,
+) {
+    if let Err(err) = copy(&mut from, &mut to) {
+        eprintln!("hamming: {err}");
+        let _ = from.shutdown(Shutdown::Both);
+    }
+    let _ = to.shutdown(Shutdown::Write);
+}
+
+/// Encode whatever `from` delivers as frames, without waiting for whole
+/// blocks
+fn encode_frames(spec: CodeSpec, mut from: impl Read, mut to: impl Write) -> io::Result<()> {
+    let code = spec.build();
+    let mut buffer = vec![0u8; MAX_FRAME];
+    loop {
+        let n = from.read(&mut buffer)?;
+        let Some(payload) = buffer.get(..n).filter(|p| !p.is_empty()) else {
+            return Ok(());
+        };
+        to.write_all(&code.encode(&(n as u16).to_le_bytes()))?;
+        to.write_all(&code.encode(payload))?;
+        to.flush()?;
+    }
+}
+
+/// Decode frames written by [`encode_frames`]
+fn decode_frames(spec: CodeSpec, mut from: impl Read, mut to: impl Write) -> io::Result<()> {
+    let code = spec.build();
+    let mut corrected = 0u64;
+    let mut decode = |encoded: &[u8]| {
+        code.decode_observed(encoded, &mut |event| {
+            if let BlockOutcome::Corrected { .. } = event.outcome {
+                corrected += 1;
+            }
+        })
+    };
+
+    let mut header = vec![0u8; code.expansion(2).encoded_len];
+    loop {
+        match from.read_exact(&mut header) {
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => break,
+            result => result?,
+        }
+        let len = match decode(&header)?.as_slice() {
+            [lo, hi, ..] => u16::from_le_bytes([*lo, *hi]) as usize,
+            _ => return Err(io::Error::from(io::ErrorKind::InvalidData)),
+        };
+
+        let mut encoded = vec![0u8; code.expansion(len).encoded_len];
+        from.read_exact(&mut encoded)?;
+        let payload = decode(&encoded)?;
+        to.write_all(payload.get(..len).unwrap_or(&payload))?;
+        to.flush()?;
+    }
+
+    if corrected > 0 {
+        eprintln!("hamming: corrected {corrected} bit errors");
+    }
+    Ok(())
+}
+
+fn udp_proxy(spec: CodeSpec, listen: &str, connect: &str, encoded_listen: bool) -> CliResult {
+    let socket = UdpSocket::bind(listen)?;
+    let upstream = UdpSocket::bind(if connect.starts_with('[') {
+        "[::]:0"
+    } else {
+        "0.0.0.0:0"
+    })?;
+    upstream.connect(connect)?;
+    eprintln!("hamming: proxying udp {listen} -> {connect} ({spec})");
+
+    // Replies go to whoever sent the last datagram
+    let client: Arc<Mutex<Option<SocketAddr>>> = Arc::default();
+    let (socket_out, upstream_in, reply_to) =
+        (socket.try_clone()?, upstream.try_clone()?, client.clone());
+    thread::spawn(move || {
+        let code = spec.build();
+        let mut buffer = vec![0u8; MAX_DATAGRAM];
+        loop {
+            let Some(n) = dropped_on_error(upstream_in.recv(&mut buffer)) else {
+                continue;
+            };
+            let datagram = buffer.get(..n).unwrap_or_default();
+            let Some(out) = transform(code.as_ref(), datagram, !encoded_listen) else {
+                continue;
+            };
+            if let Some(addr) = *reply_to.lock().unwrap_or_else(|e| e.into_inner()) {
+                dropped_on_error(socket_out.send_to(&out, addr));
+            }
+        }
+    });
+
+    let code = spec.build();
+    let mut buffer = vec![0u8; MAX_DATAGRAM];
+    loop {
+        let Some((n, addr)) = dropped_on_error(socket.recv_from(&mut buffer)) else {
+            continue;
+        };
+        *client.lock().unwrap_or_else(|e| e.into_inner()) = Some(addr);
+        let datagram = buffer.get(..n).unwrap_or_default();
+        if let Some(out) = transform(code.as_ref(), datagram, encoded_listen) {
+            dropped_on_error(upstream.send(&out));
+        }
+    }
+}
+
+// A failed receive or send loses one datagram, as the network might, and
+// the proxy carries on in both directions
+fn dropped_on_error<T>(result: io::Result<T>) -> Option<T> {
+    result
+        .inspect_err(|err| eprintln!("hamming: dropped datagram: {err}"))
+        .ok()
+}
+
+// Decode a datagram arriving on the encoded side, encode one leaving on it;
+// undecodable datagrams, and those that encode to more than fits in a UDP
+// datagram, are dropped like any corrupt UDP packet
+fn transform(code: &dyn HammingCode, datagram: &[u8], from_encoded: bool) -> Option<Vec<u8>> {
+    if !from_encoded {
+        // The terminator is one more data byte
+        let len = code.expansion(datagram.len() + 1).encoded_len;
+        if len > MAX_UDP_PAYLOAD {
+            eprintln!(
+                "hamming: dropped datagram: {} bytes encode to {len}, over the UDP limit",
+                datagram.len()
+            );
+            return None;
+        }
+        return Some(code.encode_terminated(datagram));
+    }
+    match code.decode_terminated(datagram) {
+        Ok(data) => Some(data),
+        Err(err) => {
+            eprintln!("hamming: dropped datagram: {err}");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SAMPLE_CODES;
+
+    #[test]
+    fn test_frames_roundtrip() {
+        for spec in SAMPLE_CODES {
+            let spec: CodeSpec = spec.parse().unwrap();
+            // Short reads become separate frames of odd sizes
+            let input: Vec<u8> = (0..=255).cycle().take(1000).collect();
+            let mut encoded = Vec::new();
+            for chunk in input.chunks(77) {
+                encode_frames(spec, chunk, &mut encoded).unwrap();
+            }
+
+            encoded[5] ^= 0x01;
+            let mut decoded = Vec::new();
+            decode_frames(spec, encoded.as_slice(), &mut decoded).unwrap();
+            assert_eq!(decoded, input);
+        }
+    }
+
+    #[test]
+    fn test_datagram_transform() {
+        let code = CodeSpec::Hamming1511.build();
+        let encoded = transform(code.as_ref(), b"ping", false).unwrap();
+        assert_eq!(transform(code.as_ref(), &encoded, true).unwrap(), b"ping");
+        assert_eq!(transform(code.as_ref(), b"\x00\x00", true), None);
+
+        // Hamming(7,4) doubles the size, so this would not fit a datagram
+        let code = CodeSpec::Hamming74.build();
+        let large = vec![0x5A; MAX_UDP_PAYLOAD / 2];
+        assert_eq!(transform(code.as_ref(), &large, false), None);
+        let fits = vec![0x5A; MAX_UDP_PAYLOAD / 2 - 1];
+        let encoded = transform(code.as_ref(), &fits, false).unwrap();
+        assert_eq!(encoded.len(), code.expansion(fits.len() + 1).encoded_len);
+        assert!(encoded.len() <= MAX_UDP_PAYLOAD);
+    }
+}
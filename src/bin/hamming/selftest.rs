@@ -0,0 +1,52 @@
+use crate::args::{Args, UsageError};
+use crate::json::{Json, object};
+use crate::{CliResult, parse_code};
+use hamming_rs::self_test;
+
+pub const USAGE: &str = "hamming selftest [--json] [--code CODE[,CODE...]]";
+
+// Codes checked when no --code is given
+const DEFAULT_CODES: &str = "74,1511,general:4,general:11,general:26,secded:64";
+
+/// Run the exhaustive self-test of each code, failing if any code fails
+pub fn run(mut args: Args) -> CliResult {
+    let json = args.flag(&["--json"]);
+    let codes = args.value("code")?.unwrap_or_else(|| DEFAULT_CODES.into());
+    if !args.finish()?.is_empty() {
+        return Err(UsageError("unexpected argument".into()).into());
+    }
+
+    let mut failed = 0;
+    let mut results = Vec::new();
+    for code in codes.split(',') {
+        let report = self_test(&parse_code(code)?);
+        let scope = if report.exhaustive { "all" } else { "sampled" };
+        if json {
+            results.push(object([
+                ("code", report.spec.to_string().into()),
+                ("passed", report.passed().into()),
+                ("data_values", report.data_values.into()),
+                ("exhaustive", report.exhaustive.into()),
+                ("decodes", report.cases.into()),
+                ("failures", report.failures.into()),
+            ]));
+        } else {
+            println!(
+                "{code:<12} {}  {} data values ({scope}), {} decodes, {} failures",
+                if report.passed() { "PASS" } else { "FAIL" },
+                report.data_values,
+                report.cases,
+                report.failures
+            );
+        }
+        failed += usize::from(!report.passed());
+    }
+    if json {
+        println!("{}", object([("codes", Json::Array(results))]));
+    }
+
+    if failed > 0 {
+        return Err(format!("{failed} codes failed the self-test").into());
+    }
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use crate::args::{Args, UsageError};
+use crate::json::object;
+use crate::progress;
+use crate::{CliResult, parse_code};
+use hamming_rs::{protect_file, repair_file, verify_file};
+
+pub const PROTECT_USAGE: &str =
+    "hamming protect [--code CODE] [--json] [--progress] FILE [SIDECAR]";
+pub const REPAIR_USAGE: &str = "hamming repair [--check] [--json] [--progress] FILE [SIDECAR]";
+
+// Few parity bits per byte, and double errors are reported rather than
+// miscorrected
+const DEFAULT_CODE: &str = "secded:64";
+
+/// Write the parity bits of FILE to SIDECAR (default FILE.ecc)
+pub fn protect(mut args: Args) -> CliResult {
+    let json = args.flag(&["--json"]);
+    let show_progress = args.flag(&["--progress"]);
+    let spec = parse_code(&args.value("code")?.unwrap_or_else(|| DEFAULT_CODE.into()))?;
+    let (file, sidecar_path) = paths(args.finish()?)?;
+
+    let mut bytes = 0;
+    let mut report_progress = progress::callback();
+    protect_file(&file, &sidecar_path, &spec, &mut |done, total| {
+        bytes = done;
+        if show_progress {
+            report_progress(done, total);
+        }
+    })
+    .map_err(|err| format!("{file}: {err}"))?;
+    if json {
+        let report = object([
+            ("file", file.into()),
+            ("sidecar", sidecar_path.into()),
+            ("code", spec.to_string().into()),
+            ("bytes", bytes.into()),
+        ]);
+        eprintln!("{report}");
+        return Ok(());
+    }
+    eprintln!("hamming: wrote {sidecar_path} ({spec}, {bytes} bytes protected)");
+    Ok(())
+}
+
+/// Repair FILE and its sidecar in place, or with `--check` only report
+/// what would be repaired
+///
+/// Fails if any block has more errors than the code corrects; the other
+/// blocks are still repaired.
+pub fn repair(mut args: Args) -> CliResult {
+    let check = args.flag(&["--check"]);
+    let json = args.flag(&["--json"]);
+    let show_progress = args.flag(&["--progress"]);
+    let (file, sidecar_path) = paths(args.finish()?)?;
+
+    let mut report_progress = progress::callback();
+    let mut on_progress = |done, total| {
+        if show_progress {
+            report_progress(done, total);
+        }
+    };
+    let report = if check {
+        verify_file(&file, &sidecar_path, &mut on_progress)
+    } else {
+        repair_file(&file, &sidecar_path, &mut on_progress)
+    }
+    .map_err(|err| format!("{file} / {sidecar_path}: {err}"))?;
+
+    if json {
+        let summary = object([
+            ("file", file.as_str().into()),
+            ("blocks", report.blocks.into()),
+            ("data_bits", report.data_bits.into()),
+            ("parity_bits", report.parity_bits.into()),
+            ("uncorrectable_blocks", report.uncorrectable_blocks.into()),
+            (
+                "repaired",
+                (!check && report.data_bits + report.parity_bits > 0).into(),
+            ),
+        ]);
+        println!("{summary}");
+    } else {
+        println!(
+            "{file}: {} blocks, {} data bit errors, {} parity bit errors, {} uncorrectable blocks",
+            report.blocks, report.data_bits, report.parity_bits, report.uncorrectable_blocks
+        );
+    }
+    if report.uncorrectable_blocks > 0 {
+        return Err(format!(
+            "{file}: {} uncorrectable blocks",
+            report.uncorrectable_blocks
+        )
+        .into());
+    }
+    if check && !report.is_clean() {
+        return Err(format!("{file}: needs repair").into());
+    }
+    Ok(())
+}
+
+// FILE and SIDECAR, defaulting to FILE.ecc
+fn paths(positional: Vec<String>) -> Result<(String, String), UsageError> {
+    match <[String; 1]>::try_from(positional) {
+        Ok([file]) => {
+            let sidecar = format!("{file}.ecc");
+            Ok((file, sidecar))
+        }
+        Err(positional) => <[String; 2]>::try_from(positional)
+            .map(|[file, sidecar]| (file, sidecar))
+            .map_err(|_| UsageError("expected FILE [SIDECAR]".into())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sidecar_paths() {
+        let paths = |args: &[&str]| paths(args.iter().map(|s| s.to_string()).collect());
+        assert_eq!(
+            paths(&["backup.tar"]).unwrap(),
+            ("backup.tar".into(), "backup.tar.ecc".into())
+        );
+        assert_eq!(
+            paths(&["a", "b.par"]).unwrap(),
+            ("a".into(), "b.par".into())
+        );
+        assert!(paths(&[]).is_err());
+        assert!(paths(&["a", "b", "c"]).is_err());
+    }
+}
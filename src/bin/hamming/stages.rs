@@ -0,0 +1,99 @@
+use crate::args::{Args, UsageError};
+use hamming_rs::{Interleaver, Scrambler};
+
+/// Link stages around the code, in the order of `LinkProfile`: data is
+/// scrambled before encoding and the encoded stream interleaved after
+#[derive(Default)]
+pub struct Stages {
+    interleaver: Option<Interleaver>,
+    scrambler: Option<Scrambler>,
+}
+
+impl Stages {
+    /// Take `--interleave DEPTH` and `--scramble POLY,SEED`
+    pub fn from_args(args: &mut Args) -> Result<Self, UsageError> {
+        let interleaver = args
+            .value("interleave")?
+            .map(|depth| match depth.parse() {
+                Ok(depth) if depth > 0 => Ok(Interleaver::new(depth)),
+                _ => Err(UsageError(format!("invalid --interleave {depth}"))),
+            })
+            .transpose()?;
+        let scrambler = args
+            .value("scramble")?
+            .map(|spec| parse_scramble(&spec))
+            .transpose()?;
+        Ok(Self {
+            interleaver,
+            scrambler,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.interleaver.is_none() && self.scrambler.is_none()
+    }
+
+    /// Stage applied to the input of the code: scrambling when encoding,
+    /// deinterleaving when decoding
+    pub fn before(&self, data: Vec<u8>, decoding: bool) -> Vec<u8> {
+        match (decoding, &self.interleaver, &self.scrambler) {
+            (false, _, Some(scrambler)) => scrambler.apply(&data),
+            (true, Some(interleaver), _) => interleaver.deinterleave(&data),
+            _ => data,
+        }
+    }
+
+    /// Stage applied to the output of the code: interleaving when encoding,
+    /// descrambling when decoding
+    pub fn after(&self, data: Vec<u8>, decoding: bool) -> Vec<u8> {
+        match (decoding, &self.interleaver, &self.scrambler) {
+            (false, Some(interleaver), _) => interleaver.interleave(&data),
+            (true, _, Some(scrambler)) => scrambler.apply(&data),
+            _ => data,
+        }
+    }
+}
+
+// `POLY,SEED`, each decimal or `0x` hex
+fn parse_scramble(spec: &str) -> Result<Scrambler, UsageError> {
+    let number = |s: &str| match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => s.parse().ok(),
+    };
+    spec.split_once(',')
+        .and_then(|(poly, seed)| Some((number(poly)?, number(seed)?)))
+        .filter(|&(poly, _)| poly > 1)
+        .map(|(poly, seed)| Scrambler::new(poly, seed))
+        .ok_or_else(|| UsageError(format!("invalid --scramble {spec}, expected POLY,SEED")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_scramble() {
+        assert_eq!(parse_scramble("0xC001,0x4A80").unwrap().degree(), 15);
+        assert_eq!(parse_scramble("49153,19072").unwrap().degree(), 15);
+        assert!(parse_scramble("0xC001").is_err());
+        assert!(parse_scramble("1,5").is_err());
+        assert!(parse_scramble("0xZZ,1").is_err());
+    }
+
+    #[test]
+    fn test_stages_roundtrip() {
+        let mut args = Args::new(
+            ["--interleave", "7", "--scramble=0xC001,0x4A80"]
+                .map(String::from)
+                .to_vec(),
+        );
+        let stages = Stages::from_args(&mut args).unwrap();
+        let data = b"pipeline".to_vec();
+
+        let sent = stages.after(stages.before(data.clone(), false), false);
+        assert_ne!(sent, data);
+        // Without a code in between the stages undo each other in reverse
+        let received = stages.after(stages.before(sent, true), true);
+        assert_eq!(received, data);
+    }
+}
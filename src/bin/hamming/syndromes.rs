@@ -0,0 +1,19 @@
+use crate::args::{Args, UsageError};
+use crate::{CliResult, parse_code};
+use hamming_rs::SyndromeTable;
+
+pub const USAGE: &str = "hamming syndromes --code CODE [OUTPUT]";
+
+/// Print (or write to OUTPUT) the syndrome decoding table of a code as CSV
+pub fn run(mut args: Args) -> CliResult {
+    let spec = parse_code(&args.value("code")?.unwrap_or_else(|| "74".into()))?;
+    let positional = args.finish()?;
+
+    let csv = SyndromeTable::new(&spec)?.to_csv();
+    match positional.as_slice() {
+        [] => print!("{csv}"),
+        [path] => std::fs::write(path, csv)?,
+        _ => return Err(UsageError("too many arguments".into()).into()),
+    }
+    Ok(())
+}
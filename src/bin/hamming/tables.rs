@@ -0,0 +1,24 @@
+use crate::args::{Args, UsageError};
+use crate::{CliResult, parse_code};
+use hamming_rs::{LookupTables, TableFormat};
+
+pub const USAGE: &str = "hamming tables --code CODE [--format c|rust] [OUTPUT]";
+
+/// Print (or write to OUTPUT) the lookup tables for a code
+pub fn run(mut args: Args) -> CliResult {
+    let spec = parse_code(&args.value("code")?.unwrap_or_else(|| "74".into()))?;
+    let format = match args.value("format")?.as_deref() {
+        None | Some("c") => TableFormat::C,
+        Some("rust") => TableFormat::Rust,
+        Some(other) => return Err(UsageError(format!("unknown format {other}")).into()),
+    };
+    let positional = args.finish()?;
+
+    let source = LookupTables::new(&spec)?.render(format);
+    match positional.as_slice() {
+        [] => print!("{source}"),
+        [path] => std::fs::write(path, source)?,
+        _ => return Err(UsageError("too many arguments".into()).into()),
+    }
+    Ok(())
+}
@@ -0,0 +1,373 @@
+//! Bit packing: [`BitReader`] and [`BitWriter`] take and pack groups of
+//! bits the way this crate's codecs do, e.g. the 11-bit groups of
+//! [`Hamming1511`](crate::Hamming1511)
+//!
+//! Within the crate, out-of-range reads return `false` and out-of-range
+//! writes are ignored, so callers on decode paths cannot panic on malformed
+//! input.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Bit `index` of `data`, counting LSB-first within each byte
+#[cfg(feature = "alloc")]
+pub(crate) fn get(data: &[u8], index: usize) -> bool {
+    data.get(index / 8)
+        .is_some_and(|byte| (byte >> (index % 8)) & 1 == 1)
+}
+
+/// Set bit `index` of `data`
+#[cfg(feature = "alloc")]
+pub(crate) fn set(data: &mut [u8], index: usize) {
+    if let Some(byte) = data.get_mut(index / 8) {
+        *byte |= 1 << (index % 8);
+    }
+}
+
+/// Set or clear bit `index` of `data`
+#[cfg(feature = "alloc")]
+pub(crate) fn assign(data: &mut [u8], index: usize, bit: bool) {
+    if let Some(byte) = data.get_mut(index / 8) {
+        *byte = *byte & !(1 << (index % 8)) | (u8::from(bit) << (index % 8));
+    }
+}
+
+/// `Extend<u8>` sink filling a caller's slice from the front; bytes past
+/// its end are dropped
+pub(crate) struct SliceSink<'a> {
+    out: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> SliceSink<'a> {
+    pub(crate) fn new(out: &'a mut [u8]) -> Self {
+        Self { out, len: 0 }
+    }
+
+    /// Bytes written so far
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl Extend<u8> for SliceSink<'_> {
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, bytes: I) {
+        for byte in bytes {
+            if let Some(slot) = self.out.get_mut(self.len) {
+                *slot = byte;
+                self.len += 1;
+            }
+        }
+    }
+}
+
+/// `Extend<u8>` sink appending to a borrowed vector
+#[cfg(feature = "alloc")]
+pub(crate) struct VecSink<'a>(pub(crate) &'a mut Vec<u8>);
+
+#[cfg(feature = "alloc")]
+impl Extend<u8> for VecSink<'_> {
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, bytes: I) {
+        self.0.extend(bytes);
+    }
+}
+
+/// Order in which bits are taken from and packed into bytes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum BitOrder {
+    /// Bit 0 of each byte first (this crate's native layout)
+    #[default]
+    LsbFirst,
+    /// Bit 7 of each byte first, i.e. the stream read as a left-to-right
+    /// bit string
+    MsbFirst,
+}
+
+impl core::fmt::Display for BitOrder {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            BitOrder::LsbFirst => "LSB-first",
+            BitOrder::MsbFirst => "MSB-first",
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl BitOrder {
+    /// Bit `index` of `data` in this order
+    pub(crate) fn get(self, data: &[u8], index: usize) -> bool {
+        get(data, self.position(index))
+    }
+
+    /// Set bit `index` of `data` in this order
+    pub(crate) fn set(self, data: &mut [u8], index: usize) {
+        set(data, self.position(index))
+    }
+
+    // Equivalent LSB-first index
+    pub(crate) fn position(self, index: usize) -> usize {
+        match self {
+            BitOrder::LsbFirst => index,
+            BitOrder::MsbFirst => index ^ 7,
+        }
+    }
+}
+
+/// Reads groups of bits from bytes, in [`BitOrder::LsbFirst`] unless set
+/// otherwise
+///
+/// The first bit read lands in bit 0 of the returned value. Also an
+/// iterator over single bits.
+#[derive(Debug, Clone)]
+pub struct BitReader<'a> {
+    bytes: core::slice::Iter<'a, u8>,
+    order: BitOrder,
+    acc: u128,
+    acc_bits: u32,
+}
+
+impl<'a> BitReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self {
+            bytes: data.iter(),
+            order: BitOrder::LsbFirst,
+            acc: 0,
+            acc_bits: 0,
+        }
+    }
+
+    /// Take the bits of each byte in `order`
+    pub fn with_order(mut self, order: BitOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// The next `bits` bits (at most 64), or `None` at the end of the data;
+    /// bits past the end read as zero
+    pub fn read(&mut self, bits: u32) -> Option<u64> {
+        let bits = bits.min(64);
+        while self.acc_bits < bits {
+            let Some(&byte) = self.bytes.next() else {
+                break;
+            };
+            let byte = match self.order {
+                BitOrder::LsbFirst => byte,
+                BitOrder::MsbFirst => byte.reverse_bits(),
+            };
+            self.acc |= u128::from(byte) << self.acc_bits;
+            self.acc_bits += 8;
+        }
+        if self.acc_bits == 0 {
+            return None;
+        }
+
+        let taken = bits.min(self.acc_bits);
+        let value = (self.acc & ((1u128 << taken) - 1)) as u64;
+        self.acc >>= taken;
+        self.acc_bits -= taken;
+        Some(value)
+    }
+
+    /// Bits left to read
+    pub fn remaining(&self) -> usize {
+        self.acc_bits as usize + self.bytes.len() * 8
+    }
+}
+
+impl Iterator for BitReader<'_> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        self.read(1).map(|bit| bit == 1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining(), Some(self.remaining()))
+    }
+}
+
+/// Packs groups of bits into bytes, the inverse of [`BitReader`]
+///
+/// Completed bytes are passed to the sink as they fill, so any `Extend<u8>`
+/// collection works, including fixed-capacity ones. The sink defaults to
+/// a `Vec<u8>` with the `alloc` feature.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct BitWriter<B = Vec<u8>> {
+    sink: B,
+    order: BitOrder,
+    acc: u128,
+    acc_bits: u32,
+    written: usize,
+}
+
+/// Packs groups of bits into bytes, the inverse of [`BitReader`]
+///
+/// Completed bytes are passed to the sink as they fill, so any `Extend<u8>`
+/// collection works, including fixed-capacity ones.
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+pub struct BitWriter<B> {
+    sink: B,
+    order: BitOrder,
+    acc: u128,
+    acc_bits: u32,
+    written: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl BitWriter {
+    pub fn new() -> Self {
+        Self::with_sink(Vec::new())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Default for BitWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: Extend<u8>> BitWriter<B> {
+    /// Writer appending to `sink`
+    pub fn with_sink(sink: B) -> Self {
+        Self {
+            sink,
+            order: BitOrder::LsbFirst,
+            acc: 0,
+            acc_bits: 0,
+            written: 0,
+        }
+    }
+
+    /// Pack the bits of each byte in `order`
+    pub fn with_order(mut self, order: BitOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Append the low `bits` bits of `value` (at most 64), bit 0 first
+    pub fn write(&mut self, value: u64, bits: u32) {
+        let bits = bits.min(64);
+        let value = u128::from(value) & ((1u128 << bits) - 1);
+        self.acc |= value << self.acc_bits;
+        self.acc_bits += bits;
+        self.written += bits as usize;
+        while self.acc_bits >= 8 {
+            self.emit(self.acc as u8);
+            self.acc >>= 8;
+            self.acc_bits -= 8;
+        }
+    }
+
+    pub fn write_bit(&mut self, bit: bool) {
+        self.write(u64::from(bit), 1);
+    }
+
+    /// Bits written so far
+    pub fn len(&self) -> usize {
+        self.written
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.written == 0
+    }
+
+    /// The sink, with a partial final byte padded with zero bits
+    pub fn finish(mut self) -> B {
+        if self.acc_bits > 0 {
+            self.emit(self.acc as u8);
+        }
+        self.sink
+    }
+
+    /// The sink, dropping a partial final byte
+    pub fn finish_whole_bytes(self) -> B {
+        self.sink
+    }
+
+    fn emit(&mut self, byte: u8) {
+        let byte = match self.order {
+            BitOrder::LsbFirst => byte,
+            BitOrder::MsbFirst => byte.reverse_bits(),
+        };
+        self.sink.extend(Some(byte));
+    }
+}
+
+/// The next `bits` bits (up to 128), zero past the end
+pub(crate) fn read_word(reader: &mut BitReader<'_>, bits: u32) -> u128 {
+    let low = reader.read(bits.min(64)).unwrap_or_default();
+    let high = match bits.checked_sub(64) {
+        Some(high) if high > 0 => reader.read(high).unwrap_or_default(),
+        _ => 0,
+    };
+    u128::from(low) | u128::from(high) << 64
+}
+
+/// Append the low `bits` bits (up to 128) of `word`
+pub(crate) fn write_word<B: Extend<u8>>(writer: &mut BitWriter<B>, word: u128, bits: u32) {
+    writer.write(word as u64, bits.min(64));
+    if let Some(high) = bits.checked_sub(64).filter(|&high| high > 0) {
+        writer.write((word >> 64) as u64, high);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use alloc::vec;
+
+    #[test]
+    fn test_bit_reader() {
+        let data = [0b1010_0101, 0xFF, 0x01];
+        let mut reader = BitReader::new(&data);
+        assert_eq!(reader.read(3), Some(0b101));
+        assert_eq!(reader.read(11), Some(0b111_1111_0100));
+        assert_eq!(reader.remaining(), 10);
+        // Past the end reads as zero
+        assert_eq!(reader.read(16), Some(0b00_0000_0111));
+        assert_eq!(reader.read(1), None);
+
+        assert!(
+            BitReader::new(&[0x80])
+                .with_order(BitOrder::MsbFirst)
+                .eq([true, false, false, false, false, false, false, false])
+        );
+        for (i, bit) in BitReader::new(&data).enumerate() {
+            assert_eq!(bit, data[i / 8] >> (i % 8) & 1 == 1);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_bit_writer_roundtrip() {
+        for order in [BitOrder::LsbFirst, BitOrder::MsbFirst] {
+            let data: Vec<u8> = (0..23).map(|i| (i * 73 + 5) as u8).collect();
+            let mut writer = BitWriter::new().with_order(order);
+            let mut reader = BitReader::new(&data).with_order(order);
+            while let Some(group) = reader.read(11) {
+                writer.write(group, 11);
+            }
+            assert_eq!(writer.len(), 11 * 17);
+            // 187 bits: the padding of the last group makes a 24th byte
+            let written = writer.clone().finish();
+            assert_eq!(written[..23], data[..]);
+            assert_eq!(written.len(), 24);
+            assert_eq!(writer.finish_whole_bytes(), data);
+        }
+
+        let mut writer = BitWriter::new().with_order(BitOrder::MsbFirst);
+        writer.write_bit(true);
+        writer.write(0b11, 2);
+        assert_eq!(writer.finish(), vec![0b1110_0000]);
+    }
+}
@@ -0,0 +1,178 @@
+use crate::{BitOrder, HammingError};
+use alloc::borrow::Cow;
+
+/// A codeword as stored in the encoded stream, see [`HammingCode::blocks`]
+///
+/// [`HammingCode::blocks`]: crate::HammingCode::blocks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RawBlock {
+    /// Index of the block in the encoded stream
+    pub index: usize,
+    /// Offset of the byte holding the block's first bit
+    pub byte_offset: usize,
+    /// Offset of the block's first bit in the encoded stream
+    pub bit_offset: usize,
+    /// The codeword, bit `i` holding block bit `i`
+    pub word: u128,
+}
+
+/// Iterator over the codewords of an encoded stream, without decoding
+///
+/// Only whole blocks are returned; a trailing partial block is skipped, as
+/// the decoders do. Bits between blocks, such as the spare top bit of
+/// [`Hamming74`](crate::Hamming74), are not part of `word`.
+#[derive(Debug, Clone)]
+pub struct Blocks<'a> {
+    encoded: Cow<'a, [u8]>,
+    block_size: usize,
+    stride: usize,
+    order: BitOrder,
+    next: usize,
+    count: usize,
+}
+
+impl<'a> Blocks<'a> {
+    /// Blocks of `block_size` bits every `stride` bits; wider than 128 bits
+    /// fails with [`HammingError::Unsupported`]
+    pub(crate) fn new(
+        encoded: impl Into<Cow<'a, [u8]>>,
+        block_size: usize,
+        stride: usize,
+    ) -> Result<Self, HammingError> {
+        if block_size == 0 || block_size > 128 || stride < block_size {
+            return Err(HammingError::Unsupported);
+        }
+        let encoded = encoded.into();
+        let count = encoded.len().saturating_mul(8) / stride;
+        Ok(Self {
+            encoded,
+            block_size,
+            stride,
+            order: BitOrder::LsbFirst,
+            next: 0,
+            count,
+        })
+    }
+
+    pub(crate) fn with_order(mut self, order: BitOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// The same blocks over `encoded`, from the start
+    pub(crate) fn rebind<'b>(self, encoded: impl Into<Cow<'b, [u8]>>) -> Blocks<'b> {
+        let encoded = encoded.into();
+        Blocks {
+            count: encoded.len().saturating_mul(8) / self.stride,
+            encoded,
+            block_size: self.block_size,
+            stride: self.stride,
+            order: self.order,
+            next: 0,
+        }
+    }
+}
+
+impl Iterator for Blocks<'_> {
+    type Item = RawBlock;
+
+    fn next(&mut self) -> Option<RawBlock> {
+        if self.next >= self.count {
+            return None;
+        }
+        let index = self.next;
+        self.next += 1;
+
+        let bit_offset = index * self.stride;
+        let word = (0..self.block_size)
+            .filter(|&i| self.order.get(&self.encoded, bit_offset + i))
+            .fold(0u128, |word, i| word | 1 << i);
+        Some(RawBlock {
+            index,
+            byte_offset: bit_offset / 8,
+            bit_offset,
+            word,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.count - self.next;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for Blocks<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        BitOrder, BlockOutcome, Hamming, Hamming74, Hamming1511, HammingCode, HammingError,
+        HammingWord, Interleaved,
+    };
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_blocks_fixed_codes() {
+        let encoded = Hamming74.encode(b"ab");
+        let words: Vec<_> = Hamming74
+            .blocks(&encoded)
+            .unwrap()
+            .map(|b| (b.byte_offset, b.word))
+            .collect();
+        let expected: Vec<_> = encoded
+            .iter()
+            .enumerate()
+            .map(|(i, &byte)| (i, u128::from(byte)))
+            .collect();
+        assert_eq!(words, expected);
+
+        let mut encoded = Hamming1511.encode(b"eleven byte");
+        encoded[1] |= 0x80;
+        let blocks: Vec<_> = Hamming1511.blocks(&encoded[..7]).unwrap().collect();
+        assert_eq!(blocks.len(), 3);
+        assert_eq!((blocks[2].index, blocks[2].bit_offset), (2, 32));
+        // The spare bit is not part of the codeword
+        assert_eq!(
+            blocks[0].word,
+            u128::from(u16::from_le_bytes([encoded[0], encoded[1]]) & 0x7FFF)
+        );
+    }
+
+    #[test]
+    fn test_blocks_general_codes() {
+        let data = b"raw codewords";
+        for code in [
+            Hamming::new(26),
+            Hamming::new(4).with_bit_order(BitOrder::MsbFirst),
+            Hamming::new(64).with_extended(true),
+        ] {
+            let encoded = code.encode(data);
+            let blocks = code.blocks(&encoded).unwrap();
+            assert_eq!(blocks.len(), encoded.len() * 8 / code.block_size());
+            for block in blocks {
+                assert_eq!(block.bit_offset, block.index * code.block_size());
+                let (word, outcome) = HammingWord::correct(&code, block.word).unwrap();
+                assert_eq!((word.get(), outcome), (block.word, BlockOutcome::Clean));
+            }
+        }
+        assert_eq!(
+            Hamming::new(247).blocks(&[0; 32]).err(),
+            Some(HammingError::Unsupported)
+        );
+
+        // Interleaved codewords are gathered from their bits
+        let code = Interleaved::new(Hamming74, 2);
+        let words: Vec<_> = code
+            .blocks(&code.encode(data))
+            .unwrap()
+            .map(|b| b.word)
+            .collect();
+        let plain: Vec<_> = Hamming74
+            .blocks(&Hamming74.encode(data))
+            .unwrap()
+            .map(|b| b.word)
+            .collect();
+        assert_eq!(words, plain);
+    }
+}
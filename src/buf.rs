@@ -0,0 +1,102 @@
+use crate::{HammingCode, HammingError};
+use bytes::{Buf, BufMut};
+
+/// Encode/decode between `bytes::Buf` sources and `BufMut` sinks
+///
+/// Whole [`HammingCode::chunk_sizes`] units are coded straight out of the
+/// source's contiguous chunks; only units straddling a chunk boundary are
+/// gathered first. Each chunk's output is built with
+/// [`HammingCode::encode_into`]/[`HammingCode::decode_into`] in one buffer
+/// reused for the whole call, then copied into the sink once: a `BufMut`
+/// hands out uninitialized memory, which the codecs cannot write to as a
+/// `&mut [u8]`. The sink must have room for the output (`BytesMut` and
+/// `Vec<u8>` grow as needed).
+pub trait BufCodec: HammingCode {
+    /// Encode everything remaining in `src` into `dst`
+    fn encode_buf<B: Buf, M: BufMut>(&self, src: &mut B, dst: &mut M) {
+        let (unit, _) = self.chunk_sizes();
+        let mut scratch = Vec::new();
+        let mut out = Vec::new();
+
+        while src.has_remaining() {
+            out.clear();
+            let chunk = src.chunk();
+            let whole = chunk.len() / unit * unit;
+            if let Some(units) = chunk.get(..whole).filter(|units| !units.is_empty()) {
+                self.encode_into(units, &mut out);
+                src.advance(whole);
+            } else {
+                scratch.resize(unit.min(src.remaining()), 0);
+                src.copy_to_slice(&mut scratch);
+                self.encode_into(&scratch, &mut out);
+            }
+            dst.put_slice(&out);
+        }
+    }
+
+    /// Decode everything remaining in `src` into `dst`
+    ///
+    /// On error `src` has been consumed up to and including the failing unit.
+    fn decode_buf<B: Buf, M: BufMut>(&self, src: &mut B, dst: &mut M) -> Result<(), HammingError> {
+        let (_, unit) = self.chunk_sizes();
+        let mut scratch = Vec::new();
+        let mut out = Vec::new();
+
+        while src.has_remaining() {
+            out.clear();
+            let chunk = src.chunk();
+            let whole = chunk.len() / unit * unit;
+            if let Some(units) = chunk.get(..whole).filter(|units| !units.is_empty()) {
+                let decoded = self.decode_into(units, &mut out);
+                src.advance(whole);
+                decoded?;
+            } else {
+                scratch.resize(unit.min(src.remaining()), 0);
+                src.copy_to_slice(&mut scratch);
+                self.decode_into(&scratch, &mut out)?;
+            }
+            dst.put_slice(&out);
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: HammingCode + ?Sized> BufCodec for C {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Hamming1511, sample_codecs};
+    use bytes::{Bytes, BytesMut};
+
+    #[test]
+    fn test_buf_matches_slice_api() {
+        let codecs = sample_codecs();
+        let data: Vec<u8> = (0..100).map(|i| (i * 7 + 3) as u8).collect();
+
+        for codec in &codecs {
+            // Split the source into two uneven chunks
+            let mut src =
+                Bytes::copy_from_slice(&data[..37]).chain(Bytes::copy_from_slice(&data[37..]));
+            let mut encoded = BytesMut::new();
+            codec.encode_buf(&mut src, &mut encoded);
+            assert_eq!(&encoded[..], &codec.encode(&data)[..]);
+
+            let mut src = encoded.freeze();
+            let mut decoded = Vec::new();
+            codec.decode_buf(&mut src, &mut decoded).unwrap();
+            assert_eq!(decoded, codec.decode(&codec.encode(&data)).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_decode_buf_error() {
+        let mut src = Bytes::from_static(&[0x00, 0x00, 0x00]);
+        let mut dst = BytesMut::new();
+        assert_eq!(
+            Hamming1511.decode_buf(&mut src, &mut dst),
+            Err(HammingError::InvalidLength)
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use crate::HammingError;
+use core::cell::Cell;
+
+/// Unsigned integer that can be kept in an [`EccCell`]
+pub trait EccWord: Copy + sealed::Sealed {
+    #[doc(hidden)]
+    const BITS: u32;
+    #[doc(hidden)]
+    const MASKS: [u64; 7];
+    #[doc(hidden)]
+    fn to_u64(self) -> u64;
+    #[doc(hidden)]
+    fn from_u64(value: u64) -> Self;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_ecc_word {
+    ($($t:ty),*) => {$(
+        impl sealed::Sealed for $t {}
+
+        impl EccWord for $t {
+            const BITS: u32 = <$t>::BITS;
+            const MASKS: [u64; 7] = check_masks(<$t>::BITS);
+
+            fn to_u64(self) -> u64 {
+                self.into()
+            }
+
+            fn from_u64(value: u64) -> Self {
+                value as $t
+            }
+        }
+    )*};
+}
+
+impl_ecc_word!(u8, u16, u32, u64);
+
+/// A single word stored with SEC-DED check bits, e.g. a counter or a state
+/// machine variable
+///
+/// The word is kept as it is, next to a byte of Hamming check bits and an
+/// overall parity bit (up to 7 + 1 bits, for `u64`). [`EccCell::load`]
+/// corrects a single flipped bit in either, writing the correction back,
+/// and reports two flipped bits instead of returning a wrong value. Nothing
+/// is allocated.
+#[derive(Debug, Clone, Default)]
+pub struct EccCell<T: EccWord> {
+    value: Cell<T>,
+    check: Cell<u8>,
+}
+
+impl<T: EccWord> EccCell<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            value: Cell::new(value),
+            check: Cell::new(check_bits::<T>(value.to_u64())),
+        }
+    }
+
+    pub fn store(&self, value: T) {
+        self.value.set(value);
+        self.check.set(check_bits::<T>(value.to_u64()));
+    }
+
+    /// The stored value, and whether a bit error was corrected
+    ///
+    /// Two flipped bits are reported as
+    /// [`HammingError::DoubleErrorDetected`]; an error at a position the
+    /// word does not use (three or more flips) as
+    /// [`HammingError::UncorrectableErrors`].
+    pub fn load(&self) -> Result<(T, bool), HammingError> {
+        let (value, fixed) = correct::<T>(self.value.get().to_u64(), self.check.get())?;
+        if fixed.is_some() {
+            self.value.set(T::from_u64(value));
+            self.check.set(check_bits::<T>(value));
+        }
+        Ok((T::from_u64(value), fixed.is_some()))
+    }
+}
+
+/// Syndrome of `value` stored with `check`: the Hamming syndrome in bits
+/// 0..7 and a failed overall parity in bit 7
+pub(crate) fn syndrome<T: EccWord>(value: u64, check: u8) -> u8 {
+    let odd = (value.count_ones() + check.count_ones()) % 2 == 1;
+    (check ^ check_bits::<T>(value)) & 0x7F | u8::from(odd) << 7
+}
+
+/// Correct `value` stored with `check`, returning the corrected value and
+/// the bit fixed, if any: data bits first, then check bits from
+/// `T::BITS`, the overall parity last
+pub(crate) fn correct<T: EccWord>(
+    value: u64,
+    check: u8,
+) -> Result<(u64, Option<u32>), HammingError> {
+    let syndrome = syndrome::<T>(value, check);
+    let (position, odd) = (u32::from(syndrome & 0x7F), syndrome & 0x80 != 0);
+    match (position, odd) {
+        (0, false) => return Ok((value, None)),
+        (_, false) => return Err(HammingError::DoubleErrorDetected { block: 0 }),
+        _ => {}
+    }
+
+    // Single error: in the overall parity bit, a check bit, or the data bit
+    // at codeword position `position`
+    if position == 0 {
+        return Ok((value, Some(T::BITS + 7)));
+    }
+    if position.is_power_of_two() {
+        return Ok((value, Some(T::BITS + position.ilog2())));
+    }
+    let bit = position - position.ilog2() - 2;
+    if bit >= T::BITS {
+        return Err(HammingError::UncorrectableErrors);
+    }
+    Ok((value ^ (1 << bit), Some(bit)))
+}
+
+/// Hamming check bits in bits 0..7 and overall parity in bit 7
+pub(crate) fn check_bits<T: EccWord>(value: u64) -> u8 {
+    let mut check = 0;
+    for (j, mask) in T::MASKS.iter().enumerate() {
+        check |= ((value & mask).count_ones() as u8 & 1) << j;
+    }
+    let parity = (value.count_ones() + check.count_ones()) as u8 & 1;
+    check | (parity << 7)
+}
+
+// Data bits covered by each check bit
+const fn check_masks(data_bits: u32) -> [u64; 7] {
+    let k = data_bits;
+    [
+        check_mask(k, 0),
+        check_mask(k, 1),
+        check_mask(k, 2),
+        check_mask(k, 3),
+        check_mask(k, 4),
+        check_mask(k, 5),
+        check_mask(k, 6),
+    ]
+}
+
+// Data bits covered by check bit `j`: data bit `i` sits at the `i`th
+// codeword position (from 1) that is not a power of two
+const fn check_mask(data_bits: u32, j: u32) -> u64 {
+    let (mut mask, mut position, mut bit) = (0, 3u32, 0);
+    while bit < data_bits {
+        if !position.is_power_of_two() {
+            if position & (1 << j) != 0 {
+                mask |= 1 << bit;
+            }
+            bit += 1;
+        }
+        position += 1;
+    }
+    mask
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cell_corrects_every_bit() {
+        let cell = EccCell::new(0xDEAD_BEEFu32);
+        assert_eq!(cell.load(), Ok((0xDEAD_BEEF, false)));
+        for bit in 0..32 {
+            cell.value.set(0xDEAD_BEEF ^ (1 << bit));
+            assert_eq!(cell.load(), Ok((0xDEAD_BEEF, true)), "data bit {bit}");
+            assert_eq!(cell.load(), Ok((0xDEAD_BEEF, false)));
+        }
+        for bit in 0..8 {
+            cell.check.set(cell.check.get() ^ (1 << bit));
+            assert_eq!(cell.load(), Ok((0xDEAD_BEEF, true)), "check bit {bit}");
+            assert_eq!(cell.load(), Ok((0xDEAD_BEEF, false)));
+        }
+    }
+
+    #[test]
+    fn test_cell_detects_double_errors() {
+        let cell = EccCell::new(u64::MAX - 12345);
+        cell.store(7);
+        for a in 0..64 {
+            for b in a + 1..64 {
+                cell.value.set(7 ^ (1 << a) ^ (1 << b));
+                assert_eq!(
+                    cell.load(),
+                    Err(HammingError::DoubleErrorDetected { block: 0 })
+                );
+            }
+        }
+        cell.value.set(7 ^ (1 << 9));
+        cell.check.set(cell.check.get() ^ 0x01);
+        assert_eq!(
+            cell.load(),
+            Err(HammingError::DoubleErrorDetected { block: 0 })
+        );
+
+        let small = EccCell::<u8>::default();
+        small.value.set(0x10);
+        assert_eq!(small.load(), Ok((0, true)));
+    }
+}
@@ -0,0 +1,225 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// Random source of a [`BinarySymmetricChannel`]
+///
+/// Implemented by [`SplitMix64`] and, with the `rand_core` feature, by every
+/// `rand_core::RngCore`, e.g. a deterministic PRNG in tests or a hardware
+/// RNG on an embedded target.
+pub trait ChannelRng {
+    fn next_u64(&mut self) -> u64;
+}
+
+/// The SplitMix64 generator, the default source of
+/// [`BinarySymmetricChannel`]: small, fast and reproducible from its seed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+}
+
+#[cfg(not(feature = "rand_core"))]
+impl ChannelRng for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        splitmix64(&mut self.state)
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl rand_core::RngCore for SplitMix64 {
+    fn next_u32(&mut self) -> u32 {
+        (splitmix64(&mut self.state) >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        splitmix64(&mut self.state)
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        rand_core::impls::fill_bytes_via_next(self, dst)
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl<R: rand_core::RngCore + ?Sized> ChannelRng for R {
+    fn next_u64(&mut self) -> u64 {
+        rand_core::RngCore::next_u64(self)
+    }
+}
+
+/// Binary symmetric channel: flips every bit independently with a fixed
+/// probability
+///
+/// Driven by a seeded [`SplitMix64`] generator unless given another source
+/// with [`BinarySymmetricChannel::with_rng`], so a `(ber, seed)` pair always
+/// produces the same error pattern.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BinarySymmetricChannel<R = SplitMix64> {
+    ber: f64,
+    rng: R,
+}
+
+impl BinarySymmetricChannel {
+    /// Channel with bit error rate `ber`, clamped to `0.0..=1.0`
+    pub fn new(ber: f64, seed: u64) -> Self {
+        Self::with_rng(ber, SplitMix64::new(seed))
+    }
+}
+
+impl<R: ChannelRng> BinarySymmetricChannel<R> {
+    /// Channel with bit error rate `ber`, clamped to `0.0..=1.0`, drawing
+    /// from `rng`
+    pub fn with_rng(ber: f64, rng: R) -> Self {
+        Self {
+            ber: if ber.is_nan() {
+                0.0
+            } else {
+                ber.clamp(0.0, 1.0)
+            },
+            rng,
+        }
+    }
+
+    pub fn ber(&self) -> f64 {
+        self.ber
+    }
+
+    pub fn rng(&mut self) -> &mut R {
+        &mut self.rng
+    }
+
+    /// Flip bits of `data` in place, returning how many were flipped
+    pub fn corrupt(&mut self, data: &mut [u8]) -> usize {
+        let mut flipped = 0;
+        for byte in data {
+            for bit in 0..8 {
+                if self.next_f64() < self.ber {
+                    *byte ^= 1 << bit;
+                    flipped += 1;
+                }
+            }
+        }
+        flipped
+    }
+
+    /// Flip exactly `count` distinct bits of `data`, chosen uniformly from
+    /// the bit offsets in `bits`, regardless of the error rate; for
+    /// fixed-weight error experiments. Returns how many were flipped.
+    pub fn flip_distinct(&mut self, data: &mut [u8], bits: Range<usize>, count: usize) -> usize {
+        // Partial Fisher-Yates shuffle of the candidate offsets
+        let mut offsets: Vec<usize> = bits.collect();
+        let count = count.min(offsets.len());
+        for i in 0..count {
+            let j = i + (self.rng.next_u64() % (offsets.len() - i) as u64) as usize;
+            offsets.swap(i, j);
+        }
+
+        for &bit in offsets.iter().take(count) {
+            flip_bit(data, bit / 8, (bit % 8) as u32);
+        }
+        count
+    }
+
+    // Uniform in [0, 1)
+    fn next_f64(&mut self) -> f64 {
+        (self.rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Next output of the SplitMix64 generator with state `state`
+pub(crate) fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Flip bit `bit` (0 = least significant) of byte `byte`; out-of-range
+/// positions are ignored
+pub fn flip_bit(data: &mut [u8], byte: usize, bit: u32) {
+    if let (Some(b), Some(mask)) = (data.get_mut(byte), 1u8.checked_shl(bit)) {
+        *b ^= mask;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_channel_reproducible() {
+        let mut a = vec![0u8; 4096];
+        let mut b = vec![0u8; 4096];
+        let flipped = BinarySymmetricChannel::new(0.01, 42).corrupt(&mut a);
+        BinarySymmetricChannel::new(0.01, 42).corrupt(&mut b);
+        assert_eq!(a, b);
+
+        // 32768 bits at 1%: expect ~328 flips
+        let ones: u32 = a.iter().map(|b| b.count_ones()).sum();
+        assert_eq!(ones as usize, flipped);
+        assert!((250..400).contains(&flipped), "{flipped}");
+
+        BinarySymmetricChannel::new(0.01, 43).corrupt(&mut b);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_channel_extremes() {
+        let mut data = vec![0x5Au8; 16];
+        assert_eq!(BinarySymmetricChannel::new(0.0, 1).corrupt(&mut data), 0);
+        assert_eq!(BinarySymmetricChannel::new(1.0, 1).corrupt(&mut data), 128);
+        assert_eq!(data, vec![0xA5; 16]);
+
+        let mut block = [0u8; 2];
+        let mut channel = BinarySymmetricChannel::new(0.0, 7);
+        assert_eq!(channel.flip_distinct(&mut block, 3..12, 4), 4);
+        assert_eq!(block[0] & 0x07, 0);
+        assert_eq!(block[0].count_ones() + block[1].count_ones(), 4);
+        assert_eq!(channel.flip_distinct(&mut block, 0..2, 5), 2);
+
+        flip_bit(&mut data, 3, 7);
+        flip_bit(&mut data, 16, 0);
+        flip_bit(&mut data, 0, 8);
+        assert_eq!(data[3], 0x25);
+    }
+
+    #[test]
+    fn test_channel_custom_rng() {
+        // Every draw is just below 1.0, so nothing is ever flipped
+        struct Top;
+        impl ChannelRng for Top {
+            fn next_u64(&mut self) -> u64 {
+                u64::MAX
+            }
+        }
+
+        let mut data = [0u8; 8];
+        let mut channel = BinarySymmetricChannel::with_rng(0.999, Top);
+        assert_eq!(channel.corrupt(&mut data), 0);
+        assert_eq!(
+            BinarySymmetricChannel::with_rng(0.01, SplitMix64::new(42)),
+            BinarySymmetricChannel::new(0.01, 42)
+        );
+    }
+
+    #[cfg(feature = "rand_core")]
+    #[test]
+    fn test_channel_rand_core() {
+        use rand_core::RngCore;
+
+        let mut data = [0u8; 64];
+        let mut channel = BinarySymmetricChannel::with_rng(0.5, SplitMix64::new(3));
+        let rng: &mut dyn RngCore = channel.rng();
+        rng.next_u32();
+        let mut boxed = BinarySymmetricChannel::with_rng(0.5, Box::new(SplitMix64::new(3)));
+        boxed.rng().next_u32();
+        assert_eq!(channel.corrupt(&mut data.clone()), boxed.corrupt(&mut data));
+    }
+}
@@ -0,0 +1,140 @@
+use crate::record::crc32;
+use crate::{HammingCode, HammingError};
+use alloc::vec::Vec;
+
+// Payload length and CRC-32, both little-endian
+const TRAILER_LEN: usize = 8;
+
+/// Block ECC plus an end-to-end check: data is cut into frames of
+/// `frame_size` bytes, each encoded together with a trailer holding its
+/// length and a CRC-32
+///
+/// Decoding corrects each frame, then rejects it if the CRC does not match,
+/// which catches the miscorrections of blocks with more errors than the
+/// code handles. Only the last frame may be shorter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckedFrames<C> {
+    code: C,
+    frame_size: usize,
+}
+
+impl<C: HammingCode> CheckedFrames<C> {
+    /// Frames of `frame_size` data bytes (at least 1) encoded with `code`
+    pub fn new(code: C, frame_size: usize) -> Self {
+        Self {
+            code,
+            frame_size: frame_size.max(1),
+        }
+    }
+
+    pub fn frame_size(&self) -> usize {
+        self.frame_size
+    }
+
+    pub fn code(&self) -> &C {
+        &self.code
+    }
+
+    /// Encoded bytes of a full frame
+    pub fn encoded_frame_len(&self) -> usize {
+        self.code
+            .expansion(self.frame_size + TRAILER_LEN)
+            .encoded_len
+    }
+
+    /// Encode `data` frame by frame, each with its trailer
+    pub fn encode(&self, data: &[u8]) -> Vec<u8> {
+        let mut encoded = Vec::new();
+        for payload in data.chunks(self.frame_size) {
+            let mut frame = Vec::with_capacity(payload.len() + TRAILER_LEN);
+            frame.extend_from_slice(payload);
+            frame.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+            frame.extend_from_slice(&crc32(&frame).to_le_bytes());
+            encoded.extend(self.code.encode(&frame));
+        }
+        encoded
+    }
+
+    /// Decode and check every frame, returning the payloads joined
+    ///
+    /// A frame whose CRC does not match fails with
+    /// [`HammingError::UncorrectableErrors`], a stream that does not split
+    /// into frames with [`HammingError::InvalidLength`].
+    pub fn decode(&self, encoded: &[u8]) -> Result<Vec<u8>, HammingError> {
+        let mut data = Vec::new();
+        for frame in encoded.chunks(self.encoded_frame_len()) {
+            let decoded = self.code.decode(frame)?;
+            // Decoded frames carry block padding, so try every frame length
+            // that encodes to this size, the longest first
+            let mut lengths = (TRAILER_LEN..=self.frame_size + TRAILER_LEN)
+                .rev()
+                .filter(|&len| self.code.expansion(len).encoded_len == frame.len())
+                .peekable();
+            if lengths.peek().is_none() {
+                return Err(HammingError::InvalidLength);
+            }
+            let payload = lengths
+                .find_map(|len| Self::check(decoded.get(..len)?))
+                .ok_or(HammingError::UncorrectableErrors)?;
+            data.extend_from_slice(payload);
+        }
+        Ok(data)
+    }
+
+    // The payload of a decoded frame of exactly the right length, if its
+    // trailer matches
+    fn check(frame: &[u8]) -> Option<&[u8]> {
+        let (body, crc) = frame.split_last_chunk::<4>()?;
+        let (payload, len) = body.split_last_chunk::<4>()?;
+        let valid = u32::from_le_bytes(*crc) == crc32(body)
+            && usize::try_from(u32::from_le_bytes(*len)) == Ok(payload.len());
+        valid.then_some(payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Hamming, Hamming74, Hamming1511};
+
+    #[test]
+    fn test_checked_frames_roundtrip() {
+        let data: Vec<u8> = (0..100).map(|i| (i * 31 + 7) as u8).collect();
+        let frames = CheckedFrames::new(Hamming1511, 32);
+        for len in [0, 1, 31, 32, 33, 100] {
+            let mut encoded = frames.encode(&data[..len]);
+            // One correctable error per frame
+            for frame in encoded.chunks_mut(frames.encoded_frame_len()) {
+                frame[3] ^= 0x10;
+            }
+            assert_eq!(frames.decode(&encoded).unwrap(), data[..len]);
+        }
+
+        let frames = CheckedFrames::new(Hamming::new(26).with_extended(true), 10);
+        let encoded = frames.encode(&data[..25]);
+        assert_eq!(frames.decode(&encoded).unwrap(), data[..25]);
+    }
+
+    #[test]
+    fn test_checked_frames_reject_miscorrection() {
+        let frames = CheckedFrames::new(Hamming74, 16);
+        let mut encoded = frames.encode(b"end-to-end check");
+        // Two errors in one block miscorrect silently without the CRC
+        encoded[0] ^= 0x03;
+        assert!(Hamming74.decode(&encoded).is_ok());
+        assert_eq!(
+            frames.decode(&encoded),
+            Err(HammingError::UncorrectableErrors)
+        );
+
+        let encoded = frames.encode(b"short");
+        assert_eq!(
+            frames.decode(&encoded[..encoded.len() - 2]),
+            Err(HammingError::UncorrectableErrors)
+        );
+        assert_eq!(
+            CheckedFrames::new(Hamming1511, 16).decode(&[0; 3]),
+            Err(HammingError::InvalidLength)
+        );
+    }
+}
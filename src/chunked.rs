@@ -0,0 +1,95 @@
+//! Encoding in bounded pieces, so peak memory stays near the size of the
+//! output rather than input, output and intermediate copies
+
+use crate::HammingCode;
+use alloc::vec::Vec;
+
+// Approximate data bytes encoded at a time
+const PIECE_BYTES: usize = 64 * 1024;
+
+/// Data bytes per piece for `code`: whole [`HammingCode::chunk_sizes`]
+/// units, so pieces encode to whole blocks and their encodings concatenate
+/// to the encoding of the whole
+pub(crate) fn piece_len<C: HammingCode + ?Sized>(code: &C) -> usize {
+    let chunk = code.chunk_sizes().0.max(1);
+    PIECE_BYTES.div_ceil(chunk) * chunk
+}
+
+/// Encoded bytes per piece for `code`: the encoding of [`piece_len`] data
+/// bytes, so pieces decode on their own
+pub(crate) fn encoded_piece_len<C: HammingCode + ?Sized>(code: &C) -> usize {
+    let (data, encoded) = code.chunk_sizes();
+    piece_len(code) / data.max(1) * encoded
+}
+
+/// Encodes data handed over in parts, e.g. a header, the caller's data and
+/// a trailer, without first copying them into one buffer
+pub(crate) struct ChunkedEncoder<'a, C: ?Sized> {
+    code: &'a C,
+    piece: usize,
+    carry: Vec<u8>,
+    out: Vec<u8>,
+}
+
+impl<'a, C: HammingCode + ?Sized> ChunkedEncoder<'a, C> {
+    /// Encoder for `len` bytes in total, for which the output is reserved
+    pub(crate) fn new(code: &'a C, len: usize) -> Self {
+        Self {
+            code,
+            piece: piece_len(code),
+            carry: Vec::new(),
+            out: Vec::with_capacity(code.expansion(len).encoded_len),
+        }
+    }
+
+    pub(crate) fn push(&mut self, mut bytes: &[u8]) {
+        if !self.carry.is_empty() {
+            let (head, rest) = bytes.split_at(bytes.len().min(self.piece - self.carry.len()));
+            self.carry.extend_from_slice(head);
+            bytes = rest;
+            if self.carry.len() < self.piece {
+                return;
+            }
+            self.out.extend(self.code.encode(&self.carry));
+            self.carry.clear();
+        }
+
+        let (pieces, rest) = bytes.split_at(bytes.len() / self.piece * self.piece);
+        for piece in pieces.chunks(self.piece) {
+            self.out.extend(self.code.encode(piece));
+        }
+        self.carry.extend_from_slice(rest);
+    }
+
+    pub(crate) fn finish(mut self) -> Vec<u8> {
+        if !self.carry.is_empty() {
+            self.out.extend(self.code.encode(&self.carry));
+        }
+        self.out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TERMINATOR, sample_codecs};
+
+    #[test]
+    fn test_chunked_encoder_matches_encode() {
+        let data: Vec<u8> = (0..200_000).map(|i| (i * 31 + i / 7) as u8).collect();
+        let codecs = sample_codecs();
+        for codec in &codecs {
+            for split in [0, 1, 5, 70_000, 131_072] {
+                let (head, tail) = data.split_at(split);
+                let mut encoder = ChunkedEncoder::new(codec.as_ref(), data.len() + 1);
+                encoder.push(head);
+                encoder.push(tail);
+                encoder.push(&[TERMINATOR]);
+                assert_eq!(encoder.finish(), codec.encode_terminated(&data));
+            }
+            let mut whole = data.clone();
+            whole.push(TERMINATOR);
+            assert_eq!(codec.encode_terminated(&data), codec.encode(&whole));
+        }
+    }
+}
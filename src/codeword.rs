@@ -0,0 +1,204 @@
+use crate::{BlockOutcome, Hamming74, Hamming1511};
+#[cfg(feature = "alloc")]
+use crate::{Hamming, HammingCode, HammingError};
+
+/// A valid Hamming(7,4) codeword, as [`Hamming74`] stores it in one byte
+///
+/// Only built by encoding, by [`Codeword74::new`] after checking or by
+/// [`Codeword74::correct`], so holding one means it has been validated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Codeword74(u8);
+
+impl Codeword74 {
+    /// Codeword of the low nibble of `data`
+    pub const fn encode(data: u8) -> Self {
+        Self(Hamming74::encode_codeword(data))
+    }
+
+    /// `raw` if it is a codeword, with a zero syndrome and bit 7 clear
+    pub fn new(raw: u8) -> Option<Self> {
+        let (_, outcome) = Hamming74::decode_codeword(raw);
+        (outcome == BlockOutcome::Clean && raw & 0x80 == 0).then_some(Self(raw))
+    }
+
+    /// The codeword nearest to `raw`, and what was corrected; bit 7 is
+    /// cleared
+    pub fn correct(raw: u8) -> (Self, BlockOutcome) {
+        let (data, outcome) = Hamming74::decode_codeword(raw);
+        (Self::encode(data), outcome)
+    }
+
+    pub fn get(self) -> u8 {
+        self.0
+    }
+
+    /// The data nibble
+    pub fn data(self) -> u8 {
+        Hamming74::decode_codeword(self.0).0
+    }
+}
+
+impl From<Codeword74> for u8 {
+    fn from(codeword: Codeword74) -> u8 {
+        codeword.0
+    }
+}
+
+/// A valid Hamming(15,11) codeword, as [`Hamming1511`] stores it in two
+/// bytes
+///
+/// Only built by encoding, by [`Codeword1511::new`] after checking or by
+/// [`Codeword1511::correct`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Codeword1511(u16);
+
+impl Codeword1511 {
+    /// Codeword of the low 11 bits of `data`
+    pub fn encode(data: u16) -> Self {
+        Self(Hamming1511::encode_codeword(data))
+    }
+
+    /// `raw` if it is a codeword, with a zero syndrome and bit 15 clear
+    pub fn new(raw: u16) -> Option<Self> {
+        let (_, outcome) = Hamming1511::decode_codeword(raw);
+        (outcome == BlockOutcome::Clean && raw & 0x8000 == 0).then_some(Self(raw))
+    }
+
+    /// The codeword nearest to `raw`, and what was corrected; bit 15 is
+    /// cleared
+    pub fn correct(raw: u16) -> (Self, BlockOutcome) {
+        let (data, outcome) = Hamming1511::decode_codeword(raw);
+        (Self::encode(data), outcome)
+    }
+
+    pub fn get(self) -> u16 {
+        self.0
+    }
+
+    /// The 11 data bits
+    pub fn data(self) -> u16 {
+        Hamming1511::decode_codeword(self.0).0
+    }
+}
+
+impl From<Codeword1511> for u16 {
+    fn from(codeword: Codeword1511) -> u16 {
+        codeword.0
+    }
+}
+
+/// A valid codeword of a general code with blocks of up to 128 bits, bit
+/// `i` holding codeword position `i`
+///
+/// Carries its code, so a word is never checked against the wrong one.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HammingWord {
+    code: Hamming,
+    word: u128,
+}
+
+#[cfg(feature = "alloc")]
+impl HammingWord {
+    /// Codeword of the low `data_bits` bits of `data`
+    pub fn encode(code: &Hamming, data: u128) -> Result<Self, HammingError> {
+        Self::check(code)?;
+        Ok(Self {
+            code: *code,
+            word: code.encode_word(data),
+        })
+    }
+
+    /// `word` if it is a codeword of `code`
+    pub fn new(code: &Hamming, word: u128) -> Result<Option<Self>, HammingError> {
+        Self::check(code)?;
+        let unused = word.checked_shr(code.block_size() as u32).unwrap_or(0);
+        let (_, _, outcome) = code.decode_word(word);
+        Ok((outcome == BlockOutcome::Clean && unused == 0).then_some(Self { code: *code, word }))
+    }
+
+    /// The codeword nearest to `word`, and what was corrected
+    pub fn correct(code: &Hamming, word: u128) -> Result<(Self, BlockOutcome), HammingError> {
+        Self::check(code)?;
+        let (data, syndrome, outcome) = code.decode_word(word);
+        if outcome == BlockOutcome::Uncorrectable {
+            return Err(code.uncorrectable(0, syndrome));
+        }
+        Ok((Self::encode(code, data)?, outcome))
+    }
+
+    pub fn code(&self) -> &Hamming {
+        &self.code
+    }
+
+    pub fn get(&self) -> u128 {
+        self.word
+    }
+
+    /// The data bits
+    pub fn data(&self) -> u128 {
+        self.code.decode_word(self.word).0
+    }
+
+    fn check(code: &Hamming) -> Result<(), HammingError> {
+        if code.data_bits() == 0 || code.block_size() > 128 {
+            return Err(HammingError::Unsupported);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_codewords() {
+        for data in 0..16 {
+            let codeword = Codeword74::encode(data);
+            assert_eq!(Codeword74::new(codeword.get()), Some(codeword));
+            assert_eq!(codeword.data(), data);
+            assert_eq!(Codeword74::new(codeword.get() ^ 0x02), None);
+            assert_eq!(Codeword74::new(codeword.get() | 0x80), None);
+            assert_eq!(
+                Codeword74::correct(codeword.get() ^ 0x40),
+                (codeword, BlockOutcome::Corrected { bit: 6 })
+            );
+        }
+        let mut encoded = [0; 2];
+        assert_eq!(Hamming74.encode_to_slice(&[0x0B], &mut encoded), Ok(2));
+        assert_eq!(u8::from(Codeword74::encode(0x0B)), encoded[0]);
+
+        let codeword = Codeword1511::encode(0x5A3);
+        assert_eq!(codeword.data(), 0x5A3);
+        assert_eq!(Codeword1511::new(codeword.get()), Some(codeword));
+        assert_eq!(Codeword1511::new(codeword.get() ^ 0x0100), None);
+        assert_eq!(
+            Codeword1511::correct(codeword.get() ^ 0x8001),
+            (codeword, BlockOutcome::Corrected { bit: 0 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_general_codewords() {
+        let code = Hamming::new(64).with_extended(true);
+        let word = HammingWord::encode(&code, 0xDEAD_BEEF).unwrap();
+        assert_eq!(word.data(), 0xDEAD_BEEF);
+        assert_eq!(HammingWord::new(&code, word.get()), Ok(Some(word)));
+        assert_eq!(HammingWord::new(&code, word.get() ^ 1 << 70), Ok(None));
+        assert_eq!(HammingWord::new(&code, word.get() | 1 << 72), Ok(None));
+        assert_eq!(
+            HammingWord::correct(&code, word.get() ^ 1 << 9).map(|(w, _)| w),
+            Ok(word)
+        );
+        assert_eq!(
+            HammingWord::correct(&code, word.get() ^ 0b11 << 9),
+            Err(HammingError::DoubleErrorDetected { block: 0 })
+        );
+        assert_eq!(
+            HammingWord::encode(&Hamming::new(247), 1),
+            Err(HammingError::Unsupported)
+        );
+    }
+}
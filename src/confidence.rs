@@ -0,0 +1,114 @@
+use crate::HammingCode;
+
+/// Estimated trust in decoded data, from the corrections made while
+/// decoding it
+///
+/// The channel bit error rate is estimated from the corrections as
+/// `(corrected + 1) / (bits + 2)` (Laplace's rule), so a clean decode of a
+/// short stream is not taken as proof of a perfect channel. Blocks are
+/// assumed to be hit by independent bit errors at that rate; a block is
+/// counted as silently wrong when it has more errors than the code corrects
+/// (two), or detects (three, for SEC-DED codes). Residual errors of
+/// uncorrectable blocks are not included, as those fail the decode.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Confidence {
+    /// Blocks decoded
+    pub blocks: usize,
+    /// Bits corrected
+    pub corrected: usize,
+    /// Estimated channel bit error rate
+    pub bit_error_rate: f64,
+    /// Estimated probability that a single block decoded to wrong data
+    pub block_error: f64,
+    /// Estimated probability that any block decoded to wrong data
+    pub residual_error: f64,
+}
+
+impl Confidence {
+    /// Estimate for `blocks` blocks of `code` with `corrected` corrected
+    /// bits
+    pub fn estimate<C: HammingCode + ?Sized>(code: &C, blocks: usize, corrected: usize) -> Self {
+        let n = code.block_size();
+        let bits = blocks.saturating_mul(n);
+        let p = (corrected as f64 + 1.0) / (bits as f64 + 2.0);
+        // Fewest errors in a block that can pass as a valid decode
+        let silent = if code.detects_double_errors() { 3 } else { 2 };
+
+        let block_error = binomial_tail(n, silent, p);
+        let residual_error = -f64::exp_m1(blocks as f64 * f64::ln_1p(-block_error));
+        Self {
+            blocks,
+            corrected,
+            bit_error_rate: p,
+            block_error,
+            residual_error,
+        }
+    }
+
+    /// Probability that the decoded data is entirely correct
+    pub fn trust(&self) -> f64 {
+        1.0 - self.residual_error
+    }
+}
+
+// Probability of at least `k` errors among `n` bits with error rate `p`,
+// summed from the `k`-error term up so small tails keep their precision
+fn binomial_tail(n: usize, k: usize, p: f64) -> f64 {
+    if k > n || p <= 0.0 {
+        return 0.0;
+    }
+    let ln_choose: f64 = (0..k)
+        .map(|j| ((n - j) as f64).ln() - ((j + 1) as f64).ln())
+        .sum();
+    let mut term = f64::exp(ln_choose + k as f64 * p.ln() + (n - k) as f64 * f64::ln_1p(-p));
+    let ratio = p / (1.0 - p);
+
+    let mut sum = 0.0;
+    for i in k..=n {
+        sum += term;
+        // Past the peak of the distribution, stop once terms stop counting
+        if term <= sum * f64::EPSILON && (i as f64) > n as f64 * p {
+            break;
+        }
+        term *= (n - i) as f64 / (i + 1) as f64 * ratio;
+    }
+    sum.min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Hamming, Hamming74};
+
+    #[test]
+    fn test_binomial_tail() {
+        // P(X >= 2) for X ~ B(7, 0.1)
+        let exact = 1.0 - 0.9f64.powi(7) - 7.0 * 0.1 * 0.9f64.powi(6);
+        assert!((binomial_tail(7, 2, 0.1) - exact).abs() < 1e-15);
+        // 21 p^2 for small p, where 1 - P(X < 2) would round to zero
+        let tail = binomial_tail(7, 2, 1e-10);
+        assert!((tail / 21e-20 - 1.0).abs() < 1e-6);
+        assert_eq!(binomial_tail(7, 8, 0.5), 0.0);
+    }
+
+    #[test]
+    fn test_confidence_estimate() {
+        let clean = Confidence::estimate(&Hamming74, 100_000, 0);
+        let noisy = Confidence::estimate(&Hamming74, 100_000, 7_000);
+        assert!(clean.trust() > 0.99);
+        assert!(noisy.trust() < 1e-6);
+        assert!((noisy.bit_error_rate - 0.01).abs() < 1e-4);
+
+        // Double-error detection leaves far fewer silent errors
+        let plain = Confidence::estimate(&Hamming::new(64), 1000, 100);
+        let secded = Confidence::estimate(&Hamming::new(64).with_extended(true), 1000, 100);
+        assert!(secded.block_error < plain.block_error / 10.0);
+
+        let mut encoded = Hamming74.encode(b"trust");
+        encoded[3] ^= 0x01;
+        let (decoded, confidence) = Hamming74.decode_with_confidence(&encoded).unwrap();
+        assert_eq!(decoded, b"trust");
+        assert_eq!((confidence.blocks, confidence.corrected), (10, 1));
+    }
+}
@@ -0,0 +1,233 @@
+use crate::{BlockOutcome, HammingCode, HammingError, TERMINATOR};
+use std::io::{self, Read, Write};
+
+// Approximate size of the internal buffer; rounded to whole chunk units
+const BUFFER_SIZE: usize = 8192;
+
+/// Byte and correction counts from [`encode_copy`]/[`decode_copy`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CopyStats {
+    pub bytes_read: u64,
+    pub bytes_written: u64,
+    /// Single-bit errors fixed while decoding; always 0 when encoding
+    pub corrected_bits: u64,
+}
+
+impl From<HammingError> for io::Error {
+    fn from(e: HammingError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, e)
+    }
+}
+
+/// Encode everything from `reader` into `writer`
+///
+/// The output is identical to encoding the whole input in one call.
+pub fn encode_copy<C: HammingCode + ?Sized>(
+    reader: impl Read,
+    writer: impl Write,
+    codec: &C,
+) -> io::Result<CopyStats> {
+    let (unit, _) = codec.chunk_sizes();
+    copy_chunks(reader, writer, unit, |chunk, _| Ok(codec.encode(chunk)))
+}
+
+/// Decode everything from `reader` into `writer`
+///
+/// Decode failures are reported as [`io::ErrorKind::InvalidData`] wrapping
+/// the [`HammingError`]; output for earlier chunks has already been written.
+pub fn decode_copy<C: HammingCode + ?Sized>(
+    reader: impl Read,
+    writer: impl Write,
+    codec: &C,
+) -> io::Result<CopyStats> {
+    let (_, unit) = codec.chunk_sizes();
+    copy_chunks(reader, writer, unit, |chunk, stats| {
+        codec.decode_observed(chunk, &mut |event| {
+            if let BlockOutcome::Corrected { .. } = event.outcome {
+                stats.corrected_bits += 1;
+            }
+        })
+    })
+}
+
+/// Encode everything from `reader` followed by a [`TERMINATOR`] byte, the
+/// streaming form of [`HammingCode::encode_terminated`]
+pub fn encode_copy_terminated<C: HammingCode + ?Sized>(
+    reader: impl Read,
+    writer: impl Write,
+    codec: &C,
+) -> io::Result<CopyStats> {
+    let mut stats = encode_copy(reader.chain(&[TERMINATOR][..]), writer, codec)?;
+    stats.bytes_read -= 1;
+    Ok(stats)
+}
+
+/// Decode a stream written by [`encode_copy_terminated`], stripping the
+/// padding and the terminator, so `writer` gets back exactly the input
+///
+/// The last data unit is held back until the end of the stream; a stream
+/// without the terminator fails with [`HammingError::MissingTerminator`]
+/// after everything before that unit has been written.
+pub fn decode_copy_terminated<C: HammingCode + ?Sized>(
+    reader: impl Read,
+    writer: impl Write,
+    codec: &C,
+) -> io::Result<CopyStats> {
+    let (unit, _) = codec.chunk_sizes();
+    let mut tail = Unterminate {
+        writer,
+        held: Vec::new(),
+        keep: unit.max(1),
+    };
+    let mut stats = decode_copy(reader, &mut tail, codec)?;
+    stats.bytes_written -= tail.finish()? as u64;
+    Ok(stats)
+}
+
+// Writer holding back the last `keep` bytes, which contain the terminator
+// and the padding after it
+struct Unterminate<W> {
+    writer: W,
+    held: Vec<u8>,
+    keep: usize,
+}
+
+impl<W: Write> Unterminate<W> {
+    // Write the data before the terminator, returning the bytes stripped
+    fn finish(mut self) -> io::Result<usize> {
+        let held = self.held.len();
+        while self.held.last() == Some(&0) {
+            self.held.pop();
+        }
+        if self.held.pop() != Some(TERMINATOR) {
+            return Err(HammingError::MissingTerminator.into());
+        }
+        self.writer.write_all(&self.held)?;
+        self.writer.flush()?;
+        Ok(held - self.held.len())
+    }
+}
+
+impl<W: Write> Write for Unterminate<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.held.extend_from_slice(buf);
+        let ready = self.held.len().saturating_sub(self.keep);
+        if ready > 0 {
+            let rest = self.held.split_off(ready);
+            self.writer.write_all(&self.held)?;
+            self.held = rest;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+// Read whole multiples of `unit`, code them and write the result
+fn copy_chunks(
+    mut reader: impl Read,
+    mut writer: impl Write,
+    unit: usize,
+    mut code: impl FnMut(&[u8], &mut CopyStats) -> Result<Vec<u8>, HammingError>,
+) -> io::Result<CopyStats> {
+    let capacity = BUFFER_SIZE.div_ceil(unit) * unit;
+    let mut buffer = Vec::with_capacity(capacity);
+    let mut stats = CopyStats::default();
+
+    loop {
+        buffer.clear();
+        let filled = (&mut reader)
+            .take(capacity as u64)
+            .read_to_end(&mut buffer)?;
+        if filled == 0 {
+            break;
+        }
+
+        let output = code(&buffer, &mut stats)?;
+        writer.write_all(&output)?;
+        stats.bytes_read += filled as u64;
+        stats.bytes_written += output.len() as u64;
+
+        // A short read means end of input
+        if filled < capacity {
+            break;
+        }
+    }
+
+    writer.flush()?;
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Hamming74, Hamming1511, sample_codecs};
+
+    #[test]
+    fn test_copy_matches_slice_api() {
+        let codecs = sample_codecs();
+        let data: Vec<u8> = (0..20_000).map(|i| (i * 31 + 7) as u8).collect();
+
+        for codec in &codecs {
+            let mut encoded = Vec::new();
+            let stats = encode_copy(&data[..], &mut encoded, codec.as_ref()).unwrap();
+            assert_eq!(encoded, codec.encode(&data));
+            assert_eq!(stats.bytes_read, data.len() as u64);
+            assert_eq!(stats.bytes_written, encoded.len() as u64);
+
+            let mut decoded = Vec::new();
+            decode_copy(&encoded[..], &mut decoded, codec.as_ref()).unwrap();
+            assert_eq!(decoded, codec.decode(&encoded).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_decode_copy_counts_corrections() {
+        let data = [0x5Au8; 100];
+        let mut encoded = Hamming74.encode(&data);
+        encoded[3] ^= 0x01;
+        encoded[150] ^= 0x10;
+
+        let mut decoded = Vec::new();
+        let stats = decode_copy(&encoded[..], &mut decoded, &Hamming74).unwrap();
+        assert_eq!(decoded, data);
+        assert_eq!(stats.corrected_bits, 2);
+    }
+
+    #[test]
+    fn test_copy_terminated_exact_length() {
+        let codecs = sample_codecs();
+        for codec in &codecs {
+            for len in [0, 1, 10, 1000, 20_000] {
+                let data: Vec<u8> = (0..len).map(|i| (i * 7) as u8).collect();
+                let mut encoded = Vec::new();
+                let stats =
+                    encode_copy_terminated(&data[..], &mut encoded, codec.as_ref()).unwrap();
+                assert_eq!(encoded, codec.encode_terminated(&data));
+                assert_eq!(stats.bytes_read, len as u64);
+
+                let mut decoded = Vec::new();
+                let stats =
+                    decode_copy_terminated(&encoded[..], &mut decoded, codec.as_ref()).unwrap();
+                assert_eq!(decoded, data);
+                assert_eq!(stats.bytes_written, len as u64);
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_copy_missing_terminator() {
+        let encoded = Hamming1511.encode(&[1, 2, 3]);
+        let err = decode_copy_terminated(&encoded[..], io::sink(), &Hamming1511).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_decode_copy_invalid_length() {
+        let err = decode_copy(&[0u8; 3][..], io::sink(), &Hamming1511).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
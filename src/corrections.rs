@@ -0,0 +1,134 @@
+use crate::chunked::encoded_piece_len;
+use crate::{BlockOutcome, HammingCode, HammingError};
+use alloc::collections::VecDeque;
+
+/// Offsets in the encoded stream of the bits a decode corrects, see
+/// [`HammingCode::correction_offsets`]
+///
+/// The stream is decoded a piece of about 64 KiB at a time as the iterator
+/// advances, so offsets of a large stream come without decoding it whole.
+/// Offsets count LSB-first within each byte, as
+/// [`flip_bit`](crate::flip_bit) takes them (`offset / 8`, `offset % 8`),
+/// and rise within each block. A decode error ends the iteration after
+/// the offsets of the pieces before it.
+#[derive(Debug)]
+pub struct CorrectionOffsets<'a, C: ?Sized> {
+    code: &'a C,
+    encoded: &'a [u8],
+    piece: usize,
+    start: usize,
+    blocks: usize,
+    pending: VecDeque<usize>,
+}
+
+impl<'a, C: HammingCode + ?Sized> CorrectionOffsets<'a, C> {
+    pub(crate) fn new(code: &'a C, encoded: &'a [u8]) -> Self {
+        Self {
+            code,
+            encoded,
+            piece: encoded_piece_len(code).max(1),
+            start: 0,
+            blocks: 0,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Codewords decoded so far, including those of the piece whose
+    /// offsets are still being yielded
+    pub fn blocks(&self) -> usize {
+        self.blocks
+    }
+
+    // Decode the next piece, queueing its offsets
+    fn decode_piece(&mut self) -> Result<(), HammingError> {
+        let rest = self.encoded.len() - self.start;
+        // The last piece takes the remainder, so it ends the stream as a
+        // whole decode would
+        let len = if rest < 2 * self.piece {
+            rest
+        } else {
+            self.piece
+        };
+        let piece = self
+            .encoded
+            .get(self.start..self.start + len)
+            .unwrap_or_default();
+        let base = self.start.saturating_mul(8);
+        self.start += len;
+
+        let (code, pending, blocks) = (self.code, &mut self.pending, &mut self.blocks);
+        code.decode_observed(piece, &mut |event| {
+            *blocks += 1;
+            if let BlockOutcome::Corrected { bit } = event.outcome {
+                pending.push_back(base + code.encoded_bit_offset(len, event.block, bit));
+            }
+        })?;
+        Ok(())
+    }
+}
+
+impl<C: HammingCode + ?Sized> Iterator for CorrectionOffsets<'_, C> {
+    type Item = Result<usize, HammingError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.is_empty() {
+            if self.start >= self.encoded.len() {
+                return None;
+            }
+            if let Err(err) = self.decode_piece() {
+                self.start = self.encoded.len();
+                return Some(Err(err));
+            }
+        }
+        self.pending.pop_front().map(Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        BitOrder, Hamming, Hamming74, HammingCode, HammingError, Interleaved, flip_bit,
+        sample_codecs,
+    };
+    use alloc::{boxed::Box, vec::Vec};
+
+    #[test]
+    fn test_correction_offsets_match_flips() {
+        let data: Vec<u8> = (0..200_000).map(|i| (i * 29 + i / 3) as u8).collect();
+        let mut codecs = sample_codecs();
+        codecs.push(Box::new(
+            Hamming::new(26).with_bit_order(BitOrder::MsbFirst),
+        ));
+        codecs.push(Box::new(Interleaved::new(Hamming74, 2)));
+        for codec in &codecs {
+            let mut encoded = codec.encode(&data);
+            // Far enough apart to land in different blocks
+            let flips: Vec<usize> = (0..10).map(|i| 3 + i * encoded.len() * 8 / 10).collect();
+            for &offset in &flips {
+                flip_bit(&mut encoded, offset / 8, (offset % 8) as u32);
+            }
+            let offsets: Result<Vec<_>, _> = codec.correction_offsets(&encoded).collect();
+            assert_eq!(offsets.unwrap(), flips);
+        }
+    }
+
+    #[test]
+    fn test_correction_offsets_error() {
+        let code = Hamming::new(64).with_extended(true);
+        let mut encoded = code.encode(&[0x5A; 16]);
+        encoded[1] ^= 0x01;
+        assert_eq!(
+            code.correction_offsets(&encoded).collect::<Vec<_>>(),
+            [Ok(8)]
+        );
+
+        encoded[2] ^= 0x01;
+        let mut offsets = code.correction_offsets(&encoded);
+        assert_eq!(
+            offsets.next(),
+            Some(Err(HammingError::DoubleErrorDetected { block: 0 }))
+        );
+        assert_eq!(offsets.next(), None);
+        assert_eq!(Hamming74.correction_offsets(&[]).next(), None);
+    }
+}
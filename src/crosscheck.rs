@@ -0,0 +1,135 @@
+use crate::selftest::data_values;
+use crate::{BlockOutcome, CodeSpec, HammingCode, HammingError};
+use alloc::vec::Vec;
+
+/// Result of [`cross_check`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CrossCheckReport {
+    pub spec: CodeSpec,
+    /// Data values tested, every value of a block for codes of up to 16
+    /// data bits
+    pub data_values: u64,
+    /// Encodings and clean or single-error decodes compared
+    pub cases: u64,
+    /// Cases in which the two codecs disagreed
+    pub mismatches: u64,
+    /// The first disagreement: the data value, and the flipped codeword
+    /// position of block 0 unless the encodings differed
+    pub first_mismatch: Option<(u64, Option<usize>)>,
+}
+
+impl CrossCheckReport {
+    pub fn passed(&self) -> bool {
+        self.mismatches == 0
+    }
+}
+
+/// Differential check of the codec `spec` builds against the general
+/// [`Hamming`](crate::Hamming) code with the same block layout
+///
+/// The declared mapping is that both hold the same codewords, codeword
+/// position `i` in block bit `i`, and differ only in how blocks sit in the
+/// stream: [`Hamming74`](crate::Hamming74) and
+/// [`Hamming1511`](crate::Hamming1511) give each block a byte or two bytes
+/// (its [`HammingCode::block_stride`]), the general code packs them. So for
+/// every data value the codewords of both streams must match, and for the
+/// clean codeword and each single-bit error in block 0 both decoders must
+/// report the same syndrome and outcome per block and return the same data.
+pub fn cross_check(spec: &CodeSpec) -> CrossCheckReport {
+    let dedicated = spec.build();
+    let general = spec.general_equivalent().0;
+    let (n, k) = (dedicated.block_size(), dedicated.data_bits());
+    let mut report = CrossCheckReport {
+        spec: *spec,
+        data_values: 0,
+        cases: 0,
+        mismatches: 0,
+        first_mismatch: None,
+    };
+    let record = |report: &mut CrossCheckReport, agree: bool, case| {
+        report.cases += 1;
+        if !agree {
+            report.mismatches += 1;
+            report.first_mismatch = report.first_mismatch.or(Some(case));
+        }
+    };
+
+    for value in data_values(k) {
+        report.data_values += 1;
+        let data: Vec<u8> = (0..k.div_ceil(8))
+            .map(|byte| value.checked_shr(8 * byte as u32).unwrap_or(0) as u8)
+            .collect();
+        let encoded = (dedicated.encode(&data), general.encode(&data));
+        let agree = codewords(dedicated.as_ref(), &encoded.0) == codewords(&general, &encoded.1)
+            && dedicated.block_size() == general.block_size();
+        record(&mut report, agree, (value, None));
+
+        for bit in 0..n {
+            let dedicated = decode_flipped(dedicated.as_ref(), &encoded.0, bit);
+            let general = decode_flipped(&general, &encoded.1, bit);
+            let agree = dedicated.1 == general.1
+                && match (&dedicated.0, &general.0) {
+                    (Ok(a), Ok(b)) => a.get(..data.len()) == b.get(..data.len()),
+                    (a, b) => a == b,
+                };
+            record(&mut report, agree, (value, Some(bit)));
+        }
+    }
+    report
+}
+
+// Codewords of a stream, in order
+fn codewords(code: &dyn HammingCode, encoded: &[u8]) -> Result<Vec<u128>, HammingError> {
+    Ok(code.blocks(encoded)?.map(|block| block.word).collect())
+}
+
+type Events = Vec<(usize, usize, BlockOutcome)>;
+
+// Decode `encoded` with codeword position `bit` of block 0 flipped,
+// recording every block's index, syndrome and outcome
+fn decode_flipped(
+    code: &dyn HammingCode,
+    encoded: &[u8],
+    bit: usize,
+) -> (Result<Vec<u8>, HammingError>, Events) {
+    let mut received = encoded.to_vec();
+    let offset = code.encoded_bit_offset(received.len(), 0, bit);
+    if let Some(byte) = received.get_mut(offset / 8) {
+        *byte ^= 1 << (offset % 8);
+    }
+
+    let mut events = Vec::new();
+    let decoded = code.decode_observed(&received, &mut |event| {
+        events.push((event.block, event.syndrome, event.outcome))
+    });
+    (decoded, events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cross_check_exhaustive() {
+        let report = cross_check(&CodeSpec::Hamming74);
+        assert_eq!(report.data_values, 16);
+        assert_eq!(report.cases, 16 * 8);
+        assert!(report.passed(), "{report:?}");
+
+        let report = cross_check(&CodeSpec::Hamming1511);
+        assert_eq!(report.data_values, 2048);
+        assert_eq!(report.cases, 2048 * 16);
+        assert!(report.passed(), "{report:?}");
+    }
+
+    #[test]
+    fn test_cross_check_general_codes() {
+        for spec in [
+            CodeSpec::General { data_bits: 8 },
+            CodeSpec::Secded { data_bits: 11 },
+        ] {
+            assert!(cross_check(&spec).passed(), "{spec}");
+        }
+    }
+}
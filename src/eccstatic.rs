@@ -0,0 +1,214 @@
+use crate::{Hamming74, HammingError};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Hamming(7,4)-encoded constant data, usually made by [`include_ecc!`]
+///
+/// The encoded bytes live in a `static`, i.e. in flash on most embedded
+/// targets. They cannot be written back, so [`EccStatic::decode`] corrects
+/// a single flipped bit per codeword on every call;
+/// [`EccStatic::corrected_bits`] tells how many there are.
+///
+/// [`include_ecc!`]: crate::include_ecc
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EccStatic {
+    encoded: &'static [u8],
+}
+
+/// Embed Hamming(7,4)-encoded data as a `static`, encoded at compile time
+///
+/// Takes any constant `&[u8]` expression, such as a byte string, or
+/// `file: "path"` to include a file the way `include_bytes!` does (relative
+/// to the current source file). Evaluates to an [`EccStatic`](crate::EccStatic).
+///
+/// ```
+/// use hamming_rs::{EccStatic, include_ecc};
+///
+/// static GREETING: EccStatic = include_ecc!(b"hello");
+/// let mut decoded = [0; 5];
+/// GREETING.decode_to_slice(&mut decoded)?;
+/// assert_eq!(decoded, *b"hello");
+/// # Ok::<(), hamming_rs::HammingError>(())
+/// ```
+#[macro_export]
+macro_rules! include_ecc {
+    (file: $path:literal) => {
+        $crate::include_ecc!(include_bytes!($path))
+    };
+    ($data:expr) => {{
+        const DATA: &[u8] = $data;
+        static ENCODED: [u8; DATA.len() * 2] = $crate::EccStatic::encode_array(DATA);
+        $crate::EccStatic::from_encoded(&ENCODED)
+    }};
+}
+
+/// Encode a constant `&[u8]` expression at compile time, evaluating to a
+/// `const` array of the encoded bytes
+///
+/// The code is [`Hamming74`](crate::Hamming74),
+/// [`Hamming1511`](crate::Hamming1511) or
+/// [`Hamming127120`](crate::Hamming127120); the bytes are the same as its
+/// [`HammingCode::encode`](crate::HammingCode::encode) returns.
+///
+/// ```
+/// use hamming_rs::{Hamming74, Hamming1511, hamming_encode};
+///
+/// const MAGIC: [u8; 8] = hamming_encode!(Hamming74, b"BOOT");
+/// const BANNER: &[u8] = &hamming_encode!(Hamming1511, b"boot v1.2");
+/// let mut encoded = [0; 8];
+/// Hamming74.encode_to_slice(b"BOOT", &mut encoded)?;
+/// assert_eq!(MAGIC, encoded);
+/// let mut decoded = [0; 9];
+/// Hamming1511.decode_to_slice(BANNER, &mut decoded)?;
+/// assert_eq!(decoded, *b"boot v1.2");
+/// # Ok::<(), hamming_rs::HammingError>(())
+/// ```
+#[macro_export]
+macro_rules! hamming_encode {
+    ($code:ty, $data:expr) => {{
+        const DATA: &[u8] = $data;
+        const ENCODED: [u8; <$code>::encoded_len(DATA.len())] = <$code>::encode_array(DATA);
+        ENCODED
+    }};
+}
+
+impl EccStatic {
+    /// Wrap `encoded`, two codewords per byte as [`Hamming74`] encodes them;
+    /// a trailing odd byte is ignored
+    pub const fn from_encoded(encoded: &'static [u8]) -> Self {
+        Self { encoded }
+    }
+
+    /// Encode `data` at compile time; `M` must be twice its length
+    ///
+    /// Used by [`include_ecc!`](crate::include_ecc).
+    #[doc(hidden)]
+    pub const fn encode_array<const M: usize>(data: &[u8]) -> [u8; M] {
+        Hamming74::encode_array(data)
+    }
+
+    /// Number of data bytes
+    pub fn len(&self) -> usize {
+        Hamming74::decoded_len(self.encoded.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The corrected data
+    #[cfg(feature = "alloc")]
+    pub fn decode(&self) -> Vec<u8> {
+        self.blocks()
+            .map(|blocks| Hamming74::decode_pair(blocks).0)
+            .collect()
+    }
+
+    /// Write the corrected data to the front of `out`, returning its length,
+    /// or [`HammingError::BufferTooSmall`] if it does not fit
+    pub fn decode_to_slice(&self, out: &mut [u8]) -> Result<usize, HammingError> {
+        let out = out
+            .get_mut(..self.len())
+            .ok_or(HammingError::BufferTooSmall)?;
+        for (slot, blocks) in out.iter_mut().zip(self.blocks()) {
+            *slot = Hamming74::decode_pair(blocks).0;
+        }
+        Ok(out.len())
+    }
+
+    /// Bit errors currently corrected by [`EccStatic::decode`]
+    pub fn corrected_bits(&self) -> usize {
+        self.blocks()
+            .map(|blocks| Hamming74::decode_pair(blocks).1)
+            .sum()
+    }
+
+    pub fn encoded(&self) -> &'static [u8] {
+        self.encoded
+    }
+
+    fn blocks(&self) -> impl Iterator<Item = [u8; 2]> {
+        self.encoded.as_chunks::<2>().0.iter().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::HammingCode;
+
+    static TEXT: EccStatic = include_ecc!(b"flash constants rot too");
+    #[cfg(feature = "alloc")]
+    static SOURCE: EccStatic = include_ecc!(file: "eccstatic.rs");
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_include_ecc() {
+        assert_eq!(TEXT.decode(), b"flash constants rot too");
+        assert_eq!(TEXT.encoded(), Hamming74.encode(b"flash constants rot too"));
+        assert_eq!(TEXT.len(), 23);
+        assert_eq!(SOURCE.decode(), include_bytes!("eccstatic.rs"));
+        assert_eq!(TEXT.corrected_bits(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_hamming_encode() {
+        use crate::Hamming1511;
+
+        const EMPTY: [u8; 0] = hamming_encode!(Hamming74, b"");
+        const ODD: [u8; 16] = hamming_encode!(Hamming1511, b"ten bytes!");
+        assert!(EMPTY.is_empty());
+        assert_eq!(ODD[..], Hamming1511.encode(b"ten bytes!"));
+        // Different ends of the last 11-bit group
+        assert_eq!(
+            hamming_encode!(Hamming1511, b"a"),
+            *Hamming1511.encode(b"a")
+        );
+        assert_eq!(
+            hamming_encode!(Hamming1511, b"ab"),
+            *Hamming1511.encode(b"ab")
+        );
+        assert_eq!(
+            hamming_encode!(Hamming1511, b"eleven byte"),
+            *Hamming1511.encode(b"eleven byte")
+        );
+        assert_eq!(
+            hamming_encode!(Hamming1511, b"twelve bytes"),
+            *Hamming1511.encode(b"twelve bytes")
+        );
+    }
+
+    #[test]
+    fn test_ecc_static_decode_to_slice() {
+        let mut out = [0; 23];
+        assert_eq!(TEXT.decode_to_slice(&mut out), Ok(23));
+        assert_eq!(out, *b"flash constants rot too");
+        assert_eq!(
+            TEXT.decode_to_slice(&mut [0; 22]),
+            Err(HammingError::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_ecc_static_corrects() {
+        let mut encoded = TEXT.encoded().to_vec();
+        encoded[0] ^= 0x04;
+        encoded[31] ^= 0x40;
+        encoded.push(0xFF);
+        let damaged = EccStatic::from_encoded(encoded.leak());
+        assert_eq!(damaged.decode(), TEXT.decode());
+        assert_eq!(damaged.corrected_bits(), 2);
+
+        let mut out = [0; 23];
+        assert_eq!(damaged.decode_to_slice(&mut out), Ok(23));
+        assert_eq!(out[..], TEXT.decode()[..]);
+        assert_eq!(
+            damaged.decode_to_slice(&mut [0; 22]),
+            Err(HammingError::BufferTooSmall)
+        );
+        assert!(EccStatic::from_encoded(&[]).is_empty());
+    }
+}
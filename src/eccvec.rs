@@ -0,0 +1,189 @@
+use crate::{Hamming74, Health};
+use std::fmt;
+
+/// Byte vector kept Hamming(7,4)-encoded in memory
+///
+/// Each byte is stored as two codewords. Reads correct a single flipped bit
+/// per codeword on the fly without touching the storage;
+/// [`EccVec::scrub`] writes the corrections back, and should run often
+/// enough that a second upset rarely hits the same codeword.
+#[derive(Clone, Default)]
+pub struct EccVec {
+    blocks: Vec<[u8; 2]>,
+    health: Health,
+}
+
+impl EccVec {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            blocks: Vec::with_capacity(capacity),
+            health: Health::default(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    /// The corrected byte at `index`
+    pub fn get(&self, index: usize) -> Option<u8> {
+        self.blocks
+            .get(index)
+            .map(|&blocks| Hamming74::decode_pair(blocks).0)
+    }
+
+    /// Store `value` at `index`, returning the corrected previous byte;
+    /// `None` if `index` is out of range
+    pub fn set(&mut self, index: usize, value: u8) -> Option<u8> {
+        let slot = self.blocks.get_mut(index)?;
+        let previous = Hamming74::decode_pair(*slot).0;
+        *slot = encode(value);
+        Some(previous)
+    }
+
+    pub fn push(&mut self, value: u8) {
+        self.blocks.push(encode(value));
+    }
+
+    pub fn pop(&mut self) -> Option<u8> {
+        self.blocks
+            .pop()
+            .map(|blocks| Hamming74::decode_pair(blocks).0)
+    }
+
+    pub fn truncate(&mut self, len: usize) {
+        self.blocks.truncate(len);
+    }
+
+    pub fn clear(&mut self) {
+        self.blocks.clear();
+    }
+
+    /// The corrected bytes, in order
+    pub fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+        self.blocks
+            .iter()
+            .map(|&blocks| Hamming74::decode_pair(blocks).0)
+    }
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.iter().collect()
+    }
+
+    /// Correct every codeword in place, returning the number of corrected
+    /// bits
+    pub fn scrub(&mut self) -> usize {
+        let mut corrected = 0;
+        for slot in &mut self.blocks {
+            let (value, bits) = Hamming74::decode_pair(*slot);
+            if bits > 0 {
+                *slot = encode(value);
+                corrected += bits;
+            }
+        }
+        self.health.corrected(corrected as u64);
+        corrected
+    }
+
+    /// Corrections written back by [`EccVec::scrub`] since the vector was
+    /// created
+    pub fn health(&self) -> Health {
+        self.health
+    }
+
+    /// The encoded storage, two codeword bytes per element
+    pub fn encoded(&self) -> &[u8] {
+        self.blocks.as_flattened()
+    }
+
+    /// The encoded storage, for fault injection
+    pub fn encoded_mut(&mut self) -> &mut [u8] {
+        self.blocks.as_flattened_mut()
+    }
+}
+
+fn encode(value: u8) -> [u8; 2] {
+    [
+        Hamming74::encode_nibble(value & 0x0F),
+        Hamming74::encode_nibble(value >> 4),
+    ]
+}
+
+impl From<&[u8]> for EccVec {
+    fn from(data: &[u8]) -> Self {
+        data.iter().copied().collect()
+    }
+}
+
+impl FromIterator<u8> for EccVec {
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+        Self {
+            blocks: iter.into_iter().map(encode).collect(),
+            health: Health::default(),
+        }
+    }
+}
+
+impl Extend<u8> for EccVec {
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        self.blocks.extend(iter.into_iter().map(encode));
+    }
+}
+
+// Equal contents, regardless of errors and history
+impl PartialEq for EccVec {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for EccVec {}
+
+// Shows the corrected contents
+impl fmt::Debug for EccVec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eccvec_reads_corrected() {
+        let mut v = EccVec::from(&b"telemetry"[..]);
+        v.encoded_mut()[0] ^= 0x10;
+        v.encoded_mut()[7] ^= 0x02;
+        assert_eq!(v.get(0), Some(b't'));
+        assert_eq!(v.to_vec(), b"telemetry");
+        assert_eq!(v.get(9), None);
+
+        assert_eq!(v, EccVec::from(&b"telemetry"[..]));
+        assert_eq!(v.scrub(), 2);
+        assert_eq!(v.scrub(), 0);
+        assert_eq!(v.health().corrections, 2);
+        assert!(v.health().last_correction.is_some());
+        assert_eq!(v.encoded(), EccVec::from(&b"telemetry"[..]).encoded());
+    }
+
+    #[test]
+    fn test_eccvec_mutation() {
+        let mut v: EccVec = (0..4).collect();
+        v.push(0xAB);
+        v.extend([1, 2]);
+        assert_eq!(v.set(1, 0xFF), Some(1));
+        assert_eq!(v.set(7, 0), None);
+        assert_eq!(v.pop(), Some(2));
+        assert_eq!(v.len(), 6);
+        assert_eq!(format!("{v:?}"), "[0, 255, 2, 3, 171, 1]");
+    }
+}
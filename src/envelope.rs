@@ -0,0 +1,147 @@
+use crate::{Hamming74, HammingCode, HammingError};
+use serde::de::{self, DeserializeOwned, Deserializer, SeqAccess, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+use std::fmt;
+
+/// Serde wrapper that stores its value Hamming(7,4)-protected
+///
+/// The inner value is serialized with postcard, terminated and encoded, and
+/// the result is written as a byte string. Deserializing corrects single-bit
+/// errors in every block before decoding the value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EccEnvelope<T>(pub T);
+
+impl<T: Serialize> EccEnvelope<T> {
+    /// The protected byte representation
+    pub fn to_bytes(&self) -> Result<Vec<u8>, HammingError> {
+        let raw = postcard::to_allocvec(&self.0).map_err(|_| HammingError::Serialization)?;
+        Ok(Hamming74.encode_terminated(&raw))
+    }
+}
+
+impl<T: DeserializeOwned> EccEnvelope<T> {
+    /// Recover the value from [`EccEnvelope::to_bytes`] output
+    pub fn from_bytes(encoded: &[u8]) -> Result<Self, HammingError> {
+        let raw = Hamming74.decode_terminated(encoded)?;
+        let value = postcard::from_bytes(&raw).map_err(|_| HammingError::Serialization)?;
+        Ok(EccEnvelope(value))
+    }
+}
+
+impl<T> EccEnvelope<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Serialize> Serialize for EccEnvelope<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let bytes = self.to_bytes().map_err(serde::ser::Error::custom)?;
+        serializer.serialize_bytes(&bytes)
+    }
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for EccEnvelope<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = deserializer.deserialize_bytes(BytesVisitor)?;
+        EccEnvelope::from_bytes(&bytes).map_err(de::Error::custom)
+    }
+}
+
+// Most bytes reserved up front from a sequence's size hint
+const MAX_PREALLOC: usize = 4096;
+
+// Accepts native byte strings as well as sequences (e.g. JSON arrays)
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Hamming-encoded bytes")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Vec<u8>, E> {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Vec<u8>, E> {
+        Ok(v)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+        // The hint comes from the input; cap it as serde's own
+        // `size_hint::cautious` does, so a forged length cannot make this
+        // allocate gigabytes up front
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(MAX_PREALLOC));
+        while let Some(b) = seq.next_element()? {
+            bytes.push(b);
+        }
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Config {
+        name: String,
+        gain: i32,
+        channels: Vec<u8>,
+    }
+
+    fn config() -> Config {
+        Config {
+            name: "radio".to_string(),
+            gain: -12,
+            channels: vec![1, 6, 11],
+        }
+    }
+
+    // A sequence claiming far more elements than it has
+    struct ForgedSeq(std::vec::IntoIter<u8>);
+
+    impl<'de> SeqAccess<'de> for ForgedSeq {
+        type Error = de::value::Error;
+
+        fn next_element_seed<T: de::DeserializeSeed<'de>>(
+            &mut self,
+            seed: T,
+        ) -> Result<Option<T::Value>, Self::Error> {
+            self.0
+                .next()
+                .map(|b| seed.deserialize(de::value::U8Deserializer::new(b)))
+                .transpose()
+        }
+
+        fn size_hint(&self) -> Option<usize> {
+            Some(usize::MAX)
+        }
+    }
+
+    #[test]
+    fn test_envelope_ignores_forged_size_hint() {
+        let bytes = BytesVisitor.visit_seq(ForgedSeq(vec![1, 2, 3].into_iter()));
+        assert_eq!(bytes.unwrap(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_envelope_corrects_bit_flips() {
+        let mut bytes = EccEnvelope(config()).to_bytes().unwrap();
+        for i in (0..bytes.len()).step_by(3) {
+            bytes[i] ^= 1 << (i % 7);
+        }
+
+        let restored = EccEnvelope::<Config>::from_bytes(&bytes).unwrap();
+        assert_eq!(restored.into_inner(), config());
+    }
+
+    #[test]
+    fn test_envelope_as_serde_field() {
+        let json = serde_json::to_string(&EccEnvelope(config())).unwrap();
+        let restored: EccEnvelope<Config> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.0, config());
+    }
+}
@@ -0,0 +1,146 @@
+use crate::{CodeSpec, Hamming, HammingCode, HammingError};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Write;
+
+// Blocks shown by `BlockDiagram::render` before the rest is elided
+const MAX_BLOCKS: usize = 16;
+
+/// Text diagram of a code's block layout, for teaching
+///
+/// Positions are numbered from 1 as in the textbook construction. Parity
+/// bits are labelled `p<n>` by the syndrome bit they set, data bits
+/// `d1..dk` in the order they are taken from the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockDiagram {
+    code: Hamming,
+    /// Parity-check matrix column of every codeword position
+    columns: Vec<usize>,
+}
+
+impl BlockDiagram {
+    /// Diagram for a codec selection
+    pub fn new(spec: &CodeSpec) -> Result<Self, HammingError> {
+        Self::for_code(spec.general_equivalent().0)
+    }
+
+    /// Diagram for a configured general code
+    pub fn for_code(code: Hamming) -> Result<Self, HammingError> {
+        if code.data_bits() == 0 || code.extended() {
+            return Err(HammingError::Unsupported);
+        }
+        let columns = code.columns().take(code.block_size()).collect();
+        Ok(Self { code, columns })
+    }
+
+    /// Position and role rows, the coverage set of every parity bit and,
+    /// for non-empty `data`, its encoded blocks with the parity checks of
+    /// the first one
+    pub fn render(&self, data: &[u8]) -> String {
+        let mut out = String::new();
+        let n = self.columns.len();
+        let _ = writeln!(out, "Hamming({n},{})\n", self.code.data_bits());
+
+        let mut data_bit = 0;
+        let roles: Vec<String> = self
+            .columns
+            .iter()
+            .map(|&column| {
+                if column.is_power_of_two() {
+                    format!("p{column}")
+                } else {
+                    data_bit += 1;
+                    format!("d{data_bit}")
+                }
+            })
+            .collect();
+        let width = roles.iter().map(String::len).max().unwrap_or(0).max(2) + 1;
+        let row = |label: &str, cells: &mut dyn Iterator<Item = String>| {
+            let cells: String = cells.map(|cell| format!("{cell:>width$}")).collect();
+            format!("{label:<10}{cells}\n")
+        };
+
+        out += &row("position", &mut (1..=n).map(|p| p.to_string()));
+        out += &row("role", &mut roles.iter().cloned());
+        out.push('\n');
+
+        let parity: Vec<usize> = self
+            .columns
+            .iter()
+            .copied()
+            .filter(|column| column.is_power_of_two())
+            .collect();
+        for &p in &parity {
+            let covered = self
+                .columns
+                .iter()
+                .map(|&c| if c & p != 0 { "x" } else { "." }.to_string());
+            out += &row(&format!("p{p} covers"), &mut covered.into_iter());
+        }
+
+        if data.is_empty() {
+            return out;
+        }
+        let encoded = self.code.encode(data);
+        let blocks = (encoded.len() * 8) / n;
+        let bit = |block: usize, pos: usize| {
+            let index = block * n + pos;
+            encoded.get(index / 8).map_or(0, |b| b >> (index % 8) & 1)
+        };
+
+        out.push('\n');
+        for block in 0..blocks.min(MAX_BLOCKS) {
+            let bits = (0..n).map(|pos| bit(block, pos).to_string());
+            out += &row(&format!("block {block}"), &mut bits.into_iter());
+        }
+        if blocks > MAX_BLOCKS {
+            let _ = writeln!(out, "... {} more blocks", blocks - MAX_BLOCKS);
+        }
+
+        // Every check over a valid block XORs to zero
+        out.push('\n');
+        for &p in &parity {
+            let covered: Vec<usize> = (0..n)
+                .filter(|&pos| self.columns.get(pos).is_some_and(|c| c & p != 0))
+                .collect();
+            let positions: Vec<String> = covered.iter().map(|pos| (pos + 1).to_string()).collect();
+            let values: Vec<String> = covered.iter().map(|&pos| bit(0, pos).to_string()).collect();
+            let sum = covered.iter().fold(0, |sum, &pos| sum ^ bit(0, pos));
+            let _ = writeln!(
+                out,
+                "block 0 p{p}: positions {} -> {} = {sum}",
+                positions.join(" "),
+                values.join(" ^ ")
+            );
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hamming74_diagram() {
+        let diagram = BlockDiagram::new(&CodeSpec::Hamming74).unwrap();
+        let text = diagram.render(b"");
+        assert!(text.starts_with("Hamming(7,4)\n"));
+        assert!(text.contains("role       p1 p2 d1 p4 d2 d3 d4\n"));
+        assert!(text.contains("p2 covers   .  x  x  .  .  x  x\n"));
+        assert!(!text.contains("block"));
+    }
+
+    #[test]
+    fn test_diagram_blocks() {
+        let diagram = BlockDiagram::new(&CodeSpec::Hamming74).unwrap();
+        // 'A' = 0x41: nibble 1 -> 0x07, nibble 4 -> 0x2A
+        let text = diagram.render(b"A");
+        assert!(text.contains("block 0     1  1  1  0  0  0  0\n"));
+        assert!(text.contains("block 1     0  1  0  1  0  1  0\n"));
+        assert!(text.contains("block 0 p4: positions 4 5 6 7 -> 0 ^ 0 ^ 0 ^ 0 = 0\n"));
+    }
+}
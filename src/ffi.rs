@@ -0,0 +1,363 @@
+//! C interface
+//!
+//! All functions take caller-provided buffers and return a status code
+//! (`HAMMING_OK` or one of the negative `HAMMING_ERR_*` values). The
+//! generated header lives in `include/hamming.h`.
+
+use crate::{CorrectionStats, Hamming74, Hamming1511, HammingError};
+use core::slice;
+
+/// Code identifier for Hamming(7,4)
+pub const HAMMING_CODE_74: u32 = 74;
+/// Code identifier for Hamming(15,11)
+pub const HAMMING_CODE_1511: u32 = 1511;
+
+pub const HAMMING_OK: i32 = 0;
+pub const HAMMING_ERR_INVALID_LENGTH: i32 = -1;
+pub const HAMMING_ERR_UNCORRECTABLE: i32 = -2;
+pub const HAMMING_ERR_BUFFER_TOO_SMALL: i32 = -3;
+pub const HAMMING_ERR_NULL_POINTER: i32 = -4;
+pub const HAMMING_ERR_UNKNOWN_CODE: i32 = -5;
+pub const HAMMING_ERR_OTHER: i32 = -6;
+
+// The allocation-free paths of one code
+struct Codec {
+    encoded_len: fn(usize) -> Option<usize>,
+    decoded_len: fn(usize) -> usize,
+    encode: fn(&[u8], &mut [u8]) -> Result<usize, HammingError>,
+    decode: fn(&[u8], &mut [u8]) -> Result<usize, HammingError>,
+    correct: fn(&mut [u8]) -> Result<CorrectionStats, HammingError>,
+}
+
+fn codec(code: u32) -> Option<Codec> {
+    match code {
+        HAMMING_CODE_74 => Some(Codec {
+            encoded_len: Hamming74::checked_encoded_len,
+            decoded_len: Hamming74::decoded_len,
+            encode: |data, out| Hamming74.encode_to_slice(data, out),
+            decode: |encoded, out| Hamming74.decode_to_slice(encoded, out),
+            correct: |encoded| Hamming74.correct_in_place(encoded),
+        }),
+        HAMMING_CODE_1511 => Some(Codec {
+            encoded_len: Hamming1511::checked_encoded_len,
+            decoded_len: Hamming1511::decoded_len,
+            encode: |data, out| Hamming1511.encode_to_slice(data, out),
+            decode: |encoded, out| Hamming1511.decode_to_slice(encoded, out),
+            correct: |encoded| Hamming1511.correct_in_place(encoded),
+        }),
+        _ => None,
+    }
+}
+
+fn status(err: &HammingError) -> i32 {
+    match err {
+        HammingError::InvalidLength => HAMMING_ERR_INVALID_LENGTH,
+        HammingError::UncorrectableErrors | HammingError::DoubleErrorDetected { .. } => {
+            HAMMING_ERR_UNCORRECTABLE
+        }
+        HammingError::BufferTooSmall => HAMMING_ERR_BUFFER_TOO_SMALL,
+        _ => HAMMING_ERR_OTHER,
+    }
+}
+
+// Build a slice from a C pointer, allowing NULL for empty buffers
+unsafe fn input<'a>(ptr: *const u8, len: usize) -> Option<&'a [u8]> {
+    if len == 0 {
+        Some(&[])
+    } else if ptr.is_null() {
+        None
+    } else {
+        Some(unsafe { slice::from_raw_parts(ptr, len) })
+    }
+}
+
+// Build a mutable slice from a C pointer, allowing NULL for empty buffers
+unsafe fn output<'a>(ptr: *mut u8, len: usize) -> Option<&'a mut [u8]> {
+    if len == 0 {
+        Some(&mut [])
+    } else if ptr.is_null() {
+        None
+    } else {
+        Some(unsafe { slice::from_raw_parts_mut(ptr, len) })
+    }
+}
+
+// Run a slice encode or decode into `out`, storing the bytes written, or
+// on `HAMMING_ERR_BUFFER_TOO_SMALL` the bytes required, in `*out_len`
+unsafe fn write_output(
+    run: impl FnOnce(&mut [u8]) -> Result<usize, HammingError>,
+    required: usize,
+    out: *mut u8,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> i32 {
+    if out_len.is_null() {
+        return HAMMING_ERR_NULL_POINTER;
+    }
+    let Some(out) = (unsafe { output(out, out_cap) }) else {
+        return HAMMING_ERR_NULL_POINTER;
+    };
+    match run(out) {
+        Ok(written) => {
+            unsafe { *out_len = written };
+            HAMMING_OK
+        }
+        Err(e) => {
+            if e == HammingError::BufferTooSmall {
+                unsafe { *out_len = required };
+            }
+            status(&e)
+        }
+    }
+}
+
+/// Number of bytes `hamming_encode` produces for `data_len` input bytes,
+/// or 0 for an unknown code or a size that overflows `size_t`
+#[unsafe(no_mangle)]
+pub extern "C" fn hamming_encoded_len(code: u32, data_len: usize) -> usize {
+    codec(code)
+        .and_then(|codec| (codec.encoded_len)(data_len))
+        .unwrap_or(0)
+}
+
+/// Encode `data_len` bytes into `out`
+///
+/// On success or `HAMMING_ERR_BUFFER_TOO_SMALL`, `*out_len` receives the
+/// number of bytes required.
+///
+/// # Safety
+///
+/// `data` must be valid for `data_len` reads, `out` for `out_cap` writes and
+/// `out_len` must point to writable memory.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn hamming_encode(
+    code: u32,
+    data: *const u8,
+    data_len: usize,
+    out: *mut u8,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> i32 {
+    let Some(codec) = codec(code) else {
+        return HAMMING_ERR_UNKNOWN_CODE;
+    };
+    let Some(data) = (unsafe { input(data, data_len) }) else {
+        return HAMMING_ERR_NULL_POINTER;
+    };
+
+    let required = (codec.encoded_len)(data.len()).unwrap_or(usize::MAX);
+    unsafe {
+        write_output(
+            |out| (codec.encode)(data, out),
+            required,
+            out,
+            out_cap,
+            out_len,
+        )
+    }
+}
+
+/// Decode `encoded_len` bytes into `out`, correcting single-bit errors
+///
+/// On success or `HAMMING_ERR_BUFFER_TOO_SMALL`, `*out_len` receives the
+/// number of bytes required.
+///
+/// # Safety
+///
+/// `encoded` must be valid for `encoded_len` reads, `out` for `out_cap`
+/// writes and `out_len` must point to writable memory.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn hamming_decode(
+    code: u32,
+    encoded: *const u8,
+    encoded_len: usize,
+    out: *mut u8,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> i32 {
+    let Some(codec) = codec(code) else {
+        return HAMMING_ERR_UNKNOWN_CODE;
+    };
+    let Some(encoded) = (unsafe { input(encoded, encoded_len) }) else {
+        return HAMMING_ERR_NULL_POINTER;
+    };
+
+    let required = (codec.decoded_len)(encoded.len());
+    unsafe {
+        write_output(
+            |out| (codec.decode)(encoded, out),
+            required,
+            out,
+            out_cap,
+            out_len,
+        )
+    }
+}
+
+/// Correct single-bit errors in an encoded buffer in place
+///
+/// `*corrected_bits` (if not NULL) receives the number of bits flipped.
+/// The spare top bit of each codeword is left as it was, and so is the
+/// whole buffer if any codeword is uncorrectable.
+///
+/// # Safety
+///
+/// `encoded` must be valid for `encoded_len` reads and writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn hamming_correct(
+    code: u32,
+    encoded: *mut u8,
+    encoded_len: usize,
+    corrected_bits: *mut usize,
+) -> i32 {
+    let Some(codec) = codec(code) else {
+        return HAMMING_ERR_UNKNOWN_CODE;
+    };
+    let Some(buf) = (unsafe { output(encoded, encoded_len) }) else {
+        return HAMMING_ERR_NULL_POINTER;
+    };
+
+    match (codec.correct)(buf) {
+        Ok(stats) => {
+            if !corrected_bits.is_null() {
+                unsafe { *corrected_bits = stats.corrected_bits };
+            }
+            HAMMING_OK
+        }
+        Err(e) => status(&e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ffi_encode_decode() {
+        let data = b"ffi!";
+        for code in [HAMMING_CODE_74, HAMMING_CODE_1511] {
+            let mut encoded = [0u8; 16];
+            let mut encoded_len = 0;
+            let rc = unsafe {
+                hamming_encode(
+                    code,
+                    data.as_ptr(),
+                    data.len(),
+                    encoded.as_mut_ptr(),
+                    encoded.len(),
+                    &mut encoded_len,
+                )
+            };
+            assert_eq!(rc, HAMMING_OK);
+            assert_eq!(encoded_len, hamming_encoded_len(code, data.len()));
+
+            let mut decoded = [0u8; 16];
+            let mut decoded_len = 0;
+            let rc = unsafe {
+                hamming_decode(
+                    code,
+                    encoded.as_ptr(),
+                    encoded_len,
+                    decoded.as_mut_ptr(),
+                    decoded.len(),
+                    &mut decoded_len,
+                )
+            };
+            assert_eq!(rc, HAMMING_OK);
+            assert_eq!(&decoded[..decoded_len], data);
+        }
+    }
+
+    #[test]
+    fn test_ffi_encoded_len_overflow() {
+        assert_eq!(
+            hamming_encoded_len(HAMMING_CODE_74, usize::MAX / 2),
+            usize::MAX - 1
+        );
+        assert_eq!(hamming_encoded_len(HAMMING_CODE_74, usize::MAX / 2 + 1), 0);
+        assert_eq!(
+            hamming_encoded_len(HAMMING_CODE_1511, usize::MAX / 8),
+            Hamming1511::encoded_len(usize::MAX / 8)
+        );
+        assert_eq!(hamming_encoded_len(HAMMING_CODE_1511, usize::MAX), 0);
+        assert_eq!(hamming_encoded_len(0, 4), 0);
+    }
+
+    #[test]
+    fn test_ffi_buffer_too_small() {
+        let mut out = [0u8; 1];
+        let mut out_len = 0;
+        let rc = unsafe {
+            hamming_encode(
+                HAMMING_CODE_74,
+                b"ab".as_ptr(),
+                2,
+                out.as_mut_ptr(),
+                1,
+                &mut out_len,
+            )
+        };
+        assert_eq!(rc, HAMMING_ERR_BUFFER_TOO_SMALL);
+        assert_eq!(out_len, 4);
+    }
+
+    #[test]
+    fn test_ffi_correct() {
+        let mut clean = [0; Hamming1511::encoded_len(3)];
+        Hamming1511
+            .encode_to_slice(&[0x55, 0xAA, 0x0F], &mut clean)
+            .unwrap();
+        let mut noisy = clean;
+        noisy[0] ^= 0x10;
+        noisy[3] ^= 0x02;
+
+        let mut corrected = 0;
+        let rc = unsafe {
+            hamming_correct(
+                HAMMING_CODE_1511,
+                noisy.as_mut_ptr(),
+                noisy.len(),
+                &mut corrected,
+            )
+        };
+        assert_eq!(rc, HAMMING_OK);
+        assert_eq!(corrected, 2);
+        assert_eq!(noisy, clean);
+    }
+
+    #[test]
+    fn test_ffi_correct_keeps_spare_bits() {
+        for code in [HAMMING_CODE_74, HAMMING_CODE_1511] {
+            let mut clean = [0; 16];
+            let mut len = 0;
+            let rc = unsafe {
+                hamming_encode(code, b"spare".as_ptr(), 5, clean.as_mut_ptr(), 16, &mut len)
+            };
+            assert_eq!(rc, HAMMING_OK);
+            let clean = &mut clean[..len];
+            // Spare bits 7 (Hamming74) and 15 (the high byte of a Hamming1511
+            // block) set by the caller, e.g. as flags
+            let stride = if code == HAMMING_CODE_74 { 1 } else { 2 };
+            for block in clean.chunks_mut(stride) {
+                *block.last_mut().unwrap() |= 0x80;
+            }
+            let mut noisy = [0; 16];
+            let noisy = &mut noisy[..len];
+            noisy.copy_from_slice(clean);
+            noisy[2] ^= 0x04;
+
+            let mut corrected = 0;
+            let rc =
+                unsafe { hamming_correct(code, noisy.as_mut_ptr(), noisy.len(), &mut corrected) };
+            assert_eq!(rc, HAMMING_OK);
+            assert_eq!(corrected, 1);
+            assert_eq!(noisy, clean);
+
+            let rc =
+                unsafe { hamming_correct(code, noisy.as_mut_ptr(), noisy.len(), &mut corrected) };
+            assert_eq!(rc, HAMMING_OK);
+            assert_eq!(corrected, 0);
+            assert_eq!(noisy, clean);
+        }
+    }
+}
@@ -0,0 +1,85 @@
+/// What encoding a given amount of data costs, see
+/// [`HammingCode::expansion`](crate::HammingCode::expansion)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct EncodingFootprint {
+    /// Input bytes
+    pub data_len: usize,
+    /// Exact encoded bytes
+    pub encoded_len: usize,
+    pub blocks: usize,
+    /// Zero bits padding the data to whole blocks
+    pub padding_bits: usize,
+    /// Encoded bits outside any block: spare bits between blocks and the
+    /// padding of the last byte
+    pub unused_bits: usize,
+    /// Data bits per encoded bit, padding included
+    pub rate: f64,
+}
+
+impl EncodingFootprint {
+    pub(crate) fn new(data_len: usize, data_bits: usize, block_size: usize, stride: usize) -> Self {
+        let data_bits_total = data_len.saturating_mul(8);
+        let blocks = data_bits_total.div_ceil(data_bits.max(1));
+        let encoded_bits = blocks.saturating_mul(stride);
+        let encoded_len = encoded_bits.div_ceil(8);
+        Self {
+            data_len,
+            encoded_len,
+            blocks,
+            padding_bits: blocks
+                .saturating_mul(data_bits)
+                .saturating_sub(data_bits_total),
+            unused_bits: encoded_len
+                .saturating_mul(8)
+                .saturating_sub(blocks.saturating_mul(block_size)),
+            rate: if encoded_len == 0 {
+                0.0
+            } else {
+                data_len as f64 / encoded_len as f64
+            },
+        }
+    }
+
+    /// Encoded bytes per input byte
+    pub fn expansion_ratio(&self) -> f64 {
+        if self.data_len == 0 {
+            0.0
+        } else {
+            self.encoded_len as f64 / self.data_len as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Hamming74, Hamming1511, HammingCode, sample_codecs};
+    use alloc::vec;
+
+    #[test]
+    fn test_expansion_matches_encode() {
+        let codecs = sample_codecs();
+        for codec in &codecs {
+            for len in [0, 1, 2, 10, 11, 100, 1000] {
+                let footprint = codec.expansion(len);
+                assert_eq!(footprint.encoded_len, codec.encode(&vec![0; len]).len());
+                assert_eq!(
+                    footprint.blocks * codec.data_bits(),
+                    len * 8 + footprint.padding_bits
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_expansion_fields() {
+        let footprint = Hamming1511.expansion(10);
+        assert_eq!(footprint.blocks, 8);
+        assert_eq!(footprint.encoded_len, 16);
+        assert_eq!(footprint.padding_bits, 8);
+        // One spare bit per block
+        assert_eq!(footprint.unused_bits, 8);
+        assert_eq!(footprint.rate, 10.0 / 16.0);
+        assert_eq!(Hamming74.expansion(3).expansion_ratio(), 2.0);
+    }
+}
@@ -0,0 +1,74 @@
+use crate::HammingError;
+use alloc::vec::Vec;
+
+/// Sync-word framing
+///
+/// Frames are the sync word (big-endian) followed by the payload. The
+/// receiver accepts the sync word with up to `max_sync_errors` flipped bits.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Framer {
+    sync: u32,
+    max_sync_errors: u32,
+}
+
+impl Framer {
+    pub fn new(sync: u32, max_sync_errors: u32) -> Self {
+        Self {
+            sync,
+            max_sync_errors,
+        }
+    }
+
+    /// Prepend the sync word to the payload
+    pub fn frame(&self, payload: &[u8]) -> Vec<u8> {
+        let mut framed = Vec::with_capacity(payload.len() + 4);
+        framed.extend_from_slice(&self.sync.to_be_bytes());
+        framed.extend_from_slice(payload);
+        framed
+    }
+
+    /// Locate the sync word and return the payload following it
+    pub fn deframe<'a>(&self, stream: &'a [u8]) -> Result<&'a [u8], HammingError> {
+        let mut rest = stream;
+        while let [_, tail @ ..] = rest {
+            if let [a, b, c, d, payload @ ..] = rest {
+                let word = u32::from_be_bytes([*a, *b, *c, *d]);
+                if (word ^ self.sync).count_ones() <= self.max_sync_errors {
+                    return Ok(payload);
+                }
+            }
+            rest = tail;
+        }
+
+        Err(HammingError::SyncNotFound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_framer_roundtrip() {
+        let f = Framer::new(0x1ACF_FC1D, 0);
+        let framed = f.frame(b"payload");
+
+        assert_eq!(&framed[..4], &[0x1A, 0xCF, 0xFC, 0x1D]);
+        assert_eq!(f.deframe(&framed).unwrap(), b"payload");
+    }
+
+    #[test]
+    fn test_framer_tolerates_sync_errors() {
+        let f = Framer::new(0x1ACF_FC1D, 2);
+        let mut stream = vec![0x00, 0x00, 0x00];
+        stream.extend(f.frame(b"xyz"));
+        stream[4] ^= 0x81; // two bit errors in the sync word
+
+        assert_eq!(f.deframe(&stream).unwrap(), b"xyz");
+        assert_eq!(
+            Framer::new(0x1ACF_FC1D, 1).deframe(&stream),
+            Err(HammingError::SyncNotFound)
+        );
+    }
+}
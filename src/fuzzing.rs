@@ -0,0 +1,84 @@
+use crate::{CodeSpec, Hamming};
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+// Keeps generated general codes small enough to fuzz quickly
+const MAX_FUZZ_DATA_BITS: usize = 64;
+
+impl<'a> Arbitrary<'a> for CodeSpec {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=3)? {
+            0 => CodeSpec::Hamming74,
+            1 => CodeSpec::Hamming1511,
+            2 => CodeSpec::General {
+                data_bits: u.int_in_range(1..=MAX_FUZZ_DATA_BITS)?,
+            },
+            _ => CodeSpec::Secded {
+                data_bits: u.int_in_range(1..=MAX_FUZZ_DATA_BITS)?,
+            },
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Hamming {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Hamming::new(u.int_in_range(1..=MAX_FUZZ_DATA_BITS)?).with_extended(u.arbitrary()?))
+    }
+}
+
+/// An encoded buffer with injected bit errors
+///
+/// `flips` are bit positions in the encoded stream, taken modulo its length.
+#[derive(Debug, Clone, PartialEq, Arbitrary)]
+pub struct CorruptedBuffer {
+    pub spec: CodeSpec,
+    pub data: Vec<u8>,
+    pub flips: Vec<usize>,
+}
+
+impl CorruptedBuffer {
+    /// Encode `data` with `spec` and apply the bit flips
+    pub fn encoded(&self) -> Vec<u8> {
+        let mut encoded = self.spec.build().encode(&self.data);
+        let total_bits = encoded.len() * 8;
+
+        if total_bits > 0 {
+            for &flip in &self.flips {
+                let bit = flip % total_bits;
+                if let Some(byte) = encoded.get_mut(bit / 8) {
+                    *byte ^= 1 << (bit % 8);
+                }
+            }
+        }
+
+        encoded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arbitrary_corrupted_buffer() {
+        let raw: Vec<u8> = (0..=255).cycle().take(4096).collect();
+        let mut u = Unstructured::new(&raw);
+
+        while let Ok(buf) = CorruptedBuffer::arbitrary(&mut u) {
+            let codec = buf.spec.build();
+            let _ = codec.decode(&buf.encoded());
+            if u.is_empty() {
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn test_corrupted_buffer_without_flips() {
+        let buf = CorruptedBuffer {
+            spec: CodeSpec::Hamming74,
+            data: vec![0x12, 0x34],
+            flips: vec![],
+        };
+        assert_eq!(buf.spec.build().decode(&buf.encoded()).unwrap(), buf.data);
+    }
+}
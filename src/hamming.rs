@@ -1,89 +1,489 @@
-use crate::{HammingCode, HammingError};
+use crate::bits::VecSink;
+use crate::wordcode::WordCode;
+use crate::{
+    BitOrder, BlockEvent, BlockOutcome, Blocks, HammingCode, HammingError, SecdedDecode, telemetry,
+};
+use alloc::{format, string::String, vec, vec::Vec};
+
+// Default primitive polynomials of MATLAB's `gfprimdf`, indexed by degree
+const PRIMITIVE_POLYS: [usize; 25] = [
+    0, 0x3, 0x7, 0xB, 0x13, 0x25, 0x43, 0x89, 0x11D, 0x211, 0x409, 0x805, 0x1053, 0x201B, 0x402B,
+    0x8003, 0x1100B, 0x20009, 0x40081, 0x80027, 0x100009, 0x200005, 0x400003, 0x800021, 0x1000087,
+];
+
+/// Placement of the parity and data bits within a codeword
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Layout {
+    /// Parity bits at the power-of-two positions 1, 2, 4, ...
+    #[default]
+    Positional,
+    /// Systematic layout of MATLAB's `hammgen`/`encode`: the parity bits
+    /// first, then the message bits in order. Column `j` of the parity-check
+    /// matrix is `x^j` modulo the default primitive polynomial of degree
+    /// `parity_bits` (`x^3 + x + 1` for Hamming(7,4)).
+    ///
+    /// Codes needing more than 24 parity bits keep the positional layout.
+    Matlab,
+}
 
 /// General Hamming code implementation
+///
+/// The default is Hamming(7,4), in the same layout as [`Hamming74`](crate::Hamming74).
+/// Blocks of up to 128 bits, e.g. Hamming(127,120) or SEC-DED(128,120), are
+/// encoded and decoded as `u128` words; larger ones bit by bit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Hamming {
     data_bits: usize,
     parity_bits: usize,
+    bit_order: BitOrder,
+    layout: Layout,
+    extended: bool,
 }
 
 impl Hamming {
+    /// Largest `data_bits` accepted by [`Hamming::try_new`]: the code with
+    /// 24 parity bits, the most any [`Layout`] supports
+    pub const MAX_DATA_BITS: usize = (1 << 24) - 25;
+
+    /// Code with `data_bits` data bits per block
+    ///
+    /// The parameter is not checked; a code without data bits, or with more
+    /// than [`Hamming::MAX_DATA_BITS`], fails on first use. Use
+    /// [`Hamming::try_new`] for values from configuration.
     pub fn new(data_bits: usize) -> Self {
-        // Calculate required parity bits: 2^r >= m + r + 1
+        // Calculate required parity bits: 2^r >= m + r + 1, stopping before
+        // the shift or the sum overflows for huge `data_bits`
         let mut parity_bits = 1;
-        while (1 << parity_bits) < data_bits + parity_bits + 1 {
+        while parity_bits < usize::BITS as usize - 1
+            && (1 << parity_bits) < data_bits.saturating_add(parity_bits + 1)
+        {
             parity_bits += 1;
         }
 
         Self {
             data_bits,
             parity_bits,
+            bit_order: BitOrder::LsbFirst,
+            layout: Layout::Positional,
+            extended: false,
         }
     }
-}
 
-impl HammingCode for Hamming {
-    fn encode(&self, data: &[u8]) -> Vec<u8> {
-        if data.is_empty() {
-            return Vec::new();
+    /// Code with `data_bits` data bits per block, or
+    /// [`HammingError::Unsupported`] for 0 or more than
+    /// [`Hamming::MAX_DATA_BITS`]
+    pub fn try_new(data_bits: usize) -> Result<Self, HammingError> {
+        if !Self::supported(data_bits) {
+            return Err(HammingError::Unsupported);
         }
+        Ok(Self::new(data_bits))
+    }
 
-        let block_bits = self.data_bits + self.parity_bits;
-        let total_data_bits = data.len() * 8;
+    // Whether `data_bits` is a code `try_new` accepts
+    fn supported(data_bits: usize) -> bool {
+        (1..=Self::MAX_DATA_BITS).contains(&data_bits)
+    }
 
-        // Calculate number of blocks needed
-        let num_blocks = (total_data_bits + self.data_bits - 1) / self.data_bits;
+    /// Append an overall parity bit, making this the extended (SEC-DED)
+    /// code: single errors are still corrected and double errors are
+    /// detected instead of miscorrected
+    pub fn with_extended(mut self, extended: bool) -> Self {
+        self.extended = extended;
+        self
+    }
 
-        // IMPORTANT: Calculate the exact output size
-        let total_output_bits = num_blocks * block_bits;
-        let output_bytes = (total_output_bits + 7) / 8;
+    pub fn extended(&self) -> bool {
+        self.extended
+    }
 
-        let mut encoded = vec![0u8; output_bytes];
+    /// Place the parity and data bits according to `layout`
+    pub fn with_layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
 
-        // Process data in chunks
-        for block_idx in 0..num_blocks {
-            // Start position for this block in the output
-            let output_bit_offset = block_idx * block_bits;
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
 
-            // Initialize block with all zeros
-            let mut block = vec![false; block_bits];
-
-            // Fill in data bits
-            let data_start_bit = block_idx * self.data_bits;
-            let mut data_bit_count = 0;
-
-            for pos in 1..=block_bits {
-                if !pos.is_power_of_two() && data_bit_count < self.data_bits {
-                    let global_data_bit = data_start_bit + data_bit_count;
-                    if global_data_bit < total_data_bits {
-                        let byte_idx = global_data_bit / 8;
-                        let bit_idx = global_data_bit % 8;
-                        block[pos - 1] = (data[byte_idx] >> bit_idx) & 1 == 1;
-                    }
-                    data_bit_count += 1;
-                }
+    /// Use `bit_order` for both the data bytes and the packed codewords
+    ///
+    /// With [`BitOrder::MsbFirst`], data and codewords read as left-to-right
+    /// bit strings with position 1 leftmost, the convention of textbook
+    /// examples. Blocks are still packed back to back into bytes; this is
+    /// not checked against, or claimed compatible with, any other
+    /// implementation.
+    pub fn with_bit_order(mut self, bit_order: BitOrder) -> Self {
+        self.bit_order = bit_order;
+        self
+    }
+
+    pub fn bit_order(&self) -> BitOrder {
+        self.bit_order
+    }
+
+    // Parity-check matrix column (as a syndrome value) for each codeword
+    // position; parity bits sit where the column is a power of two
+    pub(crate) fn columns(&self) -> impl Iterator<Item = usize> + use<> {
+        let poly = match self.layout {
+            Layout::Positional => None,
+            Layout::Matlab => PRIMITIVE_POLYS
+                .get(self.parity_bits)
+                .map(|&poly| (poly, 1 << self.parity_bits)),
+        };
+        let mut column = 1usize;
+
+        core::iter::from_fn(move || {
+            let current = column;
+            column = match poly {
+                None => column.wrapping_add(1),
+                // Multiply by x modulo the polynomial
+                Some((poly, top)) if (column << 1) & top != 0 => (column << 1) ^ poly,
+                Some(_) => column << 1,
+            };
+            Some(current)
+        })
+    }
+
+    // Bits of the underlying Hamming code, without the overall parity bit
+    fn hamming_bits(&self) -> usize {
+        self.data_bits.saturating_add(self.parity_bits)
+    }
+
+    // XOR of the columns of all set bits; zero for a valid codeword. For
+    // extended codes the parity of the whole block is the next bit up.
+    fn syndrome(&self, block: &[bool]) -> usize {
+        let syndrome = block
+            .iter()
+            .take(self.hamming_bits())
+            .zip(self.columns())
+            .filter(|&(&bit, _)| bit)
+            .fold(0, |syndrome, (_, column)| syndrome ^ column);
+
+        if self.extended {
+            let parity = block.iter().filter(|&&bit| bit).count() % 2;
+            syndrome | parity << self.parity_bits
+        } else {
+            syndrome
+        }
+    }
+
+    // Codeword position a nonzero syndrome points at, if any
+    fn error_position(&self, syndrome: usize) -> Option<usize> {
+        let syndrome = if self.extended {
+            // A single error always flips the overall parity; a nonzero
+            // syndrome with even parity is a double error
+            let overall = 1 << self.parity_bits;
+            if syndrome & overall == 0 {
+                return None;
             }
+            if syndrome == overall {
+                return Some(self.hamming_bits());
+            }
+            syndrome ^ overall
+        } else {
+            syndrome
+        };
+
+        self.columns()
+            .take(self.hamming_bits())
+            .position(|column| column == syndrome)
+    }
 
-            // Calculate parity bits
-            for p in 0..self.parity_bits {
-                let parity_pos = 1 << p;
-                let mut parity = false;
+    /// Error for an uncorrectable block: SEC-DED codes tell a double error
+    /// (nonzero syndrome, even overall parity) from worse
+    pub(crate) fn uncorrectable(&self, block: usize, syndrome: usize) -> HammingError {
+        if self.extended && syndrome & (1 << self.parity_bits) == 0 {
+            HammingError::DoubleErrorDetected { block }
+        } else {
+            HammingError::UncorrectableErrors
+        }
+    }
 
-                for i in 1..=block_bits {
-                    if (i & parity_pos) != 0 && block[i - 1] {
-                        parity = !parity;
-                    }
-                }
+    // Fill a block from a source of data bits and compute its parity bits
+    fn build_block(&self, mut data: impl FnMut() -> bool) -> Vec<bool> {
+        let mut block = vec![false; self.block_size()];
+        for (bit, column) in block
+            .iter_mut()
+            .take(self.hamming_bits())
+            .zip(self.columns())
+        {
+            if !column.is_power_of_two() {
+                *bit = data();
+            }
+        }
+
+        // Each parity bit cancels its bit of the syndrome
+        let syndrome = self.syndrome(&block);
+        for (bit, column) in block
+            .iter_mut()
+            .take(self.hamming_bits())
+            .zip(self.columns())
+        {
+            if column.is_power_of_two() {
+                *bit = syndrome & column != 0;
+            }
+        }
+        if self.extended {
+            let parity = block.iter().filter(|&&bit| bit).count() % 2 == 1;
+            if let Some(bit) = block.get_mut(self.hamming_bits()) {
+                *bit = parity;
+            }
+        }
+        block
+    }
+
+    // Fix a single-bit error in place
+    fn correct(&self, block: &mut [bool]) -> (usize, BlockOutcome) {
+        let syndrome = self.syndrome(block);
+        let outcome = self.syndrome_outcome(syndrome);
+        if let BlockOutcome::Corrected { bit: pos } = outcome
+            && let Some(bit) = block.get_mut(pos)
+        {
+            *bit = !*bit;
+        }
+        (syndrome, outcome)
+    }
+
+    /// Bits of a syndrome, the overall parity of extended codes included
+    pub(crate) fn syndrome_bits(&self) -> usize {
+        self.parity_bits + usize::from(self.extended)
+    }
+
+    /// How a block with `syndrome` is decoded
+    pub(crate) fn syndrome_outcome(&self, syndrome: usize) -> BlockOutcome {
+        match (syndrome, self.error_position(syndrome)) {
+            (0, _) => BlockOutcome::Clean,
+            (_, Some(pos)) => BlockOutcome::Corrected { bit: pos },
+            (_, None) => BlockOutcome::Uncorrectable,
+        }
+    }
+
+    // The data bits of a block, in order
+    fn data_of<'a>(&self, block: &'a [bool]) -> impl Iterator<Item = bool> + 'a {
+        block
+            .iter()
+            .take(self.hamming_bits())
+            .zip(self.columns())
+            .filter(|&(_, column)| !column.is_power_of_two())
+            .map(|(&bit, _)| bit)
+    }
 
-                block[parity_pos - 1] = parity;
+    // Whether each codeword position holds a parity bit
+    #[cfg(feature = "std")]
+    fn parity_positions(&self) -> impl Iterator<Item = bool> + use<> {
+        self.columns()
+            .take(self.hamming_bits())
+            .map(|column| column.is_power_of_two())
+            .chain(self.extended.then_some(true))
+    }
+
+    /// Parity bits of the block built from `data`, in codeword order
+    #[cfg(feature = "std")]
+    pub(crate) fn parity_of(&self, data: impl FnMut() -> bool) -> Vec<bool> {
+        self.build_block(data)
+            .into_iter()
+            .zip(self.parity_positions())
+            .filter(|&(_, parity)| parity)
+            .map(|(bit, _)| bit)
+            .collect()
+    }
+
+    /// Correct a block held as separate data and parity bits, in the order
+    /// of [`Hamming::parity_of`], in place
+    #[cfg(feature = "std")]
+    pub(crate) fn correct_split(&self, data: &mut [bool], parity: &mut [bool]) -> BlockOutcome {
+        let (mut data_bits, mut parity_bits) = (data.iter(), parity.iter());
+        let mut block: Vec<bool> = self
+            .parity_positions()
+            .map(|is_parity| {
+                let bit = if is_parity {
+                    parity_bits.next()
+                } else {
+                    data_bits.next()
+                };
+                bit.is_some_and(|&bit| bit)
+            })
+            .collect();
+
+        let (_, outcome) = self.correct(&mut block);
+        let (mut data_bits, mut parity_bits) = (data.iter_mut(), parity.iter_mut());
+        for (bit, is_parity) in block.into_iter().zip(self.parity_positions()) {
+            let slot = if is_parity {
+                parity_bits.next()
+            } else {
+                data_bits.next()
+            };
+            if let Some(slot) = slot {
+                *slot = bit;
             }
+        }
+        outcome
+    }
+
+    /// Codeword of the data word with only bit `data_bit` set: row
+    /// `data_bit` of the generator matrix
+    pub(crate) fn generator_row(&self, data_bit: usize) -> Vec<bool> {
+        let mut index = 0;
+        self.build_block(|| {
+            index += 1;
+            index == data_bit + 1
+        })
+    }
+
+    /// Rows of the parity-check matrix, one per syndrome bit
+    pub(crate) fn parity_check_rows(&self) -> Vec<Vec<bool>> {
+        let mut rows: Vec<Vec<bool>> = (0..self.parity_bits)
+            .map(|bit| {
+                let mut row: Vec<bool> = self
+                    .columns()
+                    .take(self.hamming_bits())
+                    .map(|column| (column >> bit) & 1 == 1)
+                    .collect();
+                row.resize(self.block_size(), false);
+                row
+            })
+            .collect();
+        if self.extended {
+            rows.push(vec![true; self.block_size()]);
+        }
+        rows
+    }
+
+    /// Encode the low `data_bits` bits of `data` as one codeword, bit `i`
+    /// holding codeword position `i`; only for codes of up to 128 bits
+    pub(crate) fn encode_word(&self, data: u128) -> u128 {
+        let mut data_bit = 0;
+        let block = self.build_block(|| {
+            data_bit += 1;
+            data.checked_shr(data_bit - 1).is_some_and(|d| d & 1 == 1)
+        });
+
+        block
+            .iter()
+            .take(128)
+            .enumerate()
+            .fold(0, |word, (i, &bit)| word | (bit as u128) << i)
+    }
+
+    /// Correct and extract the data bits of a codeword built like
+    /// [`Hamming::encode_word`]'s output
+    pub(crate) fn decode_word(&self, word: u128) -> (u128, usize, BlockOutcome) {
+        let mut block: Vec<bool> = (0..self.block_size().min(128))
+            .map(|i| (word >> i) & 1 == 1)
+            .collect();
+
+        let (syndrome, outcome) = self.correct(&mut block);
+        if outcome == BlockOutcome::Uncorrectable {
+            return (0, syndrome, outcome);
+        }
+
+        let data = self
+            .data_of(&block)
+            .take(128)
+            .enumerate()
+            .fold(0, |data, (i, bit)| data | (bit as u128) << i);
+        (data, syndrome, outcome)
+    }
+}
+
+impl Default for Hamming {
+    fn default() -> Self {
+        Self::new(4)
+    }
+}
+
+// Only the parameters are serialized; the parity bits are derived on load
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct HammingParams {
+    data_bits: usize,
+    #[serde(default, skip_serializing_if = "is_lsb_first")]
+    bit_order: BitOrder,
+    #[serde(default, skip_serializing_if = "is_positional")]
+    layout: Layout,
+    #[serde(default, skip_serializing_if = "core::ops::Not::not")]
+    extended: bool,
+}
+
+#[cfg(feature = "serde")]
+pub(crate) fn is_lsb_first(order: &BitOrder) -> bool {
+    *order == BitOrder::LsbFirst
+}
+
+#[cfg(feature = "serde")]
+pub(crate) fn is_positional(layout: &Layout) -> bool {
+    *layout == Layout::Positional
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Hamming {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        HammingParams {
+            data_bits: self.data_bits,
+            bit_order: self.bit_order,
+            layout: self.layout,
+            extended: self.extended,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Hamming {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let params = HammingParams::deserialize(deserializer)?;
+        Ok(Hamming::try_new(params.data_bits)
+            .map_err(serde::de::Error::custom)?
+            .with_bit_order(params.bit_order)
+            .with_layout(params.layout)
+            .with_extended(params.extended))
+    }
+}
+
+impl core::fmt::Display for Hamming {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.describe())
+    }
+}
+
+impl HammingCode for Hamming {
+    fn encode(&self, data: &[u8]) -> Vec<u8> {
+        if data.is_empty() {
+            return Vec::new();
+        }
+        if let Some(words) = WordCode::new(self) {
+            let capacity = (data.len() * 8).div_ceil(self.data_bits) * self.block_size();
+            return words.encode(
+                data,
+                self.bit_order,
+                Vec::with_capacity(capacity.div_ceil(8)),
+            );
+        }
+
+        let block_bits = self.block_size();
+        let total_data_bits = data.len() * 8;
+        let num_blocks = total_data_bits.div_ceil(self.data_bits);
+        let mut encoded = vec![0u8; (num_blocks * block_bits).div_ceil(8)];
+
+        // Data bits past the end of the input are zero padding
+        let mut data_bit = 0;
+        for block_idx in 0..num_blocks {
+            let block = self.build_block(|| {
+                data_bit += 1;
+                data_bit <= total_data_bits && self.bit_order.get(data, data_bit - 1)
+            });
 
-            // Write block to output
+            let output_bit_offset = block_idx * block_bits;
             for (i, &bit) in block.iter().enumerate() {
                 if bit {
-                    let global_bit_pos = output_bit_offset + i;
-                    let byte_idx = global_bit_pos / 8;
-                    let bit_idx = global_bit_pos % 8;
-                    encoded[byte_idx] |= 1 << bit_idx;
+                    self.bit_order.set(&mut encoded, output_bit_offset + i);
                 }
             }
         }
@@ -91,84 +491,222 @@ impl HammingCode for Hamming {
         encoded
     }
 
-    fn decode(&self, encoded: &[u8]) -> Result<Vec<u8>, HammingError> {
+    /// Fails with [`HammingError::Unsupported`] for a code without data
+    /// bits, on which [`HammingCode::encode`] panics
+    fn try_encode(&self, data: &[u8]) -> Result<Vec<u8>, HammingError> {
+        if !Self::supported(self.data_bits) {
+            return Err(HammingError::Unsupported);
+        }
+        Ok(self.encode(data))
+    }
+
+    fn decode_observed(
+        &self,
+        encoded: &[u8],
+        observer: &mut dyn FnMut(&BlockEvent),
+    ) -> Result<Vec<u8>, HammingError> {
+        if !Self::supported(self.data_bits) {
+            return Err(HammingError::Unsupported);
+        }
         if encoded.is_empty() {
             return Ok(Vec::new());
         }
 
-        let block_bits = self.data_bits + self.parity_bits;
-        let total_bits = encoded.len() * 8;
+        let blocks = encoded.len().saturating_mul(8) / self.block_size().max(1);
+        let decoded = Vec::with_capacity((blocks * self.data_bits).div_ceil(8));
+        telemetry::instrumented(observer, |observer| {
+            self.decode_blocks(encoded, observer, None, decoded)
+        })
+    }
 
-        let num_blocks = total_bits / block_bits;
-        if num_blocks == 0 {
-            return Err(HammingError::InvalidLength);
+    fn encode_into(&self, data: &[u8], out: &mut Vec<u8>) {
+        match WordCode::new(self) {
+            Some(words) => {
+                words.encode(data, self.bit_order, VecSink(out));
+            }
+            None => out.extend_from_slice(&self.encode(data)),
         }
+    }
 
-        let num_blocks = total_bits / block_bits;
-        let total_data_bits = num_blocks * self.data_bits;
-        let output_bytes = (total_data_bits + 7) / 8;
+    fn decode_into(&self, encoded: &[u8], out: &mut Vec<u8>) -> Result<(), HammingError> {
+        if !Self::supported(self.data_bits) {
+            return Err(HammingError::Unsupported);
+        }
+        if encoded.is_empty() {
+            return Ok(());
+        }
 
-        let mut decoded = vec![0u8; output_bytes];
-        let mut decoded_bit_pos = 0;
+        let start = out.len();
+        telemetry::instrumented(&mut |_| {}, |observer| {
+            self.decode_blocks(encoded, observer, None, VecSink(&mut *out))
+                .map(drop)
+        })
+        .inspect_err(|_| out.truncate(start))
+    }
 
-        for block_idx in 0..num_blocks {
-            // Read one block
-            let block_start_bit = block_idx * block_bits;
-            let mut block = vec![false; block_bits];
+    fn bit_order(&self) -> BitOrder {
+        self.bit_order
+    }
 
-            for i in 0..block_bits {
-                let global_bit = block_start_bit + i;
-                let byte_idx = global_bit / 8;
-                let bit_idx = global_bit % 8;
-                block[i] = (encoded[byte_idx] >> bit_idx) & 1 == 1;
-            }
+    fn blocks<'a>(&self, encoded: &'a [u8]) -> Result<Blocks<'a>, HammingError> {
+        Ok(
+            Blocks::new(encoded, self.block_size(), self.block_stride())?
+                .with_order(self.bit_order),
+        )
+    }
+
+    fn block_size(&self) -> usize {
+        self.hamming_bits().saturating_add(self.extended as usize)
+    }
 
-            // Calculate syndrome
-            let mut syndrome = 0;
-            for p in 0..self.parity_bits {
-                let parity_pos = 1 << p;
-                let mut calculated_parity = false;
+    fn detects_double_errors(&self) -> bool {
+        self.extended
+    }
 
-                for i in 1..=block_bits {
-                    if (i & parity_pos) != 0 && block[i - 1] {
-                        calculated_parity = !calculated_parity;
-                    }
-                }
+    fn describe(&self) -> String {
+        let kind = if self.extended { "SEC-DED" } else { "Hamming" };
+        let layout = match self.layout {
+            Layout::Positional => "",
+            Layout::Matlab => ", MATLAB layout",
+        };
+        format!(
+            "{kind}({},{}), {}{layout}, zero-pad",
+            self.block_size(),
+            self.data_bits,
+            self.bit_order
+        )
+    }
 
-                if calculated_parity {
-                    syndrome |= parity_pos;
+    /// Only the MATLAB layout, where it applies
+    fn is_systematic(&self) -> bool {
+        self.layout == Layout::Matlab && PRIMITIVE_POLYS.get(self.parity_bits).is_some()
+    }
+
+    fn data_bits(&self) -> usize {
+        self.data_bits
+    }
+}
+
+impl Hamming {
+    /// Decode, telling clean streams, corrected single errors and detected
+    /// double errors apart
+    ///
+    /// Unlike [`HammingCode::decode`], a double error in an extended code
+    /// does not fail the decode: the block's data bits are returned as
+    /// received and its index is listed in
+    /// [`SecdedDecode::double_errors`]. Other uncorrectable blocks still
+    /// fail with [`HammingError::UncorrectableErrors`].
+    pub fn decode_secded(&self, encoded: &[u8]) -> Result<SecdedDecode, HammingError> {
+        if encoded.is_empty() {
+            return Ok(SecdedDecode::default());
+        }
+
+        let (mut corrected, mut double_errors) = (0, Vec::new());
+        let data = telemetry::instrumented(
+            &mut |event| {
+                if let BlockOutcome::Corrected { .. } = event.outcome {
+                    corrected += 1;
                 }
-            }
+            },
+            |observer| self.decode_blocks(encoded, observer, Some(&mut double_errors), Vec::new()),
+        )?;
+        Ok(SecdedDecode {
+            data,
+            corrected,
+            double_errors,
+        })
+    }
 
-            // Fix single-bit error if needed
-            if syndrome != 0 && syndrome <= block_bits {
-                block[syndrome - 1] = !block[syndrome - 1];
-            } else if syndrome > block_bits {
-                return Err(HammingError::UncorrectableErrors);
-            }
+    // Decode block by block, appending to `out`; with `double_errors`,
+    // double errors are listed there instead of failing and their data bits
+    // kept as received
+    fn decode_blocks<B: Extend<u8>>(
+        &self,
+        encoded: &[u8],
+        observer: &mut dyn FnMut(&BlockEvent),
+        mut double_errors: Option<&mut Vec<usize>>,
+        mut out: B,
+    ) -> Result<B, HammingError> {
+        let block_bits = self.block_size();
+        // Also guards the bit offsets below against overflow
+        let total_bits = encoded
+            .len()
+            .checked_mul(8)
+            .ok_or(HammingError::InvalidLength)?;
+
+        let num_blocks = total_bits / block_bits;
+        if num_blocks == 0 {
+            return Err(HammingError::InvalidLength);
+        }
+
+        if let Some(words) = WordCode::new(self) {
+            return words.decode(
+                encoded,
+                self.bit_order,
+                num_blocks,
+                out,
+                |block, syndrome, outcome| {
+                    observer(&BlockEvent {
+                        block,
+                        bit_offset: block * block_bits,
+                        syndrome,
+                        outcome,
+                    });
+                    self.check_outcome(block, syndrome, outcome, &mut double_errors)
+                },
+            );
+        }
 
-            // Extract data bits
-            for pos in 1..=block_bits {
-                if !pos.is_power_of_two() && decoded_bit_pos < total_data_bits {
-                    if block[pos - 1] {
-                        let byte_idx = decoded_bit_pos / 8;
-                        let bit_idx = decoded_bit_pos % 8;
-                        decoded[byte_idx] |= 1 << bit_idx;
-                    }
-                    decoded_bit_pos += 1;
+        let total_data_bits = num_blocks * self.data_bits;
+        let mut decoded = vec![0u8; total_data_bits.div_ceil(8)];
+        let mut decoded_bit_pos = 0;
+
+        for block_idx in 0..num_blocks {
+            let block_start_bit = block_idx * block_bits;
+            let mut block: Vec<bool> = (0..block_bits)
+                .map(|i| self.bit_order.get(encoded, block_start_bit + i))
+                .collect();
+
+            let (syndrome, outcome) = self.correct(&mut block);
+            observer(&BlockEvent {
+                block: block_idx,
+                bit_offset: block_start_bit,
+                syndrome,
+                outcome,
+            });
+            self.check_outcome(block_idx, syndrome, outcome, &mut double_errors)?;
+
+            for bit in self.data_of(&block) {
+                if bit {
+                    self.bit_order.set(&mut decoded, decoded_bit_pos);
                 }
+                decoded_bit_pos += 1;
             }
         }
 
-        Ok(decoded)
+        out.extend(decoded);
+        Ok(out)
     }
 
-    fn block_size(&self) -> usize {
-        self.data_bits + self.parity_bits
-    }
-
-    fn data_bits(&self) -> usize {
-        self.data_bits
+    // Fail the decode for an uncorrectable block; with `double_errors`,
+    // double errors are listed there instead
+    fn check_outcome(
+        &self,
+        block: usize,
+        syndrome: usize,
+        outcome: BlockOutcome,
+        double_errors: &mut Option<&mut Vec<usize>>,
+    ) -> Result<(), HammingError> {
+        if outcome != BlockOutcome::Uncorrectable {
+            return Ok(());
+        }
+        match (self.uncorrectable(block, syndrome), double_errors) {
+            (HammingError::DoubleErrorDetected { .. }, Some(blocks)) => {
+                blocks.push(block);
+                Ok(())
+            }
+            (err, _) => Err(err),
+        }
     }
 }
 
@@ -199,4 +737,128 @@ mod tests {
         let decoded = h.decode(&encoded).unwrap();
         assert_eq!(decoded, data);
     }
+
+    #[test]
+    fn test_matlab_layout() {
+        let h = Hamming::new(4).with_layout(Layout::Matlab);
+
+        // Rows of G from MATLAB's hammgen(3): 1101000, 0110100, 1110010,
+        // 1010001, i.e. codeword bits c0..c6 packed LSB-first
+        for (i, row) in [0x0B, 0x16, 0x27, 0x45].into_iter().enumerate() {
+            assert_eq!(h.encode(&[1 << i]), vec![row, 0x00]);
+        }
+
+        let msb = Hamming::new(4)
+            .with_layout(Layout::Matlab)
+            .with_bit_order(BitOrder::MsbFirst);
+        assert_eq!(msb.encode(&[0x80]), vec![0b1101_0000, 0x00]);
+
+        let data: Vec<u8> = (0..=255).collect();
+        for bit in 0..7 {
+            let mut encoded = h.encode(&data);
+            encoded[0] ^= 1 << bit;
+            assert_eq!(h.decode(&encoded).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_matlab_polys_primitive() {
+        // x must have order 2^m - 1, so the columns only repeat after a full
+        // cycle and a code of every length gets distinct columns
+        for m in 2..PRIMITIVE_POLYS.len() {
+            let n = (1 << m) - 1;
+            let h = Hamming::new(n - m).with_layout(Layout::Matlab);
+            assert_eq!(h.parity_bits, m);
+            assert_eq!(h.columns().skip(1).position(|c| c == 1), Some(n - 1));
+        }
+    }
+
+    #[test]
+    fn test_extended_secded() {
+        let h = Hamming::new(64).with_extended(true);
+        assert_eq!(h.block_size(), 72);
+
+        let data = [0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF];
+        let encoded = h.encode(&data);
+        assert_eq!(encoded.len(), 9);
+
+        for i in 0..72 {
+            let mut single = encoded.clone();
+            single[i / 8] ^= 1 << (i % 8);
+            assert_eq!(h.decode(&single).unwrap(), data);
+
+            // Double errors, including ones involving the overall parity
+            // bit, are detected rather than miscorrected
+            for j in (i + 1)..72 {
+                let mut double = single.clone();
+                double[j / 8] ^= 1 << (j % 8);
+                assert_eq!(
+                    h.decode(&double),
+                    Err(HammingError::DoubleErrorDetected { block: 0 })
+                );
+            }
+        }
+
+        // The block is reported
+        let mut encoded = h.encode(&[data, data].concat());
+        encoded[12] ^= 0x41;
+        assert_eq!(
+            h.decode(&encoded),
+            Err(HammingError::DoubleErrorDetected { block: 1 })
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_general_hamming_serde() {
+        let h: Hamming = serde_json::from_str(r#"{"data_bits":26}"#).unwrap();
+        assert_eq!(h.block_size(), 31);
+        assert_eq!(serde_json::to_string(&h).unwrap(), r#"{"data_bits":26}"#);
+
+        let h: Hamming = serde_json::from_str(r#"{"data_bits":4,"bit_order":"msbfirst"}"#).unwrap();
+        assert_eq!(h.bit_order(), BitOrder::MsbFirst);
+
+        let h: Hamming = serde_json::from_str(r#"{"data_bits":64,"extended":true}"#).unwrap();
+        assert_eq!(h.block_size(), 72);
+
+        assert!(serde_json::from_str::<Hamming>(r#"{"data_bits":0}"#).is_err());
+    }
+
+    #[test]
+    fn test_general_hamming_try_new() {
+        assert_eq!(Hamming::try_new(0), Err(HammingError::Unsupported));
+        assert_eq!(
+            Hamming::try_new(Hamming::MAX_DATA_BITS + 1),
+            Err(HammingError::Unsupported)
+        );
+        assert_eq!(Hamming::try_new(usize::MAX), Err(HammingError::Unsupported));
+        assert_eq!(Hamming::try_new(26), Ok(Hamming::new(26)));
+
+        let largest = Hamming::try_new(Hamming::MAX_DATA_BITS).unwrap();
+        assert_eq!(largest.block_size(), (1 << 24) - 1);
+
+        // Out of range, but constructing does not overflow
+        assert_eq!(
+            Hamming::new(usize::MAX).parity_bits,
+            usize::BITS as usize - 1
+        );
+    }
+
+    #[test]
+    fn test_general_hamming_msb_first() {
+        let h = Hamming::new(4).with_bit_order(BitOrder::MsbFirst);
+
+        // Data 1011 gives the textbook codeword p1 p2 d1 p4 d2 d3 d4 = 0110011,
+        // followed by the all-zero codeword for the low nibble
+        assert_eq!(h.encode(&[0xB0]), vec![0b0110_0110, 0b0000_0000]);
+
+        // 1011 -> 0110011 and 0001 -> 1101001, packed left to right
+        let encoded = h.encode(&[0xB1]);
+        assert_eq!(encoded, vec![0b0110_0111, 0b1010_0100]);
+
+        // Flipping position 5 (d2) is corrected
+        let mut corrupted = encoded.clone();
+        corrupted[0] ^= 0b0000_1000;
+        assert_eq!(h.decode(&corrupted).unwrap(), vec![0xB1]);
+    }
 }
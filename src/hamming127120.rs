@@ -0,0 +1,498 @@
+use crate::bits::SliceSink;
+#[cfg(feature = "alloc")]
+use crate::bits::VecSink;
+use crate::{BlockEvent, BlockOutcome, HammingError, telemetry};
+#[cfg(feature = "alloc")]
+use crate::{Hamming, HammingCode};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+// Data bytes and encoded bytes per block: 120 data bits, and the 127-bit
+// codeword with a spare top bit
+const DATA_BYTES: usize = 15;
+const BLOCK_BYTES: usize = 16;
+
+// Runs of data bits between the parity positions: first data bit, codeword
+// bit, length
+const RUNS: [(u32, u32, u32); 6] = [
+    (0, 2, 1),
+    (1, 4, 3),
+    (4, 8, 7),
+    (11, 16, 15),
+    (26, 32, 31),
+    (57, 64, 63),
+];
+
+// Codeword bits covered by each of the 7 parity bits
+const CHECKS: [u128; 7] = check_masks();
+
+/// Hamming(127,120) implementation, the longest Hamming code in a `u128`:
+/// 15 data bytes per 16-byte block, 6.7% overhead
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Hamming127120;
+
+#[cfg(feature = "alloc")]
+impl core::fmt::Display for Hamming127120 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.describe())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl HammingCode for Hamming127120 {
+    fn encode(&self, data: &[u8]) -> Vec<u8> {
+        let mut encoded = Vec::with_capacity(Self::encoded_len(data.len()));
+        Self::encode_blocks(data, |block| {
+            encoded.extend_from_slice(&block.to_le_bytes())
+        });
+        encoded
+    }
+
+    fn decode_observed(
+        &self,
+        encoded: &[u8],
+        observer: &mut dyn FnMut(&BlockEvent),
+    ) -> Result<Vec<u8>, HammingError> {
+        if !encoded.len().is_multiple_of(BLOCK_BYTES) {
+            return Err(HammingError::InvalidLength);
+        }
+
+        telemetry::instrumented(observer, |observer| {
+            let decoded = Vec::with_capacity(Self::decoded_len(encoded.len()));
+            Self::decode_blocks(encoded, false, observer, decoded)
+        })
+    }
+
+    fn encode_into(&self, data: &[u8], out: &mut Vec<u8>) {
+        out.reserve(Self::encoded_len(data.len()));
+        Self::encode_blocks(data, |block| out.extend_from_slice(&block.to_le_bytes()));
+    }
+
+    fn decode_into(&self, encoded: &[u8], out: &mut Vec<u8>) -> Result<(), HammingError> {
+        if !encoded.len().is_multiple_of(BLOCK_BYTES) {
+            return Err(HammingError::InvalidLength);
+        }
+
+        let start = out.len();
+        out.reserve(Self::decoded_len(encoded.len()));
+        telemetry::instrumented(&mut |_| {}, |observer| {
+            Self::decode_blocks(encoded, false, observer, VecSink(&mut *out)).map(drop)
+        })
+        .inspect_err(|_| out.truncate(start))
+    }
+
+    fn encode_to_slice(&self, data: &[u8], out: &mut [u8]) -> Result<usize, HammingError> {
+        Hamming127120::encode_to_slice(self, data, out)
+    }
+
+    fn decode_to_slice(&self, encoded: &[u8], out: &mut [u8]) -> Result<usize, HammingError> {
+        Hamming127120::decode_to_slice(self, encoded, out)
+    }
+
+    fn block_size(&self) -> usize {
+        127
+    }
+
+    fn data_bits(&self) -> usize {
+        120
+    }
+
+    fn block_stride(&self) -> usize {
+        128
+    }
+}
+
+impl Hamming127120 {
+    /// Encoded size in bytes for `data_len` input bytes
+    pub const fn encoded_len(data_len: usize) -> usize {
+        data_len.div_ceil(DATA_BYTES) * BLOCK_BYTES
+    }
+
+    /// Decoded size in bytes for `encoded_len` encoded bytes
+    pub const fn decoded_len(encoded_len: usize) -> usize {
+        encoded_len / BLOCK_BYTES * DATA_BYTES
+    }
+
+    /// Encode into the front of `out`, returning the bytes written, or
+    /// [`HammingError::BufferTooSmall`] if they do not fit
+    pub fn encode_to_slice(&self, data: &[u8], out: &mut [u8]) -> Result<usize, HammingError> {
+        let len = Self::encoded_len(data.len());
+        let mut blocks = out
+            .get_mut(..len)
+            .ok_or(HammingError::BufferTooSmall)?
+            .as_chunks_mut::<BLOCK_BYTES>()
+            .0
+            .iter_mut();
+        Self::encode_blocks(data, |block| {
+            if let Some(bytes) = blocks.next() {
+                *bytes = block.to_le_bytes();
+            }
+        });
+        Ok(len)
+    }
+
+    /// Decode into the front of `out`, returning the bytes written, or
+    /// [`HammingError::BufferTooSmall`] if they do not fit
+    pub fn decode_to_slice(&self, encoded: &[u8], out: &mut [u8]) -> Result<usize, HammingError> {
+        if !encoded.len().is_multiple_of(BLOCK_BYTES) {
+            return Err(HammingError::InvalidLength);
+        }
+        let len = Self::decoded_len(encoded.len());
+        let out = out.get_mut(..len).ok_or(HammingError::BufferTooSmall)?;
+
+        telemetry::instrumented(&mut |_| {}, |observer| {
+            Ok(Self::decode_blocks(encoded, false, observer, SliceSink::new(out))?.len())
+        })
+    }
+
+    /// Encode `data` at compile time; `M` must be
+    /// [`Hamming127120::encoded_len`] of its length
+    ///
+    /// Used by [`hamming_encode!`](crate::hamming_encode).
+    #[doc(hidden)]
+    pub const fn encode_array<const M: usize>(data: &[u8]) -> [u8; M] {
+        assert!(M == Self::encoded_len(data.len()));
+        let mut encoded = [0; M];
+        let (mut data, mut k) = (data, 0);
+        while !data.is_empty() {
+            let (mut group, mut i) = (0u128, 0);
+            while let ([byte, rest @ ..], true) = (data, i < DATA_BYTES) {
+                group |= (*byte as u128) << (8 * i);
+                data = rest;
+                i += 1;
+            }
+            let block = Self::encode_block(group).to_le_bytes();
+            let mut bytes = block.as_slice();
+            while let [byte, rest @ ..] = bytes {
+                if let Some((_, [slot, ..])) = encoded.split_at_mut_checked(k) {
+                    *slot = *byte;
+                }
+                bytes = rest;
+                k += 1;
+            }
+        }
+        encoded
+    }
+
+    /// Encode one group of 120 data bits (the low bits of `data`) as a
+    /// codeword
+    #[inline]
+    pub const fn encode_codeword(data: u128) -> u128 {
+        Self::encode_block(data)
+    }
+
+    /// Decode one codeword into its 120 data bits and what was done to it
+    ///
+    /// A fixed amount of work with no allocation, locks or telemetry. Bit
+    /// 127 is ignored, as by [`Hamming127120::decode_to_slice`].
+    #[inline]
+    pub fn decode_codeword(codeword: u128) -> (u128, BlockOutcome) {
+        let (data, _, outcome) = Self::decode_block(codeword);
+        (data, outcome)
+    }
+
+    /// Decode, treating a set bit 127 as a bit error instead of ignoring it
+    ///
+    /// [`HammingCode::encode`] leaves bit 127 clear, so a set one is
+    /// corrected like any other flipped bit (`BlockOutcome::Corrected { bit:
+    /// 127 }`); on top of an error in the other bits, the block is rejected
+    /// with [`HammingError::DoubleErrorDetected`].
+    #[cfg(feature = "alloc")]
+    pub fn decode_strict(&self, encoded: &[u8]) -> Result<Vec<u8>, HammingError> {
+        self.decode_strict_observed(encoded, &mut |_| {})
+    }
+
+    /// [`Hamming127120::decode_strict`], reporting every block to `observer`
+    #[cfg(feature = "alloc")]
+    pub fn decode_strict_observed(
+        &self,
+        encoded: &[u8],
+        observer: &mut dyn FnMut(&BlockEvent),
+    ) -> Result<Vec<u8>, HammingError> {
+        if !encoded.len().is_multiple_of(BLOCK_BYTES) {
+            return Err(HammingError::InvalidLength);
+        }
+
+        telemetry::instrumented(observer, |observer| {
+            let decoded = Vec::with_capacity(Self::decoded_len(encoded.len()));
+            Self::decode_blocks(encoded, true, observer, decoded)
+        })
+    }
+
+    /// SEC-DED(128,120): the same layout with bit 127 as an overall parity
+    /// bit
+    ///
+    /// Its encoding equals [`Hamming127120`]'s apart from bit 127.
+    #[cfg(feature = "alloc")]
+    pub fn secded() -> Hamming {
+        Hamming::new(120).with_extended(true)
+    }
+
+    // One encoded block per 15 data bytes; the last group is padded with
+    // zero bytes
+    fn encode_blocks(data: &[u8], mut emit: impl FnMut(u128)) {
+        for group in data.chunks(DATA_BYTES) {
+            let mut bytes = [0; BLOCK_BYTES];
+            bytes
+                .get_mut(..group.len())
+                .unwrap_or_default()
+                .copy_from_slice(group);
+            emit(Self::encode_block(u128::from_le_bytes(bytes)));
+        }
+    }
+
+    // Decode 16-byte blocks, reporting each one, and append their data bytes
+    // to `out`
+    fn decode_blocks<B: Extend<u8>>(
+        encoded: &[u8],
+        strict: bool,
+        observer: &mut dyn FnMut(&BlockEvent),
+        mut out: B,
+    ) -> Result<B, HammingError> {
+        let (chunks, _) = encoded.as_chunks::<BLOCK_BYTES>();
+        for (i, &chunk) in chunks.iter().enumerate() {
+            let block = u128::from_le_bytes(chunk);
+
+            let (data, syndrome, mut outcome) = Self::decode_block(block);
+            // In strict mode a set spare bit is an error of its own
+            let spare = strict && block >> 127 != 0;
+            if spare {
+                outcome = match outcome {
+                    BlockOutcome::Clean => BlockOutcome::Corrected { bit: 127 },
+                    _ => BlockOutcome::Uncorrectable,
+                };
+            }
+            observer(&BlockEvent {
+                block: i,
+                bit_offset: i * 128,
+                syndrome: syndrome as usize,
+                outcome,
+            });
+            if outcome == BlockOutcome::Uncorrectable {
+                return Err(HammingError::DoubleErrorDetected { block: i });
+            }
+
+            out.extend(data.to_le_bytes().into_iter().take(DATA_BYTES));
+        }
+        Ok(out)
+    }
+
+    /// Encode into a fixed-capacity vector without allocating
+    #[cfg(feature = "heapless")]
+    pub fn encode_heapless<const N: usize>(
+        &self,
+        data: &[u8],
+    ) -> Result<heapless::Vec<u8, N>, HammingError> {
+        if Self::encoded_len(data.len()) > N {
+            return Err(HammingError::BufferTooSmall);
+        }
+
+        let mut encoded = heapless::Vec::new();
+        Self::encode_blocks(data, |block| {
+            // Capacity was checked above
+            let _ = encoded.extend_from_slice(&block.to_le_bytes());
+        });
+        Ok(encoded)
+    }
+
+    /// Decode into a fixed-capacity vector without allocating
+    #[cfg(feature = "heapless")]
+    pub fn decode_heapless<const N: usize>(
+        &self,
+        encoded: &[u8],
+    ) -> Result<heapless::Vec<u8, N>, HammingError> {
+        if !encoded.len().is_multiple_of(BLOCK_BYTES) {
+            return Err(HammingError::InvalidLength);
+        }
+        if Self::decoded_len(encoded.len()) > N {
+            return Err(HammingError::BufferTooSmall);
+        }
+
+        telemetry::instrumented(&mut |_| {}, |observer| {
+            // Capacity was checked above
+            Self::decode_blocks(encoded, false, observer, heapless::Vec::new())
+        })
+    }
+
+    pub(crate) const fn encode_block(data: u128) -> u128 {
+        // Data bits fill the positions that are not powers of two
+        let mut block = 0;
+        let mut runs = RUNS.as_slice();
+        while let [(data_bit, position, len), rest @ ..] = runs {
+            block |= (data >> *data_bit & mask(*len)) << *position;
+            runs = rest;
+        }
+
+        // Parity bit `j` at position 2^j covers the positions with bit `j`
+        // set
+        let mut checks = CHECKS.as_slice();
+        let mut j = 0;
+        while let [check, rest @ ..] = checks {
+            block |= ((block & *check).count_ones() as u128 & 1) << ((1 << j) - 1);
+            checks = rest;
+            j += 1;
+        }
+        block
+    }
+
+    // Returns the data bits, the syndrome and what was done to the block
+    pub(crate) fn decode_block(block: u128) -> (u128, u8, BlockOutcome) {
+        let syndrome = CHECKS.iter().enumerate().fold(0u8, |syndrome, (j, check)| {
+            syndrome | ((block & check).count_ones() as u8 & 1) << j
+        });
+
+        // Every syndrome points at a bit: the code is perfect
+        let mut corrected = block;
+        let mut outcome = BlockOutcome::Clean;
+        if syndrome != 0 {
+            corrected ^= 1 << (syndrome - 1);
+            outcome = BlockOutcome::Corrected {
+                bit: syndrome as usize - 1,
+            };
+        }
+
+        let data = RUNS.iter().fold(0, |data, &(data_bit, position, len)| {
+            data | (corrected >> position & mask(len)) << data_bit
+        });
+        (data, syndrome, outcome)
+    }
+}
+
+const fn mask(len: u32) -> u128 {
+    (1 << len) - 1
+}
+
+// Bit `b` of the codeword is position `b + 1`; check `j` covers the positions
+// with bit `j` set
+const fn check_masks() -> [u128; 7] {
+    let mut checks = [0; 7];
+    let mut bit = 0;
+    while bit < 127 {
+        let position = bit + 1;
+        let mut j = 0;
+        while j < 7 {
+            if let Some((_, [check, ..])) = checks.split_at_mut_checked(j)
+                && position & (1 << j) != 0
+            {
+                *check |= 1 << bit;
+            }
+            j += 1;
+        }
+        bit += 1;
+    }
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::flip_bit;
+    use crate::hamming_encode;
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_hamming127120_roundtrip() {
+        let data: Vec<u8> = (0..100u8).map(|i| i.wrapping_mul(151) ^ 0x3C).collect();
+        let h = Hamming127120;
+        let mut encoded = h.encode(&data);
+        assert_eq!(encoded.len(), Hamming127120::encoded_len(100));
+        assert_eq!(Hamming127120::decoded_len(encoded.len()), 105);
+
+        // One error per block, anywhere in the 127 bits
+        for block in 0..encoded.len() / 16 {
+            let bit = (block * 37) % 127;
+            flip_bit(&mut encoded, block * 16 + bit / 8, (bit % 8) as u32);
+        }
+        let decoded = h.decode(&encoded).unwrap();
+        assert_eq!(decoded[..100], data);
+        assert_eq!(decoded[100..], [0; 5]);
+        assert_eq!(h.decode(&encoded[..15]), Err(HammingError::InvalidLength));
+
+        let mut out = [0; Hamming127120::decoded_len(112)];
+        assert_eq!(h.decode_to_slice(&encoded, &mut out), Ok(105));
+        assert_eq!(out[..], decoded[..]);
+        let mut out = [0; Hamming127120::encoded_len(100)];
+        assert_eq!(h.encode_to_slice(&data, &mut out), Ok(112));
+        assert_eq!(out[..], h.encode(&data)[..]);
+    }
+
+    #[test]
+    fn test_hamming127120_slices() {
+        let h = Hamming127120;
+        const ENCODED: [u8; 32] = hamming_encode!(Hamming127120, b"compile-time encoded");
+        let mut encoded = [0; Hamming127120::encoded_len(20)];
+        assert_eq!(
+            h.encode_to_slice(b"compile-time encoded", &mut encoded),
+            Ok(32)
+        );
+        assert_eq!(encoded, ENCODED);
+        assert_eq!(
+            h.encode_to_slice(b"compile-time encoded", &mut encoded[..20]),
+            Err(HammingError::BufferTooSmall)
+        );
+
+        // One error in each block
+        encoded[3] ^= 0x10;
+        encoded[30] ^= 0x40;
+        let mut decoded = [0; Hamming127120::decoded_len(32)];
+        assert_eq!(h.decode_to_slice(&encoded, &mut decoded), Ok(30));
+        assert_eq!(decoded[..20], *b"compile-time encoded");
+        assert_eq!(decoded[20..], [0; 10]);
+        assert_eq!(
+            h.decode_to_slice(&encoded[..15], &mut decoded),
+            Err(HammingError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn test_hamming127120_codeword() {
+        let data = 0x00AB_CDEF_0123_4567_89AB_CDEF_0123_4567;
+        let codeword = Hamming127120::encode_codeword(data);
+        assert_eq!(codeword >> 127, 0);
+        assert_eq!(
+            Hamming127120::decode_codeword(codeword),
+            (data & mask(120), BlockOutcome::Clean)
+        );
+        for bit in 0..127 {
+            assert_eq!(
+                Hamming127120::decode_codeword(codeword ^ (1 << bit)),
+                (data & mask(120), BlockOutcome::Corrected { bit })
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_hamming127120_matches_general() {
+        // The same codewords as the general code, and SEC-DED apart from
+        // the spare bit
+        let data = 0x00AB_CDEF_0123_4567_89AB_CDEF_0123_4567;
+        let codeword = Hamming127120::encode_codeword(data);
+        let general = Hamming::new(120).encode_word(data & mask(120));
+        assert_eq!(codeword, general);
+        let secded = Hamming127120::secded().encode_word(data & mask(120));
+        assert_eq!(codeword, secded & mask(127));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_hamming127120_spare_bit() {
+        let h = Hamming127120;
+        let mut encoded = h.encode(b"spare bit of the last byte");
+        encoded[15] |= 0x80;
+        assert!(h.decode(&encoded).is_ok());
+        let mut outcomes = Vec::new();
+        h.decode_strict_observed(&encoded, &mut |event| outcomes.push(event.outcome))
+            .unwrap();
+        assert_eq!(outcomes[0], BlockOutcome::Corrected { bit: 127 });
+
+        encoded[16] ^= 0x01;
+        encoded[31] |= 0x80;
+        assert_eq!(
+            h.decode_strict(&encoded),
+            Err(HammingError::DoubleErrorDetected { block: 1 })
+        );
+    }
+}
@@ -1,95 +1,393 @@
-use crate::{HammingCode, HammingError};
+#[cfg(feature = "alloc")]
+use crate::bits::VecSink;
+use crate::bits::{BitReader, BitWriter, SliceSink};
+use crate::{BlockEvent, BlockOutcome, CorrectionStats, HammingError, telemetry};
+#[cfg(feature = "alloc")]
+use crate::{Hamming, HammingCode};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 /// Hamming(15,11) implementation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Hamming1511;
 
+#[cfg(feature = "alloc")]
+impl core::fmt::Display for Hamming1511 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.describe())
+    }
+}
+
+#[cfg(feature = "alloc")]
 impl HammingCode for Hamming1511 {
     fn encode(&self, data: &[u8]) -> Vec<u8> {
-        if data.is_empty() {
-            return Vec::new();
+        let mut encoded = Vec::with_capacity(Self::encoded_len(data.len()));
+        Self::encode_blocks(data, |block| {
+            encoded.push(block as u8);
+            encoded.push((block >> 8) as u8);
+        });
+        encoded
+    }
+
+    fn decode_observed(
+        &self,
+        encoded: &[u8],
+        observer: &mut dyn FnMut(&BlockEvent),
+    ) -> Result<Vec<u8>, HammingError> {
+        if !encoded.len().is_multiple_of(2) {
+            return Err(HammingError::InvalidLength);
         }
 
-        let mut encoded = Vec::new();
-        let mut bit_accumulator = 0u32;
-        let mut acc_bits = 0;
+        telemetry::instrumented(observer, |observer| {
+            let decoded = Vec::with_capacity(Self::decoded_len(encoded.len()));
+            Self::decode_blocks(encoded, false, observer, decoded)
+        })
+    }
 
-        for &byte in data {
-            // Add byte to accumulator
-            bit_accumulator |= (byte as u32) << acc_bits;
-            acc_bits += 8;
+    fn encode_into(&self, data: &[u8], out: &mut Vec<u8>) {
+        out.reserve(Self::encoded_len(data.len()));
+        Self::encode_blocks(data, |block| out.extend_from_slice(&block.to_le_bytes()));
+    }
+
+    fn decode_into(&self, encoded: &[u8], out: &mut Vec<u8>) -> Result<(), HammingError> {
+        if !encoded.len().is_multiple_of(2) {
+            return Err(HammingError::InvalidLength);
+        }
+
+        let start = out.len();
+        out.reserve(Self::decoded_len(encoded.len()));
+        telemetry::instrumented(&mut |_| {}, |observer| {
+            Self::decode_blocks(encoded, false, observer, VecSink(&mut *out)).map(drop)
+        })
+        .inspect_err(|_| out.truncate(start))
+    }
+
+    fn encode_to_slice(&self, data: &[u8], out: &mut [u8]) -> Result<usize, HammingError> {
+        Hamming1511::encode_to_slice(self, data, out)
+    }
+
+    fn decode_to_slice(&self, encoded: &[u8], out: &mut [u8]) -> Result<usize, HammingError> {
+        Hamming1511::decode_to_slice(self, encoded, out)
+    }
+
+    fn correct_in_place(&self, encoded: &mut [u8]) -> Result<CorrectionStats, HammingError> {
+        Hamming1511::correct_in_place(self, encoded)
+    }
+
+    fn block_size(&self) -> usize {
+        15
+    }
+    fn data_bits(&self) -> usize {
+        11
+    }
+
+    fn block_stride(&self) -> usize {
+        16
+    }
+}
+
+impl Hamming1511 {
+    /// Encoded size in bytes for `data_len` input bytes, saturating at
+    /// `usize::MAX` for lengths no buffer can hold
+    pub const fn encoded_len(data_len: usize) -> usize {
+        match Self::checked_encoded_len(data_len) {
+            Some(len) => len,
+            None => usize::MAX,
+        }
+    }
+
+    /// Encoded size in bytes for `data_len` input bytes, or `None` if it
+    /// overflows `usize`
+    pub const fn checked_encoded_len(data_len: usize) -> Option<usize> {
+        // Every 11 bytes fill exactly 8 blocks, so only the remainder is
+        // scaled up and nothing overflows before the final doubling
+        let blocks = data_len / 11 * 8 + (data_len % 11 * 8).div_ceil(11);
+        blocks.checked_mul(2)
+    }
 
-            // Process while we have at least 11 bits
-            while acc_bits >= 11 {
-                // Take 11 bits
-                let data_bits = (bit_accumulator & 0x7FF) as u16;
-                bit_accumulator >>= 11;
-                acc_bits -= 11;
+    /// Decoded size in bytes for `encoded_len` encoded bytes
+    pub const fn decoded_len(encoded_len: usize) -> usize {
+        let blocks = encoded_len / 2;
+        blocks / 8 * 11 + blocks % 8 * 11 / 8
+    }
 
-                // Encode to 15 bits
-                let encoded_block = Self::encode_block(data_bits);
+    /// Encode into the front of `out`, returning the bytes written, or
+    /// [`HammingError::BufferTooSmall`] if they do not fit
+    pub fn encode_to_slice(&self, data: &[u8], out: &mut [u8]) -> Result<usize, HammingError> {
+        let len = Self::encoded_len(data.len());
+        let mut blocks = out
+            .get_mut(..len)
+            .ok_or(HammingError::BufferTooSmall)?
+            .as_chunks_mut::<2>()
+            .0
+            .iter_mut();
+        Self::encode_blocks(data, |block| {
+            if let Some(bytes) = blocks.next() {
+                *bytes = block.to_le_bytes();
+            }
+        });
+        Ok(len)
+    }
+
+    /// Decode into the front of `out`, returning the bytes written, or
+    /// [`HammingError::BufferTooSmall`] if they do not fit
+    pub fn decode_to_slice(&self, encoded: &[u8], out: &mut [u8]) -> Result<usize, HammingError> {
+        if !encoded.len().is_multiple_of(2) {
+            return Err(HammingError::InvalidLength);
+        }
+        let len = Self::decoded_len(encoded.len());
+        let out = out.get_mut(..len).ok_or(HammingError::BufferTooSmall)?;
+
+        telemetry::instrumented(&mut |_| {}, |observer| {
+            Ok(Self::decode_blocks(encoded, false, observer, SliceSink::new(out))?.len())
+        })
+    }
 
-                // Output the encoded block
-                encoded.push(encoded_block as u8);
-                encoded.push((encoded_block >> 8) as u8);
+    /// Fix every correctable error of `encoded` in place, leaving it
+    /// encoded, as `HammingCode::correct_in_place` does
+    ///
+    /// All blocks are checked before any is changed, so on an uncorrectable
+    /// block the stream is left as it was. Bit 15 of each block is kept.
+    pub fn correct_in_place(&self, encoded: &mut [u8]) -> Result<CorrectionStats, HammingError> {
+        if !encoded.len().is_multiple_of(2) {
+            return Err(HammingError::InvalidLength);
+        }
+        // A sink with no room discards the decoded data
+        telemetry::instrumented(&mut |_| {}, |observer| {
+            Self::decode_blocks(encoded, false, observer, SliceSink::new(&mut [])).map(drop)
+        })?;
+
+        let (blocks, _) = encoded.as_chunks_mut::<2>();
+        let mut corrected_bits = 0;
+        for pair in blocks.iter_mut() {
+            let block = u16::from_le_bytes(*pair);
+            if let (_, _, BlockOutcome::Corrected { bit }) = Self::decode_block(block) {
+                *pair = (block ^ (1 << bit)).to_le_bytes();
+                corrected_bits += 1;
             }
         }
+        Ok(CorrectionStats {
+            blocks: blocks.len(),
+            corrected_bits,
+        })
+    }
 
-        // Handle remaining bits if any
-        if acc_bits > 0 {
-            let data_bits = (bit_accumulator & ((1 << acc_bits) - 1)) as u16;
-            let encoded_block = Self::encode_block(data_bits);
-            encoded.push(encoded_block as u8);
-            encoded.push((encoded_block >> 8) as u8);
+    /// Encode `data` at compile time; `M` must be [`Hamming1511::encoded_len`]
+    /// of its length
+    ///
+    /// Used by [`hamming_encode!`](crate::hamming_encode).
+    #[doc(hidden)]
+    pub const fn encode_array<const M: usize>(data: &[u8]) -> [u8; M] {
+        assert!(M == Self::encoded_len(data.len()));
+        let mut encoded = [0; M];
+        let (mut data, mut out) = (data, encoded.as_mut_slice());
+        // 11-bit groups taken LSB-first, as `BitReader` does
+        let (mut acc, mut acc_bits) = (0u32, 0u32);
+        while let [byte, rest @ ..] = data {
+            acc |= (*byte as u32) << acc_bits;
+            acc_bits += 8;
+            data = rest;
+            while acc_bits >= 11 || (data.is_empty() && acc_bits > 0) {
+                let block = Self::encode_block(acc as u16 & 0x7FF).to_le_bytes();
+                if let [lower, upper, out_rest @ ..] = out {
+                    (*lower, *upper) = (block[0], block[1]);
+                    out = out_rest;
+                }
+                acc >>= 11;
+                acc_bits = acc_bits.saturating_sub(11);
+            }
         }
+        encoded
+    }
 
+    /// Encode one group of 11 data bits (the low bits of `data`) as a
+    /// codeword
+    #[inline]
+    pub const fn encode_codeword(data: u16) -> u16 {
+        Self::encode_block(data)
+    }
+
+    /// Decode one codeword into its 11 data bits and what was done to it
+    ///
+    /// A pure function of `codeword`: a fixed amount of work with no loops,
+    /// allocation, locks, shared state or telemetry, and built without the
+    /// `std` and `alloc` features, so `no_std` firmware can call it from an
+    /// interrupt handler, e.g. to decode one codeword per control tick. Bit
+    /// 15 is ignored, as by [`Hamming1511::decode_to_slice`].
+    #[inline]
+    pub fn decode_codeword(codeword: u16) -> (u16, BlockOutcome) {
+        let (data, _, outcome) = Self::decode_block(codeword);
+        (data, outcome)
+    }
+
+    /// Encode followed by one block holding the number of valid data bits
+    /// in the final block, so [`Hamming1511::decode_exact`] returns exactly
+    /// `data.len()` bytes
+    ///
+    /// Plain [`HammingCode::decode`] returns every whole byte the blocks
+    /// hold, which can include a byte of zero padding.
+    #[cfg(feature = "alloc")]
+    pub fn encode_exact(&self, data: &[u8]) -> Vec<u8> {
+        let mut encoded = self.encode(data);
+        let valid = match (data.len() * 8) % 11 {
+            0 if data.is_empty() => 0,
+            0 => 11,
+            bits => bits,
+        };
+        encoded.extend_from_slice(&Self::encode_block(valid as u16).to_le_bytes());
         encoded
     }
 
-    fn decode(&self, encoded: &[u8]) -> Result<Vec<u8>, HammingError> {
-        if encoded.is_empty() {
-            return Ok(Vec::new());
+    /// Decode a stream produced by [`Hamming1511::encode_exact`], dropping
+    /// the padding of the final block
+    #[cfg(feature = "alloc")]
+    pub fn decode_exact(&self, encoded: &[u8]) -> Result<Vec<u8>, HammingError> {
+        let (encoded, trailer) = encoded
+            .split_last_chunk::<2>()
+            .ok_or(HammingError::InvalidLength)?;
+        let mut decoded = self.decode(encoded)?;
+
+        let (valid, _, outcome) = Self::decode_block(u16::from_le_bytes(*trailer));
+        if outcome == BlockOutcome::Uncorrectable {
+            return Err(HammingError::UncorrectableErrors);
+        }
+        let blocks = encoded.len() / 2;
+        if valid > 11 || (blocks == 0) != (valid == 0) {
+            return Err(HammingError::InvalidLength);
         }
+        decoded.truncate((blocks.saturating_sub(1) * 11 + valid as usize) / 8);
+        Ok(decoded)
+    }
+
+    /// Decode, treating a set bit 15 as a bit error instead of ignoring it
+    ///
+    /// [`HammingCode::encode`] leaves bit 15 clear, so a set one is corrected
+    /// like any other flipped bit (`BlockOutcome::Corrected { bit: 15 }`); on
+    /// top of an error in the other bits, the block is rejected with
+    /// [`HammingError::DoubleErrorDetected`].
+    #[cfg(feature = "alloc")]
+    pub fn decode_strict(&self, encoded: &[u8]) -> Result<Vec<u8>, HammingError> {
+        self.decode_strict_observed(encoded, &mut |_| {})
+    }
 
-        if encoded.len() % 2 != 0 {
+    /// [`Hamming1511::decode_strict`], reporting every block to `observer`
+    #[cfg(feature = "alloc")]
+    pub fn decode_strict_observed(
+        &self,
+        encoded: &[u8],
+        observer: &mut dyn FnMut(&BlockEvent),
+    ) -> Result<Vec<u8>, HammingError> {
+        if !encoded.len().is_multiple_of(2) {
             return Err(HammingError::InvalidLength);
         }
 
-        let mut decoded = Vec::new();
-        let mut bit_accumulator = 0u32;
-        let mut acc_bits = 0;
+        telemetry::instrumented(observer, |observer| {
+            let decoded = Vec::with_capacity(Self::decoded_len(encoded.len()));
+            Self::decode_blocks(encoded, true, observer, decoded)
+        })
+    }
 
-        // Process each 15-bit block (stored in 2 bytes)
-        for chunk in encoded.chunks(2) {
-            let block = chunk[0] as u16 | ((chunk[1] as u16) << 8);
+    /// SEC-DED(16,11): the same layout with bit 15 as an overall parity bit
+    ///
+    /// Its encoding equals [`Hamming1511`]'s apart from bit 15.
+    #[cfg(feature = "alloc")]
+    pub fn secded() -> Hamming {
+        Hamming::new(11).with_extended(true)
+    }
 
-            // Decode the block
-            let data_bits = Self::decode_block(block)?;
+    // Pack the data into 11-bit groups and emit one encoded block per group;
+    // the last group is padded with zero bits
+    fn encode_blocks(data: &[u8], mut emit: impl FnMut(u16)) {
+        let mut reader = BitReader::new(data);
+        while let Some(data_bits) = reader.read(11) {
+            emit(Self::encode_block(data_bits as u16));
+        }
+    }
 
-            // Add to accumulator
-            bit_accumulator |= (data_bits as u32) << acc_bits;
-            acc_bits += 11;
+    // Decode 2-byte blocks, reporting each one, and append every completed
+    // output byte to `out`
+    fn decode_blocks<B: Extend<u8>>(
+        encoded: &[u8],
+        strict: bool,
+        observer: &mut dyn FnMut(&BlockEvent),
+        out: B,
+    ) -> Result<B, HammingError> {
+        let mut writer = BitWriter::with_sink(out);
+
+        // Process each 15-bit block (stored in 2 bytes)
+        let (chunks, _) = encoded.as_chunks::<2>();
+        for (i, &chunk) in chunks.iter().enumerate() {
+            let block = u16::from_le_bytes(chunk);
 
-            // Output complete bytes
-            while acc_bits >= 8 {
-                decoded.push(bit_accumulator as u8);
-                bit_accumulator >>= 8;
-                acc_bits -= 8;
+            // Decode the block
+            let (data_bits, syndrome, mut outcome) = Self::decode_block(block);
+            // In strict mode a set spare bit is an error of its own
+            let spare = strict && block & 0x8000 != 0;
+            if spare {
+                outcome = match outcome {
+                    BlockOutcome::Clean => BlockOutcome::Corrected { bit: 15 },
+                    _ => BlockOutcome::Uncorrectable,
+                };
+            }
+            observer(&BlockEvent {
+                block: i,
+                bit_offset: i * 16,
+                syndrome: syndrome as usize,
+                outcome,
+            });
+            if outcome == BlockOutcome::Uncorrectable {
+                return Err(if spare {
+                    HammingError::DoubleErrorDetected { block: i }
+                } else {
+                    HammingError::UncorrectableErrors
+                });
             }
+
+            writer.write(data_bits.into(), 11);
         }
 
-        Ok(decoded)
+        Ok(writer.finish_whole_bytes())
     }
 
-    fn block_size(&self) -> usize {
-        15
+    /// Encode into a fixed-capacity vector without allocating
+    #[cfg(feature = "heapless")]
+    pub fn encode_heapless<const N: usize>(
+        &self,
+        data: &[u8],
+    ) -> Result<heapless::Vec<u8, N>, HammingError> {
+        if Self::encoded_len(data.len()) > N {
+            return Err(HammingError::BufferTooSmall);
+        }
+
+        let mut encoded = heapless::Vec::new();
+        Self::encode_blocks(data, |block| {
+            // Capacity was checked above
+            let _ = encoded.extend_from_slice(&block.to_le_bytes());
+        });
+        Ok(encoded)
     }
-    fn data_bits(&self) -> usize {
-        11
+
+    /// Decode into a fixed-capacity vector without allocating
+    #[cfg(feature = "heapless")]
+    pub fn decode_heapless<const N: usize>(
+        &self,
+        encoded: &[u8],
+    ) -> Result<heapless::Vec<u8, N>, HammingError> {
+        if !encoded.len().is_multiple_of(2) {
+            return Err(HammingError::InvalidLength);
+        }
+        if Self::decoded_len(encoded.len()) > N {
+            return Err(HammingError::BufferTooSmall);
+        }
+
+        telemetry::instrumented(&mut |_| {}, |observer| {
+            // Capacity was checked above
+            Self::decode_blocks(encoded, false, observer, heapless::Vec::new())
+        })
     }
-}
 
-impl Hamming1511 {
-    fn encode_block(data: u16) -> u16 {
+    pub(crate) const fn encode_block(data: u16) -> u16 {
         let d = data & 0x7FF; // Ensure only 11 bits
 
         // Map data bits to their positions in the 15-bit block
@@ -124,7 +422,8 @@ impl Hamming1511 {
         block
     }
 
-    fn decode_block(block: u16) -> Result<u16, HammingError> {
+    // Returns the data bits, the syndrome and what was done to the block
+    pub(crate) fn decode_block(block: u16) -> (u16, u16, BlockOutcome) {
         // Calculate syndrome
         let s1 = Self::calc_parity(block, 0x5555);
         let s2 = Self::calc_parity(block, 0x6666);
@@ -135,11 +434,15 @@ impl Hamming1511 {
 
         // Correct error if needed
         let mut corrected = block;
+        let mut outcome = BlockOutcome::Clean;
         if syndrome != 0 {
             if syndrome <= 15 {
                 corrected ^= 1 << (syndrome - 1);
+                outcome = BlockOutcome::Corrected {
+                    bit: syndrome as usize - 1,
+                };
             } else {
-                return Err(HammingError::UncorrectableErrors);
+                return (0, syndrome, BlockOutcome::Uncorrectable);
             }
         }
 
@@ -157,11 +460,11 @@ impl Hamming1511 {
         data |= (corrected >> 4) & 0x200; // position 14 -> d9
         data |= (corrected >> 4) & 0x400; // position 15 -> d10
 
-        Ok(data)
+        (data, syndrome, outcome)
     }
 
     #[inline]
-    fn calc_parity(block: u16, mask: u16) -> u16 {
+    const fn calc_parity(block: u16, mask: u16) -> u16 {
         (block & mask).count_ones() as u16 & 1
     }
 }
@@ -169,8 +472,11 @@ impl Hamming1511 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(feature = "alloc")]
+    use alloc::vec;
 
     #[test]
+    #[cfg(feature = "alloc")]
     fn test_hamming1511_basic() {
         let h = Hamming1511;
         let data = vec![0x47, 0xA3];
@@ -182,6 +488,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "alloc")]
     fn test_hamming1511_single_bit_error() {
         let h = Hamming1511;
         let data = vec![0x55, 0xAA];
@@ -194,12 +501,139 @@ mod tests {
         assert_eq!(decoded, data);
     }
 
+    #[test]
+    fn test_hamming1511_codeword() {
+        const CODEWORD: u16 = Hamming1511::encode_codeword(0x5A3);
+        let codeword = CODEWORD;
+        let mut encoded = [0; Hamming1511::encoded_len(2)];
+        assert_eq!(
+            Hamming1511.encode_to_slice(&[0xA3, 0x05], &mut encoded),
+            Ok(4)
+        );
+        assert_eq!(codeword.to_le_bytes(), encoded[..2]);
+        assert_eq!(
+            Hamming1511::decode_codeword(codeword),
+            (0x5A3, BlockOutcome::Clean)
+        );
+        for bit in 0..15 {
+            assert_eq!(
+                Hamming1511::decode_codeword(codeword ^ (1 << bit)),
+                (0x5A3, BlockOutcome::Corrected { bit })
+            );
+        }
+    }
+
+    #[test]
+    fn test_hamming1511_len_overflow() {
+        for len in 0..100 {
+            assert_eq!(Hamming1511::encoded_len(len), (len * 8).div_ceil(11) * 2);
+            assert_eq!(Hamming1511::decoded_len(len), len / 2 * 11 / 8);
+        }
+        // Past usize::MAX / 8 the bit count overflows but the length does not
+        let len = usize::MAX / 4;
+        let encoded = Hamming1511::checked_encoded_len(len).unwrap();
+        assert!(encoded > len && Hamming1511::decoded_len(encoded) >= len);
+        assert_eq!(Hamming1511::checked_encoded_len(usize::MAX), None);
+        assert_eq!(Hamming1511::encoded_len(usize::MAX), usize::MAX);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_hamming1511_exact_length() {
+        let h = Hamming1511;
+        // 3 bytes fill 3 blocks with 9 bits of padding
+        assert_eq!(h.decode(&h.encode(&[1, 2, 3])).unwrap().len(), 4);
+
+        for len in 0..40 {
+            let data: Vec<u8> = (0..len).map(|i| i as u8 ^ 0x5A).collect();
+            let mut encoded = h.encode_exact(&data);
+            assert_eq!(h.decode_exact(&encoded), Ok(data.clone()), "length {len}");
+
+            // The trailer is corrected like any other block
+            let last = encoded.len() - 1;
+            encoded[last] ^= 0x10;
+            assert_eq!(h.decode_exact(&encoded), Ok(data));
+        }
+
+        assert_eq!(h.decode_exact(&[]), Err(HammingError::InvalidLength));
+        // Data blocks with the trailer of an empty message
+        let mut encoded = h.encode(&[1, 2, 3]);
+        encoded.extend(h.encode_exact(b""));
+        assert_eq!(h.decode_exact(&encoded), Err(HammingError::InvalidLength));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_hamming1511_spare_bit() {
+        let h = Hamming1511;
+        let data = b"eleven byte".to_vec();
+        let mut encoded = h.encode(&data);
+        encoded[3] ^= 0x80;
+        assert_eq!(h.decode(&encoded), Ok(data.clone()));
+        assert_eq!(h.decode_strict(&encoded), Ok(data.clone()));
+
+        encoded[2] ^= 0x04;
+        assert_eq!(h.decode(&encoded), Ok(data.clone()));
+        assert_eq!(
+            h.decode_strict(&encoded),
+            Err(HammingError::DoubleErrorDetected { block: 1 })
+        );
+
+        let extended = Hamming1511::secded().encode(&data);
+        let plain = h.encode(&data);
+        assert_eq!(extended.len(), plain.len());
+        assert_eq!(extended[3] & 0x7F, plain[3]);
+    }
+
+    #[test]
+    fn test_hamming1511_slices() {
+        let h = Hamming1511;
+        let data = [0x47, 0xA3, 0x5C, 0x01, 0x99];
+        let mut encoded = [0; Hamming1511::encoded_len(5)];
+        assert_eq!(h.encode_to_slice(&data, &mut encoded), Ok(8));
+        assert_eq!(encoded, [0xB4, 0x34, 0x20, 0x39, 0xA6, 0x40, 0xE2, 0x04]);
+
+        encoded[5] ^= 0x10;
+        let mut decoded = [0; 8];
+        let len = h.decode_to_slice(&encoded, &mut decoded).unwrap();
+        assert_eq!(len, Hamming1511::decoded_len(8));
+        assert_eq!(decoded[..len], data);
+        assert_eq!(
+            h.decode_to_slice(&encoded, &mut decoded[..4]),
+            Err(HammingError::BufferTooSmall)
+        );
+
+        let stats = h.correct_in_place(&mut encoded).unwrap();
+        assert_eq!((stats.corrected_bits, stats.blocks), (1, 4));
+        assert_eq!(encoded, [0xB4, 0x34, 0x20, 0x39, 0xA6, 0x40, 0xE2, 0x04]);
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn test_hamming1511_heapless() {
+        let h = Hamming1511;
+        let data = [0x47, 0xA3, 0x5C, 0x01];
+
+        let encoded = h
+            .encode_heapless::<{ Hamming1511::encoded_len(4) }>(&data)
+            .unwrap();
+        assert_eq!(&encoded[..], &h.encode(&data)[..]);
+
+        let decoded = h.decode_heapless::<4>(&encoded).unwrap();
+        assert_eq!(&decoded[..], &data[..]);
+
+        assert_eq!(
+            h.encode_heapless::<4>(&data),
+            Err(HammingError::BufferTooSmall)
+        );
+    }
+
     #[test]
     fn test_hamming1511_block_encoding() {
         // Test specific bit pattern
         let data = 0x555; // 01010101101 (11 bits)
         let encoded = Hamming1511::encode_block(data);
-        let decoded = Hamming1511::decode_block(encoded).unwrap();
+        let decoded = Hamming1511::decode_block(encoded).0;
 
         assert_eq!(decoded, data);
     }
@@ -208,7 +642,7 @@ mod tests {
     fn test_hamming1511_all_ones() {
         let data = 0x7FF; // All 11 bits set
         let encoded = Hamming1511::encode_block(data);
-        let decoded = Hamming1511::decode_block(encoded).unwrap();
+        let decoded = Hamming1511::decode_block(encoded).0;
 
         assert_eq!(decoded, data);
     }
@@ -1,11 +1,25 @@
-use crate::{HammingCode, HammingError};
+#[cfg(feature = "alloc")]
+use crate::{BitOrder, Hamming, HammingCode};
+use crate::{BlockEvent, BlockOutcome, CorrectionStats, HammingError, telemetry};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 /// Hamming(7,4) implementation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Hamming74;
 
+#[cfg(feature = "alloc")]
+impl core::fmt::Display for Hamming74 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.describe())
+    }
+}
+
+#[cfg(feature = "alloc")]
 impl HammingCode for Hamming74 {
     fn encode(&self, data: &[u8]) -> Vec<u8> {
-        let mut encoded = Vec::new();
+        let mut encoded = Vec::with_capacity(Self::encoded_len(data.len()));
 
         for byte in data {
             // Process lower nibble
@@ -17,20 +31,53 @@ impl HammingCode for Hamming74 {
         encoded
     }
 
-    fn decode(&self, encoded: &[u8]) -> Result<Vec<u8>, HammingError> {
-        if encoded.len() % 2 != 0 {
+    fn decode_observed(
+        &self,
+        encoded: &[u8],
+        observer: &mut dyn FnMut(&BlockEvent),
+    ) -> Result<Vec<u8>, HammingError> {
+        if !encoded.len().is_multiple_of(2) {
             return Err(HammingError::InvalidLength);
         }
 
-        let mut decoded = Vec::new();
+        telemetry::instrumented(observer, |observer| {
+            let mut decoded = Vec::with_capacity(Self::decoded_len(encoded.len()));
+            Self::decode_pairs(encoded, false, observer, |byte| decoded.push(byte))?;
+            Ok(decoded)
+        })
+    }
+
+    fn encode_into(&self, data: &[u8], out: &mut Vec<u8>) {
+        out.reserve(Self::encoded_len(data.len()));
+        for byte in data {
+            out.push(Self::encode_nibble(*byte & 0x0F));
+            out.push(Self::encode_nibble(*byte >> 4));
+        }
+    }
 
-        for pair in encoded.chunks(2) {
-            let lower = Self::decode_block(pair[0])?;
-            let upper = Self::decode_block(pair[1])?;
-            decoded.push(lower | (upper << 4));
+    fn decode_into(&self, encoded: &[u8], out: &mut Vec<u8>) -> Result<(), HammingError> {
+        if !encoded.len().is_multiple_of(2) {
+            return Err(HammingError::InvalidLength);
         }
 
-        Ok(decoded)
+        let start = out.len();
+        out.reserve(Self::decoded_len(encoded.len()));
+        telemetry::instrumented(&mut |_| {}, |observer| {
+            Self::decode_pairs(encoded, false, observer, |byte| out.push(byte))
+        })
+        .inspect_err(|_| out.truncate(start))
+    }
+
+    fn encode_to_slice(&self, data: &[u8], out: &mut [u8]) -> Result<usize, HammingError> {
+        Hamming74::encode_to_slice(self, data, out)
+    }
+
+    fn decode_to_slice(&self, encoded: &[u8], out: &mut [u8]) -> Result<usize, HammingError> {
+        Hamming74::decode_to_slice(self, encoded, out)
+    }
+
+    fn correct_in_place(&self, encoded: &mut [u8]) -> Result<CorrectionStats, HammingError> {
+        Hamming74::correct_in_place(self, encoded)
     }
 
     fn block_size(&self) -> usize {
@@ -40,11 +87,293 @@ impl HammingCode for Hamming74 {
     fn data_bits(&self) -> usize {
         4
     }
+
+    fn block_stride(&self) -> usize {
+        8
+    }
 }
 
 impl Hamming74 {
-    fn encode_nibble(nibble: u8) -> u8 {
-        let d1 = (nibble >> 0) & 1;
+    /// Encoded size in bytes for `data_len` input bytes, saturating at
+    /// `usize::MAX` for lengths no buffer can hold
+    pub const fn encoded_len(data_len: usize) -> usize {
+        data_len.saturating_mul(2)
+    }
+
+    /// Encoded size in bytes for `data_len` input bytes, or `None` if it
+    /// overflows `usize`
+    pub const fn checked_encoded_len(data_len: usize) -> Option<usize> {
+        data_len.checked_mul(2)
+    }
+
+    /// Decoded size in bytes for `encoded_len` encoded bytes
+    pub const fn decoded_len(encoded_len: usize) -> usize {
+        encoded_len / 2
+    }
+
+    /// Encode into the front of `out`, returning the bytes written, or
+    /// [`HammingError::BufferTooSmall`] if they do not fit
+    pub fn encode_to_slice(&self, data: &[u8], out: &mut [u8]) -> Result<usize, HammingError> {
+        let len = Self::encoded_len(data.len());
+        let out = out.get_mut(..len).ok_or(HammingError::BufferTooSmall)?;
+        for (byte, [lower, upper]) in data.iter().zip(out.as_chunks_mut::<2>().0) {
+            *lower = Self::encode_nibble(*byte & 0x0F);
+            *upper = Self::encode_nibble(*byte >> 4);
+        }
+        Ok(len)
+    }
+
+    /// Decode into the front of `out`, returning the bytes written, or
+    /// [`HammingError::BufferTooSmall`] if they do not fit
+    pub fn decode_to_slice(&self, encoded: &[u8], out: &mut [u8]) -> Result<usize, HammingError> {
+        if !encoded.len().is_multiple_of(2) {
+            return Err(HammingError::InvalidLength);
+        }
+        let len = Self::decoded_len(encoded.len());
+        let mut slots = out
+            .get_mut(..len)
+            .ok_or(HammingError::BufferTooSmall)?
+            .iter_mut();
+
+        telemetry::instrumented(&mut |_| {}, |observer| {
+            Self::decode_pairs(encoded, false, observer, |byte| {
+                if let Some(slot) = slots.next() {
+                    *slot = byte;
+                }
+            })?;
+            Ok(len)
+        })
+    }
+
+    /// Fix every correctable error of `encoded` in place, leaving it
+    /// encoded, as `HammingCode::correct_in_place` does
+    ///
+    /// All blocks are checked before any is changed, so on an uncorrectable
+    /// block the stream is left as it was. Bit 7 of each byte is kept.
+    pub fn correct_in_place(&self, encoded: &mut [u8]) -> Result<CorrectionStats, HammingError> {
+        if !encoded.len().is_multiple_of(2) {
+            return Err(HammingError::InvalidLength);
+        }
+        telemetry::instrumented(&mut |_| {}, |observer| {
+            Self::decode_pairs(encoded, false, observer, |_| {})
+        })?;
+
+        let mut corrected_bits = 0;
+        for byte in encoded.iter_mut() {
+            if let (_, _, BlockOutcome::Corrected { bit }) = Self::decode_block(*byte) {
+                *byte ^= 1 << bit;
+                corrected_bits += 1;
+            }
+        }
+        Ok(CorrectionStats {
+            blocks: encoded.len(),
+            corrected_bits,
+        })
+    }
+
+    /// Encode `data` at compile time; `M` must be twice its length
+    ///
+    /// Used by [`hamming_encode!`](crate::hamming_encode) and
+    /// [`include_ecc!`](crate::include_ecc).
+    #[doc(hidden)]
+    pub const fn encode_array<const M: usize>(data: &[u8]) -> [u8; M] {
+        assert!(M == Self::encoded_len(data.len()));
+        let mut encoded = [0; M];
+        let (mut data, mut out) = (data, encoded.as_mut_slice());
+        while let ([byte, rest @ ..], [lower, upper, out_rest @ ..]) = (data, out) {
+            *lower = Self::encode_nibble(*byte & 0x0F);
+            *upper = Self::encode_nibble(*byte >> 4);
+            (data, out) = (rest, out_rest);
+        }
+        encoded
+    }
+
+    /// Encode into a fixed-capacity vector without allocating
+    #[cfg(feature = "heapless")]
+    pub fn encode_heapless<const N: usize>(
+        &self,
+        data: &[u8],
+    ) -> Result<heapless::Vec<u8, N>, HammingError> {
+        if Self::encoded_len(data.len()) > N {
+            return Err(HammingError::BufferTooSmall);
+        }
+
+        let mut encoded = heapless::Vec::new();
+        for byte in data {
+            // Capacity was checked above
+            let _ = encoded.push(Self::encode_nibble(*byte & 0x0F));
+            let _ = encoded.push(Self::encode_nibble(*byte >> 4));
+        }
+        Ok(encoded)
+    }
+
+    /// Decode into a fixed-capacity vector without allocating
+    #[cfg(feature = "heapless")]
+    pub fn decode_heapless<const N: usize>(
+        &self,
+        encoded: &[u8],
+    ) -> Result<heapless::Vec<u8, N>, HammingError> {
+        if !encoded.len().is_multiple_of(2) {
+            return Err(HammingError::InvalidLength);
+        }
+        if Self::decoded_len(encoded.len()) > N {
+            return Err(HammingError::BufferTooSmall);
+        }
+
+        telemetry::instrumented(&mut |_| {}, |observer| {
+            let mut decoded = heapless::Vec::new();
+            Self::decode_pairs(encoded, false, observer, |byte| {
+                let _ = decoded.push(byte);
+            })?;
+            Ok(decoded)
+        })
+    }
+
+    /// Decode, treating a set bit 7 as a bit error instead of ignoring it
+    ///
+    /// [`HammingCode::encode`] leaves bit 7 clear, so a set one is corrected
+    /// like any other flipped bit (`BlockOutcome::Corrected { bit: 7 }`); on
+    /// top of an error in the other bits, the block is rejected with
+    /// [`HammingError::DoubleErrorDetected`].
+    #[cfg(feature = "alloc")]
+    pub fn decode_strict(&self, encoded: &[u8]) -> Result<Vec<u8>, HammingError> {
+        self.decode_strict_observed(encoded, &mut |_| {})
+    }
+
+    /// [`Hamming74::decode_strict`], reporting every block to `observer`
+    #[cfg(feature = "alloc")]
+    pub fn decode_strict_observed(
+        &self,
+        encoded: &[u8],
+        observer: &mut dyn FnMut(&BlockEvent),
+    ) -> Result<Vec<u8>, HammingError> {
+        if !encoded.len().is_multiple_of(2) {
+            return Err(HammingError::InvalidLength);
+        }
+
+        telemetry::instrumented(observer, |observer| {
+            let mut decoded = Vec::with_capacity(Self::decoded_len(encoded.len()));
+            Self::decode_pairs(encoded, true, observer, |byte| decoded.push(byte))?;
+            Ok(decoded)
+        })
+    }
+
+    /// SEC-DED(8,4): the same layout with bit 7 as an overall parity bit
+    ///
+    /// Its encoding equals [`Hamming74`]'s apart from bit 7, so data can be
+    /// moved to it by re-encoding, and double errors are detected instead of
+    /// miscorrected.
+    #[cfg(feature = "alloc")]
+    pub fn secded() -> Hamming {
+        Hamming::new(4).with_extended(true)
+    }
+
+    /// Encode one nibble (the low 4 bits of `data`) as a codeword
+    pub const fn encode_codeword(data: u8) -> u8 {
+        Self::encode_nibble(data & 0x0F)
+    }
+
+    /// Decode one codeword into its nibble and what was done to it
+    ///
+    /// A pure function of `codeword`: a fixed amount of work with no loops,
+    /// allocation, locks, shared state or telemetry, and built without the
+    /// `std` and `alloc` features, so `no_std` firmware can call it from an
+    /// interrupt handler, e.g. to decode one codeword per control tick. Bit
+    /// 7 is ignored, as by [`Hamming74::decode_to_slice`].
+    #[inline]
+    pub fn decode_codeword(codeword: u8) -> (u8, BlockOutcome) {
+        let (data, _, outcome) = Self::decode_block(codeword);
+        (data, outcome)
+    }
+
+    /// Hamming(7,4) in the textbook convention, for exchanging codewords
+    /// with decoders that follow it
+    ///
+    /// Codewords are `p1 p2 d1 p3 d2 d3 d4`, and data and codewords are
+    /// transmitted MSB first, so each data byte is two nibbles `d1 d2 d3 d4`
+    /// from the top and the 7-bit codewords are packed left to right without
+    /// a spare bit. [`Hamming74`] itself keeps one codeword per byte, LSB
+    /// first.
+    ///
+    /// | data | codeword | data | codeword |
+    /// |------|----------|------|----------|
+    /// | 0000 | 0000000  | 1000 | 1110000  |
+    /// | 0001 | 1101001  | 1001 | 0011001  |
+    /// | 0010 | 0101010  | 1010 | 1011010  |
+    /// | 0011 | 1000011  | 1011 | 0110011  |
+    /// | 0100 | 1001100  | 1100 | 0111100  |
+    /// | 0101 | 0100101  | 1101 | 1010101  |
+    /// | 0110 | 1100110  | 1110 | 0010110  |
+    /// | 0111 | 0001111  | 1111 | 1111111  |
+    ///
+    /// E.g. the byte `0xB1` (1011 0001) encodes to 0110011 1101001, sent as
+    /// the bytes `0x67 0xA4`.
+    #[cfg(feature = "alloc")]
+    pub fn textbook() -> Hamming {
+        Hamming::new(4).with_bit_order(BitOrder::MsbFirst)
+    }
+
+    // Decode pairs of blocks into bytes, reporting every block
+    fn decode_pairs(
+        encoded: &[u8],
+        strict: bool,
+        observer: &mut dyn FnMut(&BlockEvent),
+        mut emit: impl FnMut(u8),
+    ) -> Result<(), HammingError> {
+        let (pairs, _) = encoded.as_chunks::<2>();
+        for (i, &[lower, upper]) in pairs.iter().enumerate() {
+            let lower = Self::decode_reported(2 * i, lower, strict, observer)?;
+            let upper = Self::decode_reported(2 * i + 1, upper, strict, observer)?;
+            emit(lower | (upper << 4));
+        }
+        Ok(())
+    }
+
+    fn decode_reported(
+        index: usize,
+        block: u8,
+        strict: bool,
+        observer: &mut dyn FnMut(&BlockEvent),
+    ) -> Result<u8, HammingError> {
+        let (data, syndrome, mut outcome) = Self::decode_block(block);
+        // In strict mode a set spare bit is an error of its own
+        let spare = strict && block & 0x80 != 0;
+        if spare {
+            outcome = match outcome {
+                BlockOutcome::Clean => BlockOutcome::Corrected { bit: 7 },
+                _ => BlockOutcome::Uncorrectable,
+            };
+        }
+        observer(&BlockEvent {
+            block: index,
+            bit_offset: index * 8,
+            syndrome: syndrome as usize,
+            outcome,
+        });
+
+        match outcome {
+            BlockOutcome::Uncorrectable if spare => {
+                Err(HammingError::DoubleErrorDetected { block: index })
+            }
+            BlockOutcome::Uncorrectable => Err(HammingError::UncorrectableErrors),
+            _ => Ok(data),
+        }
+    }
+
+    // The corrected byte of a pair of blocks and how many bits needed
+    // correcting; an uncorrectable block decodes to zero
+    pub(crate) fn decode_pair([lower, upper]: [u8; 2]) -> (u8, usize) {
+        let (lower, _, lower_outcome) = Self::decode_block(lower);
+        let (upper, _, upper_outcome) = Self::decode_block(upper);
+        let corrected = [lower_outcome, upper_outcome]
+            .iter()
+            .filter(|outcome| matches!(outcome, BlockOutcome::Corrected { .. }))
+            .count();
+        (lower | (upper << 4), corrected)
+    }
+
+    pub(crate) const fn encode_nibble(nibble: u8) -> u8 {
+        let d1 = nibble & 1;
         let d2 = (nibble >> 1) & 1;
         let d3 = (nibble >> 2) & 1;
         let d4 = (nibble >> 3) & 1;
@@ -55,14 +384,15 @@ impl Hamming74 {
         let p3 = d2 ^ d3 ^ d4;
 
         // Layout: p1 p2 d1 p3 d2 d3 d4
-        (p1 << 0) | (p2 << 1) | (d1 << 2) | (p3 << 3) | (d2 << 4) | (d3 << 5) | (d4 << 6)
+        p1 | (p2 << 1) | (d1 << 2) | (p3 << 3) | (d2 << 4) | (d3 << 5) | (d4 << 6)
     }
 
-    fn decode_block(block: u8) -> Result<u8, HammingError> {
+    // Returns the data nibble, the syndrome and what was done to the block
+    pub(crate) fn decode_block(block: u8) -> (u8, u8, BlockOutcome) {
         let block = block & 0x7F; // Only use lower 7 bits
 
         // Calculate syndrome
-        let s1 = ((block >> 0) & 1) ^ ((block >> 2) & 1) ^ ((block >> 4) & 1) ^ ((block >> 6) & 1);
+        let s1 = (block & 1) ^ ((block >> 2) & 1) ^ ((block >> 4) & 1) ^ ((block >> 6) & 1);
         let s2 = ((block >> 1) & 1) ^ ((block >> 2) & 1) ^ ((block >> 5) & 1) ^ ((block >> 6) & 1);
         let s3 = ((block >> 3) & 1) ^ ((block >> 4) & 1) ^ ((block >> 5) & 1) ^ ((block >> 6) & 1);
 
@@ -70,12 +400,16 @@ impl Hamming74 {
 
         // Correct single bit error if needed
         let mut corrected = block;
+        let mut outcome = BlockOutcome::Clean;
         if syndrome != 0 {
             let error_pos = syndrome - 1;
             if error_pos < 7 {
                 corrected ^= 1 << error_pos;
+                outcome = BlockOutcome::Corrected {
+                    bit: error_pos as usize,
+                };
             } else {
-                return Err(HammingError::UncorrectableErrors);
+                return (0, syndrome, BlockOutcome::Uncorrectable);
             }
         }
 
@@ -85,16 +419,18 @@ impl Hamming74 {
         let d3 = (corrected >> 5) & 1;
         let d4 = (corrected >> 6) & 1;
 
-        Ok(d1 | (d2 << 1) | (d3 << 2) | (d4 << 3))
+        (d1 | (d2 << 1) | (d3 << 2) | (d4 << 3), syndrome, outcome)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::hamming74::Hamming74;
+    #[cfg(feature = "alloc")]
+    use alloc::vec;
 
     #[test]
+    #[cfg(feature = "alloc")]
     fn test_hamming74_encode_decode() {
         let h74 = Hamming74;
         let data = vec![0x47, 0xA3]; // Example data
@@ -106,6 +442,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "alloc")]
     fn test_hamming74_single_bit_error() {
         let h74 = Hamming74;
         let data = vec![0x47];
@@ -117,4 +454,130 @@ mod tests {
         let decoded = h74.decode(&encoded).unwrap();
         assert_eq!(data, decoded);
     }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_hamming74_spare_bit() {
+        let h74 = Hamming74;
+        let mut encoded = h74.encode(&[0x47, 0xA3]);
+        encoded[1] ^= 0x80;
+        assert_eq!(h74.decode(&encoded), Ok(vec![0x47, 0xA3]));
+
+        let mut outcomes = Vec::new();
+        let decoded = h74.decode_strict_observed(&encoded, &mut |event| {
+            outcomes.push(event.outcome);
+        });
+        assert_eq!(decoded, Ok(vec![0x47, 0xA3]));
+        assert_eq!(outcomes[1], BlockOutcome::Corrected { bit: 7 });
+
+        encoded[1] ^= 0x01;
+        assert_eq!(h74.decode(&encoded), Ok(vec![0x47, 0xA3]));
+        assert_eq!(
+            h74.decode_strict(&encoded),
+            Err(HammingError::DoubleErrorDetected { block: 1 })
+        );
+
+        // The SEC-DED variant only differs in bit 7
+        let data: Vec<u8> = (0..=255).collect();
+        let extended = Hamming74::secded().encode(&data);
+        for (plain, extended) in h74.encode(&data).iter().zip(&extended) {
+            assert_eq!(*plain, extended & 0x7F);
+        }
+    }
+
+    #[test]
+    fn test_hamming74_len_overflow() {
+        assert_eq!(
+            Hamming74::checked_encoded_len(usize::MAX / 2),
+            Some(usize::MAX - 1)
+        );
+        assert_eq!(Hamming74::checked_encoded_len(usize::MAX), None);
+        assert_eq!(Hamming74::encoded_len(usize::MAX), usize::MAX);
+    }
+
+    #[test]
+    fn test_hamming74_codeword() {
+        for data in 0..16 {
+            let codeword = Hamming74::encode_codeword(data);
+            let mut encoded = [0; 2];
+            assert_eq!(Hamming74.encode_to_slice(&[data], &mut encoded), Ok(2));
+            assert_eq!(codeword, encoded[0]);
+            assert_eq!(
+                Hamming74::decode_codeword(codeword),
+                (data, BlockOutcome::Clean)
+            );
+            assert_eq!(
+                Hamming74::decode_codeword(codeword ^ 0x20),
+                (data, BlockOutcome::Corrected { bit: 5 })
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_hamming74_textbook_vectors() {
+        let codewords = [
+            0b0000000, 0b1101001, 0b0101010, 0b1000011, 0b1001100, 0b0100101, 0b1100110, 0b0001111,
+            0b1110000, 0b0011001, 0b1011010, 0b0110011, 0b0111100, 0b1010101, 0b0010110, 0b1111111,
+        ];
+        let h = Hamming74::textbook();
+        for (nibble, codeword) in codewords.into_iter().enumerate() {
+            // The nibble followed by 0000, whose codeword is all zeros
+            let encoded = h.encode(&[(nibble as u8) << 4]);
+            assert_eq!(encoded, vec![codeword << 1, 0], "data {nibble:04b}");
+            assert_eq!(h.decode(&encoded).unwrap(), vec![(nibble as u8) << 4]);
+        }
+        assert_eq!(h.encode(&[0xB1]), vec![0x67, 0xA4]);
+    }
+
+    #[test]
+    fn test_hamming74_slices() {
+        let data = [0x47, 0xA3, 0x0F];
+        let mut encoded = [0xFF; 8];
+        assert_eq!(Hamming74.encode_to_slice(&data, &mut encoded), Ok(6));
+        assert_eq!(encoded[..6], [0x34, 0x2A, 0x1E, 0x52, 0x7F, 0x00]);
+        assert_eq!(encoded[6..], [0xFF; 2]);
+
+        encoded[1] ^= 0x04;
+        let mut decoded = [0; 3];
+        assert_eq!(
+            Hamming74.decode_to_slice(&encoded[..6], &mut decoded),
+            Ok(3)
+        );
+        assert_eq!(decoded, data);
+        assert_eq!(
+            Hamming74.encode_to_slice(&data, &mut encoded[..5]),
+            Err(HammingError::BufferTooSmall)
+        );
+        assert_eq!(
+            Hamming74.decode_to_slice(&encoded[..6], &mut decoded[..2]),
+            Err(HammingError::BufferTooSmall)
+        );
+        assert_eq!(
+            Hamming74.decode_to_slice(&encoded[..5], &mut decoded),
+            Err(HammingError::InvalidLength)
+        );
+
+        let stats = Hamming74.correct_in_place(&mut encoded[..6]).unwrap();
+        assert_eq!((stats.corrected_bits, stats.blocks), (1, 6));
+        assert_eq!(encoded[..6], [0x34, 0x2A, 0x1E, 0x52, 0x7F, 0x00]);
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn test_hamming74_heapless() {
+        let h74 = Hamming74;
+        let data = [0x47, 0xA3];
+
+        let encoded = h74.encode_heapless::<4>(&data).unwrap();
+        assert_eq!(&encoded[..], &h74.encode(&data)[..]);
+
+        let decoded = h74.decode_heapless::<2>(&encoded).unwrap();
+        assert_eq!(&decoded[..], &data[..]);
+
+        assert_eq!(
+            h74.decode_heapless::<1>(&encoded),
+            Err(HammingError::BufferTooSmall)
+        );
+    }
 }
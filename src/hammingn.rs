@@ -0,0 +1,379 @@
+#[cfg(feature = "alloc")]
+use crate::HammingCode;
+#[cfg(feature = "alloc")]
+use crate::bits::VecSink;
+use crate::bits::{self, BitReader, BitWriter, SliceSink};
+use crate::{BlockEvent, BlockOutcome, HammingError, telemetry};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Hamming code with `K` data bits per block (1 to 120), laid out at
+/// compile time
+///
+/// Encodes and decodes exactly like `Hamming::new(K)`, but the parity masks
+/// and the runs of data bits between the parity positions are constants of
+/// the type, so each `K` gets its own code with the loops over them
+/// unrolled, and blocks are `u128` words on the stack. A `K` outside
+/// 1..=120 fails to compile.
+///
+/// ```
+/// use hamming_rs::HammingN;
+///
+/// let code = HammingN::<26>;
+/// assert_eq!(HammingN::<26>::BLOCK_BITS, 31);
+/// let mut encoded = [0; HammingN::<26>::encoded_len(4)];
+/// code.encode_to_slice(b"data", &mut encoded)?;
+/// encoded[1] ^= 0x08;
+/// let mut decoded = [0; HammingN::<26>::decoded_len(8)];
+/// code.decode_to_slice(&encoded, &mut decoded)?;
+/// assert_eq!(decoded[..4], *b"data");
+/// # Ok::<(), hamming_rs::HammingError>(())
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HammingN<const K: usize>;
+
+#[cfg(feature = "alloc")]
+impl<const K: usize> core::fmt::Display for HammingN<K> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.describe())
+    }
+}
+
+impl<const K: usize> HammingN<K> {
+    /// Parity bits per block
+    pub const PARITY_BITS: usize = parity_bits(K);
+    /// Bits per block, data and parity
+    pub const BLOCK_BITS: usize = K + Self::PARITY_BITS;
+
+    const VALID: () = assert!(K >= 1 && K <= 120, "HammingN takes 1 to 120 data bits");
+    // Codeword bits covered by each parity bit
+    const CHECKS: [u128; 7] = checks(Self::BLOCK_BITS);
+    // Runs of data bits: first data bit, codeword bit, length
+    const RUNS: [(u32, u32, u32); 7] = runs(K);
+
+    /// Encoded size in bytes for `data_len` input bytes
+    pub const fn encoded_len(data_len: usize) -> usize {
+        ((data_len * 8).div_ceil(K) * Self::BLOCK_BITS).div_ceil(8)
+    }
+
+    /// Decoded size in bytes for `encoded_len` encoded bytes
+    pub const fn decoded_len(encoded_len: usize) -> usize {
+        (encoded_len * 8 / Self::BLOCK_BITS * K).div_ceil(8)
+    }
+
+    /// Encode into the front of `out`, returning the bytes written, or
+    /// [`HammingError::BufferTooSmall`] if they do not fit
+    pub fn encode_to_slice(&self, data: &[u8], out: &mut [u8]) -> Result<usize, HammingError> {
+        let len = Self::encoded_len(data.len());
+        let out = out.get_mut(..len).ok_or(HammingError::BufferTooSmall)?;
+        Ok(Self::encode_blocks(data, SliceSink::new(out)).len())
+    }
+
+    /// Decode into the front of `out`, returning the bytes written, or
+    /// [`HammingError::BufferTooSmall`] if they do not fit
+    pub fn decode_to_slice(&self, encoded: &[u8], out: &mut [u8]) -> Result<usize, HammingError> {
+        Self::check_encoded_len(encoded)?;
+        let len = Self::decoded_len(encoded.len());
+        let out = out.get_mut(..len).ok_or(HammingError::BufferTooSmall)?;
+
+        telemetry::instrumented(&mut |_| {}, |observer| {
+            Ok(Self::decode_blocks(encoded, observer, SliceSink::new(out))?.len())
+        })
+    }
+
+    /// Encode the low `K` bits of `data` as a codeword
+    pub const fn encode_codeword(data: u128) -> u128 {
+        let () = Self::VALID;
+        let mut word = 0;
+        let mut runs = Self::RUNS.as_slice();
+        while let [(data_bit, position, len), rest @ ..] = runs {
+            word |= (data >> *data_bit & mask(*len)) << *position;
+            runs = rest;
+        }
+
+        // Parity bit `j` sits at position 2^j, covered by no other check
+        let mut checks = Self::CHECKS.as_slice();
+        let mut j = 0;
+        while let ([check, rest @ ..], true) = (checks, j < Self::PARITY_BITS) {
+            word |= ((word & *check).count_ones() as u128 & 1) << ((1 << j) - 1);
+            checks = rest;
+            j += 1;
+        }
+        word
+    }
+
+    /// Decode one codeword into its `K` data bits and what was done to it;
+    /// the data of an uncorrectable codeword is as received
+    pub fn decode_codeword(codeword: u128) -> (u128, BlockOutcome) {
+        let (data, _, outcome) = Self::decode_block(codeword);
+        (data, outcome)
+    }
+
+    // Pack the data into `K`-bit groups and append one codeword per group to
+    // `out`; the last group is padded with zero bits
+    fn encode_blocks<B: Extend<u8>>(data: &[u8], out: B) -> B {
+        let mut reader = BitReader::new(data);
+        let mut writer = BitWriter::with_sink(out);
+        for _ in 0..(data.len() * 8).div_ceil(K) {
+            let word = Self::encode_codeword(bits::read_word(&mut reader, K as u32));
+            bits::write_word(&mut writer, word, Self::BLOCK_BITS as u32);
+        }
+        writer.finish()
+    }
+
+    // A stream holds at least one whole block, or is empty
+    fn check_encoded_len(encoded: &[u8]) -> Result<(), HammingError> {
+        let total_bits = encoded
+            .len()
+            .checked_mul(8)
+            .ok_or(HammingError::InvalidLength)?;
+        if total_bits > 0 && total_bits < Self::BLOCK_BITS {
+            return Err(HammingError::InvalidLength);
+        }
+        Ok(())
+    }
+
+    // Decode the whole blocks of `encoded`, reporting each one, and append
+    // their data bits to `out`
+    fn decode_blocks<B: Extend<u8>>(
+        encoded: &[u8],
+        observer: &mut dyn FnMut(&BlockEvent),
+        out: B,
+    ) -> Result<B, HammingError> {
+        let mut reader = BitReader::new(encoded);
+        let mut writer = BitWriter::with_sink(out);
+        for block in 0..encoded.len() * 8 / Self::BLOCK_BITS {
+            let word = bits::read_word(&mut reader, Self::BLOCK_BITS as u32);
+            let (data, syndrome, outcome) = Self::decode_block(word);
+            observer(&BlockEvent {
+                block,
+                bit_offset: block * Self::BLOCK_BITS,
+                syndrome,
+                outcome,
+            });
+            if outcome == BlockOutcome::Uncorrectable {
+                return Err(HammingError::UncorrectableErrors);
+            }
+            bits::write_word(&mut writer, data, K as u32);
+        }
+        Ok(writer.finish())
+    }
+
+    // Returns the data bits, the syndrome and what was done to the block
+    fn decode_block(mut block: u128) -> (u128, usize, BlockOutcome) {
+        let () = Self::VALID;
+        let syndrome = Self::CHECKS
+            .iter()
+            .take(Self::PARITY_BITS)
+            .enumerate()
+            .fold(0, |syndrome, (j, check)| {
+                syndrome | ((block & check).count_ones() as usize & 1) << j
+            });
+
+        let outcome = match syndrome {
+            0 => BlockOutcome::Clean,
+            // Codes that are not perfect have syndromes past the block
+            s if s > Self::BLOCK_BITS => BlockOutcome::Uncorrectable,
+            s => {
+                block ^= 1 << (s - 1);
+                BlockOutcome::Corrected { bit: s - 1 }
+            }
+        };
+
+        let data = Self::RUNS
+            .iter()
+            .fold(0, |data, &(data_bit, position, len)| {
+                data | (block >> position & mask(len)) << data_bit
+            });
+        (data, syndrome, outcome)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const K: usize> HammingCode for HammingN<K> {
+    fn encode(&self, data: &[u8]) -> Vec<u8> {
+        let encoded = Vec::with_capacity(Self::encoded_len(data.len()));
+        Self::encode_blocks(data, encoded)
+    }
+
+    fn decode_observed(
+        &self,
+        encoded: &[u8],
+        observer: &mut dyn FnMut(&BlockEvent),
+    ) -> Result<Vec<u8>, HammingError> {
+        Self::check_encoded_len(encoded)?;
+        telemetry::instrumented(observer, |observer| {
+            let decoded = Vec::with_capacity(Self::decoded_len(encoded.len()));
+            Self::decode_blocks(encoded, observer, decoded)
+        })
+    }
+
+    fn encode_into(&self, data: &[u8], out: &mut Vec<u8>) {
+        out.reserve(Self::encoded_len(data.len()));
+        Self::encode_blocks(data, VecSink(out));
+    }
+
+    fn decode_into(&self, encoded: &[u8], out: &mut Vec<u8>) -> Result<(), HammingError> {
+        Self::check_encoded_len(encoded)?;
+        let start = out.len();
+        out.reserve(Self::decoded_len(encoded.len()));
+        telemetry::instrumented(&mut |_| {}, |observer| {
+            Self::decode_blocks(encoded, observer, VecSink(&mut *out)).map(drop)
+        })
+        .inspect_err(|_| out.truncate(start))
+    }
+
+    fn encode_to_slice(&self, data: &[u8], out: &mut [u8]) -> Result<usize, HammingError> {
+        HammingN::encode_to_slice(self, data, out)
+    }
+
+    fn decode_to_slice(&self, encoded: &[u8], out: &mut [u8]) -> Result<usize, HammingError> {
+        HammingN::decode_to_slice(self, encoded, out)
+    }
+
+    fn block_size(&self) -> usize {
+        Self::BLOCK_BITS
+    }
+
+    fn data_bits(&self) -> usize {
+        K
+    }
+}
+
+// Fewest parity bits `r` with 2^r >= k + r + 1
+const fn parity_bits(k: usize) -> usize {
+    let mut r = 0;
+    while (1 << r) < k + r + 1 {
+        r += 1;
+    }
+    r
+}
+
+// Bit `b` of a codeword is position `b + 1`; check `j` covers the positions
+// with bit `j` set
+const fn checks(block_bits: usize) -> [u128; 7] {
+    let mut checks = [0; 7];
+    let mut bit = 0;
+    while bit < block_bits && bit < 128 {
+        let mut j = 0;
+        while j < 7 {
+            if let Some((_, [check, ..])) = checks.split_at_mut_checked(j)
+                && (bit + 1) & (1 << j) != 0
+            {
+                *check |= 1 << bit;
+            }
+            j += 1;
+        }
+        bit += 1;
+    }
+    checks
+}
+
+// Data bits fill positions 2^j + 1 ..= 2^(j+1) - 1, a run of 2^j - 1 bits
+// starting at codeword bit 2^j, until `k` are placed
+const fn runs(k: usize) -> [(u32, u32, u32); 7] {
+    let mut runs = [(0, 0, 0); 7];
+    let mut data_bit = 0;
+    let mut j = 1;
+    while data_bit < k && j <= 7 {
+        let room = (1 << j) - 1;
+        let len = if k - data_bit < room {
+            k - data_bit
+        } else {
+            room
+        };
+        if let Some((_, [run, ..])) = runs.split_at_mut_checked(j - 1) {
+            *run = (data_bit as u32, 1 << j, len as u32);
+        }
+        data_bit += len;
+        j += 1;
+    }
+    runs
+}
+
+const fn mask(len: u32) -> u128 {
+    if len == 0 {
+        0
+    } else {
+        u128::MAX >> (128 - len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::{Hamming, flip_bit};
+
+    #[cfg(feature = "alloc")]
+    fn check_against_general<const K: usize>() {
+        let code = HammingN::<K>;
+        let general = Hamming::new(K);
+        assert_eq!(code.block_size(), general.block_size());
+
+        let data: Vec<u8> = (0..97u8).map(|i| i.wrapping_mul(59) ^ 0xA7).collect();
+        let mut encoded = code.encode(&data);
+        assert_eq!(encoded, general.encode(&data), "K = {K}");
+        for block in 0..encoded.len() * 8 / code.block_size() {
+            let bit = block * code.block_size() + block % code.block_size();
+            flip_bit(&mut encoded, bit / 8, (bit % 8) as u32);
+        }
+        assert_eq!(code.decode(&encoded), general.decode(&encoded), "K = {K}");
+        assert_eq!(code.decode(&encoded).unwrap()[..97], data);
+
+        let mut out = [0; 512];
+        let len = code.decode_to_slice(&encoded, &mut out).unwrap();
+        assert_eq!(len, HammingN::<K>::decoded_len(encoded.len()));
+        assert_eq!(out[..len], code.decode(&encoded).unwrap()[..], "K = {K}");
+        let len = code.encode_to_slice(&data, &mut out).unwrap();
+        assert_eq!(len, HammingN::<K>::encoded_len(97));
+        assert_eq!(out[..len], general.encode(&data)[..], "K = {K}");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_hamming_n_matches_general() {
+        const CODEWORD: u128 = HammingN::<11>::encode_codeword(0x5A5);
+        assert_eq!(CODEWORD, Hamming::new(11).encode_word(0x5A5));
+        assert_eq!(
+            HammingN::<26>.decode(&[0; 3]),
+            Err(HammingError::InvalidLength)
+        );
+        check_against_general::<1>();
+        check_against_general::<4>();
+        check_against_general::<11>();
+        check_against_general::<26>();
+        check_against_general::<57>();
+        check_against_general::<64>();
+        check_against_general::<100>();
+        check_against_general::<120>();
+    }
+
+    #[test]
+    fn test_hamming_n_codewords() {
+        const CODEWORD: u128 = HammingN::<11>::encode_codeword(0x5A5);
+        assert_eq!(
+            HammingN::<11>::decode_codeword(CODEWORD),
+            (0x5A5, BlockOutcome::Clean)
+        );
+        assert_eq!(HammingN::<64>::PARITY_BITS, 7);
+        assert_eq!(HammingN::<120>::BLOCK_BITS, 127);
+
+        let data = 0x0123_4567_89AB_CDEF;
+        let codeword = HammingN::<64>::encode_codeword(data);
+        for bit in 0..71 {
+            assert_eq!(
+                HammingN::<64>::decode_codeword(codeword ^ (1 << bit)),
+                (data, BlockOutcome::Corrected { bit })
+            );
+        }
+        // Hamming(71,64) is not perfect: positions 64 and 8 give syndrome
+        // 72, past the block
+        let (_, outcome) = HammingN::<64>::decode_codeword(codeword ^ (1 << 63) ^ (1 << 7));
+        assert_eq!(outcome, BlockOutcome::Uncorrectable);
+        assert_eq!(
+            HammingN::<26>.decode_to_slice(&[0; 3], &mut [0; 8]),
+            Err(HammingError::InvalidLength)
+        );
+    }
+}
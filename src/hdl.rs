@@ -0,0 +1,326 @@
+use crate::{CodeSpec, Hamming, HammingCode, HammingError};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt::Write;
+
+/// Target language for [`ParityEquations::render`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HdlLanguage {
+    Verilog,
+    Vhdl,
+}
+
+/// Parity and syndrome XOR equations of a code
+///
+/// Rendered as two combinational units, `<name>_encode` and `<name>_decode`.
+/// Vector bit `i` is codeword position `i` (bit `i` of the values in
+/// [`LookupTables`](crate::LookupTables)); the decoder also outputs the
+/// syndrome and `corrected`/`uncorrectable` status bits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParityEquations {
+    name: String,
+    /// Parity-check matrix column of every codeword position
+    columns: Vec<usize>,
+    data_bits: usize,
+    parity_bits: usize,
+}
+
+impl ParityEquations {
+    /// Equations for a codec selection, named like
+    /// [`LookupTables::new`](crate::LookupTables::new)
+    pub fn new(spec: &CodeSpec) -> Result<Self, HammingError> {
+        let (code, name) = spec.general_equivalent();
+        Self::for_code(&code, &name)
+    }
+
+    /// Equations for a configured general code
+    pub fn for_code(code: &Hamming, name: &str) -> Result<Self, HammingError> {
+        // The overall parity bit of extended codes has no column
+        if code.data_bits() == 0 || code.extended() {
+            return Err(HammingError::Unsupported);
+        }
+
+        let columns: Vec<usize> = code.columns().take(code.block_size()).collect();
+        Ok(Self {
+            name: name.to_string(),
+            data_bits: code.data_bits(),
+            parity_bits: code.block_size() - code.data_bits(),
+            columns,
+        })
+    }
+
+    /// Codeword position of each data bit
+    fn data_positions(&self) -> impl Iterator<Item = usize> + '_ {
+        self.columns
+            .iter()
+            .enumerate()
+            .filter(|(_, column)| !column.is_power_of_two())
+            .map(|(pos, _)| pos)
+    }
+
+    /// Right-hand side of each codeword bit in terms of the data bits
+    fn encode_terms(&self) -> Vec<Vec<usize>> {
+        let data_columns: Vec<usize> = self
+            .columns
+            .iter()
+            .copied()
+            .filter(|column| !column.is_power_of_two())
+            .collect();
+
+        let mut data_bit = 0;
+        self.columns
+            .iter()
+            .map(|&column| {
+                if column.is_power_of_two() {
+                    // Parity: the data bits whose column shares this bit
+                    (0..data_columns.len())
+                        .filter(|&d| data_columns.get(d).is_some_and(|c| c & column != 0))
+                        .collect()
+                } else {
+                    data_bit += 1;
+                    vec![data_bit - 1]
+                }
+            })
+            .collect()
+    }
+
+    /// Codeword bits covered by each syndrome bit
+    fn syndrome_terms(&self) -> Vec<Vec<usize>> {
+        (0..self.parity_bits)
+            .map(|bit| {
+                (0..self.columns.len())
+                    .filter(|&pos| self.columns.get(pos).is_some_and(|c| (c >> bit) & 1 == 1))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Source text for both units
+    pub fn render(&self, language: HdlLanguage) -> String {
+        let mut out = String::new();
+        let title = format!(
+            "Hamming({},{}) encoder/decoder generated by hamming-rs {}",
+            self.columns.len(),
+            self.data_bits,
+            env!("CARGO_PKG_VERSION")
+        );
+        match language {
+            HdlLanguage::Verilog => self.render_verilog(&mut out, &title),
+            HdlLanguage::Vhdl => self.render_vhdl(&mut out, &title),
+        }
+        out
+    }
+
+    fn render_verilog(&self, out: &mut String, title: &str) {
+        let (name, n, k, r) = (
+            &self.name,
+            self.columns.len(),
+            self.data_bits,
+            self.parity_bits,
+        );
+        let xor = |vector: &str, terms: &[usize]| match terms {
+            [] => "1'b0".to_string(),
+            _ => terms
+                .iter()
+                .map(|t| format!("{vector}[{t}]"))
+                .collect::<Vec<_>>()
+                .join(" ^ "),
+        };
+
+        let _ = writeln!(out, "// {title}\n");
+        let _ = writeln!(out, "module {name}_encode (");
+        let _ = writeln!(out, "    input  wire [{}:0] data,", k - 1);
+        let _ = writeln!(out, "    output wire [{}:0] codeword", n - 1);
+        let _ = writeln!(out, ");");
+        for (pos, terms) in self.encode_terms().iter().enumerate() {
+            let _ = writeln!(out, "    assign codeword[{pos}] = {};", xor("data", terms));
+        }
+        let _ = writeln!(out, "endmodule\n");
+
+        let _ = writeln!(out, "module {name}_decode (");
+        let _ = writeln!(out, "    input  wire [{}:0] codeword,", n - 1);
+        let _ = writeln!(out, "    output wire [{}:0] data,", k - 1);
+        let _ = writeln!(out, "    output wire [{}:0] syndrome,", r - 1);
+        let _ = writeln!(out, "    output wire        corrected,");
+        let _ = writeln!(out, "    output wire        uncorrectable");
+        let _ = writeln!(out, ");");
+        let _ = writeln!(out, "    wire [{}:0] flip;", n - 1);
+        let _ = writeln!(out, "    wire [{}:0] fixed;\n", n - 1);
+        for (bit, terms) in self.syndrome_terms().iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "    assign syndrome[{bit}] = {};",
+                xor("codeword", terms)
+            );
+        }
+        out.push('\n');
+        for (pos, column) in self.columns.iter().enumerate() {
+            let _ = writeln!(out, "    assign flip[{pos}] = syndrome == {r}'d{column};");
+        }
+        let _ = writeln!(out, "    assign fixed = codeword ^ flip;\n");
+        for (d, pos) in self.data_positions().enumerate() {
+            let _ = writeln!(out, "    assign data[{d}] = fixed[{pos}];");
+        }
+        let _ = writeln!(out, "    assign corrected = |flip;");
+        let _ = writeln!(out, "    assign uncorrectable = |syndrome & ~|flip;");
+        let _ = writeln!(out, "endmodule");
+    }
+
+    fn render_vhdl(&self, out: &mut String, title: &str) {
+        let (name, n, k, r) = (
+            &self.name,
+            self.columns.len(),
+            self.data_bits,
+            self.parity_bits,
+        );
+        let xor = |vector: &str, terms: &[usize]| match terms {
+            [] => "'0'".to_string(),
+            _ => terms
+                .iter()
+                .map(|t| format!("{vector}({t})"))
+                .collect::<Vec<_>>()
+                .join(" xor "),
+        };
+        let libraries = "library ieee;\nuse ieee.std_logic_1164.all;\n";
+
+        let _ = writeln!(out, "-- {title}\n");
+        let _ = writeln!(out, "{libraries}");
+        let _ = writeln!(out, "entity {name}_encode is");
+        let _ = writeln!(out, "    port (");
+        let _ = writeln!(
+            out,
+            "        data     : in  std_logic_vector({} downto 0);",
+            k - 1
+        );
+        let _ = writeln!(
+            out,
+            "        codeword : out std_logic_vector({} downto 0)",
+            n - 1
+        );
+        let _ = writeln!(out, "    );");
+        let _ = writeln!(out, "end entity;\n");
+        let _ = writeln!(out, "architecture rtl of {name}_encode is");
+        let _ = writeln!(out, "begin");
+        for (pos, terms) in self.encode_terms().iter().enumerate() {
+            let _ = writeln!(out, "    codeword({pos}) <= {};", xor("data", terms));
+        }
+        let _ = writeln!(out, "end architecture;\n");
+
+        let _ = writeln!(out, "{libraries}");
+        let _ = writeln!(out, "entity {name}_decode is");
+        let _ = writeln!(out, "    port (");
+        let _ = writeln!(
+            out,
+            "        codeword      : in  std_logic_vector({} downto 0);",
+            n - 1
+        );
+        let _ = writeln!(
+            out,
+            "        data          : out std_logic_vector({} downto 0);",
+            k - 1
+        );
+        let _ = writeln!(
+            out,
+            "        syndrome      : out std_logic_vector({} downto 0);",
+            r - 1
+        );
+        let _ = writeln!(out, "        corrected     : out std_logic;");
+        let _ = writeln!(out, "        uncorrectable : out std_logic");
+        let _ = writeln!(out, "    );");
+        let _ = writeln!(out, "end entity;\n");
+        let _ = writeln!(out, "architecture rtl of {name}_decode is");
+        let _ = writeln!(
+            out,
+            "    signal s     : std_logic_vector({} downto 0);",
+            r - 1
+        );
+        let _ = writeln!(
+            out,
+            "    signal flip  : std_logic_vector({} downto 0);",
+            n - 1
+        );
+        let _ = writeln!(
+            out,
+            "    signal fixed : std_logic_vector({} downto 0);",
+            n - 1
+        );
+        let _ = writeln!(out, "begin");
+        for (bit, terms) in self.syndrome_terms().iter().enumerate() {
+            let _ = writeln!(out, "    s({bit}) <= {};", xor("codeword", terms));
+        }
+        out.push('\n');
+        for (pos, column) in self.columns.iter().enumerate() {
+            let bits = format!("{column:0r$b}");
+            let _ = writeln!(out, "    flip({pos}) <= '1' when s = \"{bits}\" else '0';");
+        }
+        let _ = writeln!(out, "    fixed <= codeword xor flip;\n");
+        for (d, pos) in self.data_positions().enumerate() {
+            let _ = writeln!(out, "    data({d}) <= fixed({pos});");
+        }
+        let zeros = |width: usize| "0".repeat(width);
+        let _ = writeln!(out, "    syndrome <= s;");
+        let _ = writeln!(
+            out,
+            "    corrected <= '0' when flip = \"{}\" else '1';",
+            zeros(n)
+        );
+        let _ = writeln!(
+            out,
+            "    uncorrectable <= '1' when s /= \"{}\" and flip = \"{}\" else '0';",
+            zeros(r),
+            zeros(n)
+        );
+        let _ = writeln!(out, "end architecture;");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hamming74_equations() {
+        let eq = ParityEquations::new(&CodeSpec::Hamming74).unwrap();
+
+        // p1 p2 d1 p3 d2 d3 d4
+        assert_eq!(
+            eq.encode_terms(),
+            vec![
+                vec![0, 1, 3],
+                vec![0, 2, 3],
+                vec![0],
+                vec![1, 2, 3],
+                vec![1],
+                vec![2],
+                vec![3],
+            ]
+        );
+        assert_eq!(
+            eq.syndrome_terms(),
+            vec![vec![0, 2, 4, 6], vec![1, 2, 5, 6], vec![3, 4, 5, 6]]
+        );
+    }
+
+    #[test]
+    fn test_render_hdl() {
+        let eq = ParityEquations::new(&CodeSpec::Hamming74).unwrap();
+
+        let verilog = eq.render(HdlLanguage::Verilog);
+        assert!(verilog.contains("module hamming74_encode ("));
+        assert!(verilog.contains("    assign codeword[0] = data[0] ^ data[1] ^ data[3];"));
+        assert!(verilog.contains("    assign flip[6] = syndrome == 3'd7;"));
+
+        let vhdl = eq.render(HdlLanguage::Vhdl);
+        assert!(vhdl.contains("entity hamming74_decode is"));
+        assert!(
+            vhdl.contains(
+                "    s(2) <= codeword(3) xor codeword(4) xor codeword(5) xor codeword(6);"
+            )
+        );
+        assert!(vhdl.contains("    flip(6) <= '1' when s = \"111\" else '0';"));
+    }
+}
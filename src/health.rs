@@ -0,0 +1,44 @@
+use std::time::SystemTime;
+
+/// Corruption counters of a protection container since it was created
+///
+/// Returned by `health()` on [`Protected`](crate::Protected),
+/// [`EccVec`](crate::EccVec), [`EccRing`](crate::EccRing) and
+/// [`MemoryRegion`](crate::MemoryRegion). A rising correction rate is the
+/// early warning: memory that needs more and more corrections is heading
+/// for uncorrectable errors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Health {
+    /// Bit errors corrected and written back
+    pub corrections: u64,
+    /// Words or records found with more errors than the code corrects
+    pub uncorrectable: u64,
+    /// When the last correction was made
+    pub last_correction: Option<SystemTime>,
+}
+
+impl Health {
+    /// Whether no errors have been found
+    pub fn is_clean(&self) -> bool {
+        self.corrections == 0 && self.uncorrectable == 0
+    }
+
+    /// Add the counters of another container, keeping the later correction
+    /// time
+    pub fn merge(&mut self, other: &Health) {
+        self.corrections += other.corrections;
+        self.uncorrectable += other.uncorrectable;
+        self.last_correction = self.last_correction.max(other.last_correction);
+    }
+
+    pub(crate) fn corrected(&mut self, bits: u64) {
+        if bits > 0 {
+            self.corrections += bits;
+            self.last_correction = Some(SystemTime::now());
+        }
+    }
+
+    pub(crate) fn failed(&mut self) {
+        self.uncorrectable += 1;
+    }
+}
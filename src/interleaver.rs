@@ -0,0 +1,381 @@
+use crate::{BitOrder, BlockEvent, Blocks, HammingCode, HammingError, bits, chunked};
+use alloc::{format, string::String, vec, vec::Vec};
+
+/// Bit-level block interleaver
+///
+/// Bits are read out in `depth` passes, pass `r` taking every `depth`-th bit
+/// starting at bit `r`. A burst in the interleaved stream therefore lands on
+/// input bits that are `depth` apart, i.e. in different codewords when
+/// `depth` is at least the codeword size.
+pub struct Interleaver {
+    depth: usize,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct InterleaverParams {
+    depth: usize,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Interleaver {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        InterleaverParams { depth: self.depth }.serialize(serializer)
+    }
+}
+
+// Go through the constructor so a zero depth is clamped like `new`
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Interleaver {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let params = InterleaverParams::deserialize(deserializer)?;
+        Ok(Interleaver::new(params.depth))
+    }
+}
+
+impl Interleaver {
+    pub fn new(depth: usize) -> Self {
+        Self {
+            depth: depth.max(1),
+        }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Interleave the bits of `data`
+    pub fn interleave(&self, data: &[u8]) -> Vec<u8> {
+        let mut out = vec![0u8; data.len()];
+
+        for (out_bit, in_bit) in self.order(data.len() * 8).enumerate() {
+            if bits::get(data, in_bit) {
+                bits::set(&mut out, out_bit);
+            }
+        }
+
+        out
+    }
+
+    /// Undo [`Interleaver::interleave`]
+    pub fn deinterleave(&self, data: &[u8]) -> Vec<u8> {
+        let mut out = vec![0u8; data.len()];
+
+        for (in_bit, out_bit) in self.order(data.len() * 8).enumerate() {
+            if bits::get(data, in_bit) {
+                bits::set(&mut out, out_bit);
+            }
+        }
+
+        out
+    }
+
+    // Source bit index for each position of the interleaved stream
+    fn order(&self, total_bits: usize) -> impl Iterator<Item = usize> {
+        let depth = self.depth;
+        (0..depth).flat_map(move |r| (r..total_bits).step_by(depth))
+    }
+}
+
+/// A code whose codewords are bit-interleaved in groups of `ways`
+///
+/// The encoded stream holds bit 0 of each codeword in the group, then bit 1
+/// of each, and so on, so physically adjacent double-bit upsets, common in
+/// DRAM and flash, land in different codewords and stay correctable. Unlike
+/// [`Interleaver`], this works within a few codewords rather than across the
+/// stream. A final group with fewer codewords is interleaved as far as it
+/// goes. Block events report the bit offsets of the deinterleaved stream.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Interleaved<C> {
+    code: C,
+    ways: usize,
+}
+
+impl<C: HammingCode> Interleaved<C> {
+    /// `code` with groups of `ways` codewords interleaved; 2 separates any
+    /// two adjacent bits
+    pub fn new(code: C, ways: usize) -> Self {
+        Self {
+            code,
+            ways: ways.max(1),
+        }
+    }
+
+    pub fn ways(&self) -> usize {
+        self.ways
+    }
+
+    pub fn code(&self) -> &C {
+        &self.code
+    }
+
+    // Move the bits of whole groups between codeword order and interleaved
+    // order in place, one group at a time; trailing padding bits stay where
+    // they are
+    fn permute(&self, encoded: &mut [u8], interleave: bool) {
+        let stride = self.code.block_stride().max(1);
+        let blocks = encoded.len() * 8 / stride;
+        let mut group_bits = Vec::with_capacity(self.ways * stride);
+
+        for group in (0..blocks).step_by(self.ways) {
+            let ways = self.ways.min(blocks - group);
+            let base = group * stride;
+            let position = |word: usize, bit: usize| {
+                let (plain, mixed) = (word * stride + bit, bit * ways + word);
+                if interleave {
+                    (plain, mixed)
+                } else {
+                    (mixed, plain)
+                }
+            };
+
+            group_bits.clear();
+            group_bits.extend((0..ways * stride).map(|i| bits::get(encoded, base + i)));
+            for word in 0..ways {
+                for bit in 0..stride {
+                    let (from, to) = position(word, bit);
+                    let value = group_bits.get(from).is_some_and(|&value| value);
+                    bits::assign(encoded, base + to, value);
+                }
+            }
+        }
+    }
+}
+
+impl<C: HammingCode> core::fmt::Display for Interleaved<C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.describe())
+    }
+}
+
+impl<C: HammingCode> HammingCode for Interleaved<C> {
+    fn encode(&self, data: &[u8]) -> Vec<u8> {
+        let mut encoded = self.code.encode(data);
+        self.permute(&mut encoded, true);
+        encoded
+    }
+
+    fn try_encode(&self, data: &[u8]) -> Result<Vec<u8>, HammingError> {
+        let mut encoded = self.code.try_encode(data)?;
+        self.permute(&mut encoded, true);
+        Ok(encoded)
+    }
+
+    fn decode_observed(
+        &self,
+        encoded: &[u8],
+        observer: &mut dyn FnMut(&BlockEvent),
+    ) -> Result<Vec<u8>, HammingError> {
+        // Piece by piece, so only one piece is copied at a time; the last
+        // one takes the remainder, so it ends the stream as a whole would
+        let piece = chunked::encoded_piece_len(self).max(1);
+        let stride = self.code.block_stride().max(1);
+        let mut decoded = Vec::with_capacity(encoded.len());
+        let mut start = 0;
+        while start < encoded.len() {
+            let end = if encoded.len() - start < 2 * piece {
+                encoded.len()
+            } else {
+                start + piece
+            };
+            let mut plain = encoded.get(start..end).unwrap_or_default().to_vec();
+            self.permute(&mut plain, false);
+
+            let (block_base, bit_base) = (start * 8 / stride, start * 8);
+            let part = self
+                .code
+                .decode_observed(&plain, &mut |event| {
+                    observer(&BlockEvent {
+                        block: event.block + block_base,
+                        bit_offset: event.bit_offset + bit_base,
+                        ..*event
+                    })
+                })
+                .map_err(|err| match err {
+                    HammingError::DoubleErrorDetected { block } => {
+                        HammingError::DoubleErrorDetected {
+                            block: block + block_base,
+                        }
+                    }
+                    err => err,
+                })?;
+            decoded.extend(part);
+            start = end;
+        }
+        Ok(decoded)
+    }
+
+    fn bit_order(&self) -> BitOrder {
+        self.code.bit_order()
+    }
+
+    fn name(&self) -> String {
+        format!("{}-x{}", self.code.name(), self.ways)
+    }
+
+    fn describe(&self) -> String {
+        format!("{}, {}-way interleaved", self.code.describe(), self.ways)
+    }
+
+    /// Codewords gathered from their interleaved bits, with the offsets of
+    /// the deinterleaved stream
+    fn blocks<'a>(&self, encoded: &'a [u8]) -> Result<Blocks<'a>, HammingError> {
+        // The inner code's blocks of nothing carry its size, stride and order
+        let mut plain = encoded.to_vec();
+        self.permute(&mut plain, false);
+        Ok(self.code.blocks(&[])?.rebind(plain))
+    }
+
+    fn encoded_bit_offset(&self, encoded_len: usize, block: usize, bit: usize) -> usize {
+        // Where the inner code puts the bit, then where the group's
+        // permutation moves it
+        let stride = self.code.block_stride().max(1);
+        let plain = self.code.encoded_bit_offset(encoded_len, block, bit);
+        let blocks = encoded_len.saturating_mul(8) / stride;
+        let word = plain / stride;
+        if word >= blocks {
+            return plain;
+        }
+        let group = word / self.ways * self.ways;
+        let ways = self.ways.min(blocks - group);
+        group * stride + plain % stride * ways + (word - group)
+    }
+
+    fn block_size(&self) -> usize {
+        self.code.block_size()
+    }
+
+    fn data_bits(&self) -> usize {
+        self.code.data_bits()
+    }
+
+    fn block_stride(&self) -> usize {
+        self.code.block_stride()
+    }
+
+    fn detects_double_errors(&self) -> bool {
+        self.code.detects_double_errors()
+    }
+
+    fn supports_erasures(&self) -> bool {
+        self.code.supports_erasures()
+    }
+
+    fn supports_soft_input(&self) -> bool {
+        self.code.supports_soft_input()
+    }
+
+    /// Whole groups of the inner code's chunks
+    fn chunk_sizes(&self) -> (usize, usize) {
+        fn gcd(a: usize, b: usize) -> usize {
+            if b == 0 { a } else { gcd(b, a % b) }
+        }
+
+        let (data, encoded) = self.code.chunk_sizes();
+        let blocks = (encoded * 8 / self.code.block_stride().max(1)).max(1);
+        let chunks = self.ways / gcd(blocks, self.ways);
+        (data * chunks, encoded * chunks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::boxed::Box;
+
+    #[test]
+    fn test_interleaver_roundtrip() {
+        let il = Interleaver::new(16);
+        let data: Vec<u8> = (0..37).map(|i| (i * 73 + 5) as u8).collect();
+
+        let interleaved = il.interleave(&data);
+        assert_eq!(il.deinterleave(&interleaved), data);
+    }
+
+    #[test]
+    fn test_interleaved_corrects_adjacent_pairs() {
+        use crate::{Hamming, Hamming74, Hamming1511};
+
+        let data: Vec<u8> = (0..23).map(|i| (i * 37 + 11) as u8).collect();
+        let codes: Vec<(Box<dyn HammingCode>, usize)> = vec![
+            (Box::new(Interleaved::new(Hamming74, 2)), 2),
+            (Box::new(Interleaved::new(Hamming1511, 2)), 2),
+            (Box::new(Interleaved::new(Hamming::new(26), 3)), 3),
+        ];
+        for (code, ways) in &codes {
+            let encoded = code.encode(&data);
+            assert_eq!(code.decode(&encoded).unwrap()[..data.len()], data[..]);
+
+            // Every adjacent pair inside whole groups
+            let group_bits = ways * code.block_stride();
+            let whole = encoded.len() * 8 / group_bits * group_bits;
+            for bit in 0..whole - 1 {
+                let mut corrupted = encoded.clone();
+                corrupted[bit / 8] ^= 1 << (bit % 8);
+                corrupted[(bit + 1) / 8] ^= 1 << ((bit + 1) % 8);
+                let decoded = code.decode(&corrupted).unwrap();
+                assert_eq!(decoded[..data.len()], data[..], "bits {bit}, {}", bit + 1);
+            }
+        }
+
+        // Without interleaving the same pair miscorrects
+        let mut plain = Hamming74.encode(&data);
+        plain[0] ^= 0x03;
+        assert_ne!(Hamming74.decode(&plain).unwrap(), data);
+
+        assert_eq!(Interleaved::new(Hamming74, 2).chunk_sizes(), (1, 2));
+        assert_eq!(Interleaved::new(Hamming74, 4).chunk_sizes(), (2, 4));
+        assert_eq!(Interleaved::new(Hamming1511, 2).chunk_sizes(), (11, 16));
+    }
+
+    #[test]
+    fn test_interleaved_decodes_in_pieces() {
+        use crate::{BlockOutcome, Hamming1511};
+
+        let code = Interleaved::new(Hamming1511, 3);
+        let data: Vec<u8> = (0..300_000).map(|i| (i * 13 + i / 5) as u8).collect();
+        let mut encoded = code.encode(&data);
+        assert!(encoded.len() > 3 * chunked::encoded_piece_len(&code));
+
+        // One flip in each of several pieces; within a group of 3 blocks,
+        // interleaved bit i belongs to block i % 3
+        let group_bits = 3 * code.block_stride();
+        let flips = [5, 1_000_000, 2_500_001, encoded.len() * 8 - 100];
+        for bit in flips {
+            encoded[bit / 8] ^= 1 << (bit % 8);
+        }
+
+        let mut blocks = Vec::new();
+        let decoded = code
+            .decode_observed(&encoded, &mut |event| {
+                assert_eq!(event.bit_offset, event.block * code.block_stride());
+                if event.outcome != BlockOutcome::Clean {
+                    blocks.push(event.block);
+                }
+            })
+            .unwrap();
+        assert_eq!(decoded[..data.len()], data[..]);
+        let expected: Vec<usize> = flips
+            .iter()
+            .map(|bit| bit / group_bits * 3 + bit % group_bits % 3)
+            .collect();
+        assert_eq!(blocks, expected);
+    }
+
+    #[test]
+    fn test_interleaver_spreads_burst() {
+        let il = Interleaver::new(16);
+        let data = vec![0u8; 64];
+
+        // Corrupt 16 consecutive bits of the interleaved stream
+        let mut interleaved = il.interleave(&data);
+        interleaved[10] = 0xFF;
+        interleaved[11] = 0xFF;
+
+        // Each 16-bit slot sees at most one flipped bit
+        let restored = il.deinterleave(&interleaved);
+        for slot in restored.chunks(2) {
+            assert!(slot[0].count_ones() + slot[1].count_ones() <= 1);
+        }
+    }
+}
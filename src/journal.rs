@@ -0,0 +1,145 @@
+use crate::{BlockEvent, BlockOutcome, HammingCode, HammingError};
+use std::collections::VecDeque;
+use std::time::SystemTime;
+
+/// A correction recorded by a [`CorrectionJournal`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JournalEntry {
+    /// Number of corrections recorded before this one
+    pub sequence: u64,
+    pub time: SystemTime,
+    /// Number of [`CorrectionJournal::decode`] calls before the one that
+    /// made the correction
+    pub decode: u64,
+    /// Index of the block in the decoded stream
+    pub block: usize,
+    /// Corrected bit within the block
+    pub bit: usize,
+    pub syndrome: usize,
+}
+
+/// Bounded log of the individual corrections made while decoding, the raw
+/// evidence behind rising correction counters
+///
+/// Decode through [`CorrectionJournal::decode`], or pass
+/// [`CorrectionJournal::record`] as the observer of
+/// [`HammingCode::decode_observed`]. Once full, the oldest entries are
+/// dropped; [`CorrectionJournal::recorded`] keeps counting.
+#[derive(Debug, Clone)]
+pub struct CorrectionJournal {
+    entries: VecDeque<JournalEntry>,
+    capacity: usize,
+    recorded: u64,
+    decodes: u64,
+}
+
+impl CorrectionJournal {
+    /// Journal keeping the last `capacity` corrections
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+            recorded: 0,
+            decodes: 0,
+        }
+    }
+
+    /// Decode with `code`, recording every correction
+    pub fn decode(
+        &mut self,
+        code: &dyn HammingCode,
+        encoded: &[u8],
+    ) -> Result<Vec<u8>, HammingError> {
+        let decoded = code.decode_observed(encoded, &mut |event| self.record(event));
+        self.decodes += 1;
+        decoded
+    }
+
+    /// Record `event` if it is a correction
+    pub fn record(&mut self, event: &BlockEvent) {
+        let BlockOutcome::Corrected { bit } = event.outcome else {
+            return;
+        };
+        if self.capacity == 0 {
+            self.recorded += 1;
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(JournalEntry {
+            sequence: self.recorded,
+            time: SystemTime::now(),
+            decode: self.decodes,
+            block: event.block,
+            bit,
+            syndrome: event.syndrome,
+        });
+        self.recorded += 1;
+    }
+
+    /// The kept entries, oldest first
+    pub fn entries(&self) -> impl Iterator<Item = &JournalEntry> {
+        self.entries.iter()
+    }
+
+    /// Corrections recorded, dropped entries included
+    pub fn recorded(&self) -> u64 {
+        self.recorded
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Hamming74, Hamming1511};
+
+    #[test]
+    fn test_journal_records_corrections() {
+        let mut journal = CorrectionJournal::new(8);
+        let mut encoded = Hamming1511.encode(b"journal");
+        encoded[2] ^= 0x10;
+        encoded[7] ^= 0x01;
+        assert_eq!(
+            journal.decode(&Hamming1511, &encoded).unwrap()[..7],
+            *b"journal"
+        );
+        journal.decode(&Hamming1511, &encoded[..4]).unwrap();
+
+        let entries: Vec<_> = journal
+            .entries()
+            .map(|e| (e.sequence, e.decode, e.block, e.bit, e.syndrome))
+            .collect();
+        assert_eq!(
+            entries,
+            vec![(0, 0, 1, 4, 5), (1, 0, 3, 8, 9), (2, 1, 1, 4, 5)]
+        );
+    }
+
+    #[test]
+    fn test_journal_is_bounded() {
+        let mut journal = CorrectionJournal::new(2);
+        let mut encoded = Hamming74.encode(&[1, 2, 3]);
+        for block in &mut encoded {
+            *block ^= 0x04;
+        }
+        // Also usable as a plain observer
+        Hamming74
+            .decode_observed(&encoded, &mut |event| journal.record(event))
+            .unwrap();
+
+        assert_eq!(journal.recorded(), 6);
+        let blocks: Vec<_> = journal.entries().map(|e| e.block).collect();
+        assert_eq!(blocks, vec![4, 5]);
+        journal.clear();
+        assert_eq!(journal.entries().count(), 0);
+    }
+}
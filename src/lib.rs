@@ -1,28 +1,1443 @@
+//! Hamming error-correcting codes
+//!
+//! # `no_std`
+//!
+//! Without the default `std` feature the crate is `no_std`. The fixed-size
+//! codecs [`Hamming74`], [`Hamming1511`], [`Hamming127120`], [`Secded7264`]
+//! and [`HammingN`] need no allocator: their `const fn`
+//! `encoded_len`/`decoded_len`, `encode_to_slice`/`decode_to_slice`, the
+//! single-codeword `encode_codeword`/`decode_codeword` and the
+//! `correct_in_place` of those that have them are available without any
+//! feature, as are
+//! [`EccStatic`] and [`EccCell`]. The `alloc` feature adds the `HammingCode`
+//! trait and everything built on vectors; `std` adds I/O, files, threads
+//! and clocks.
+
+// Decode paths take untrusted input, so library code must not panic: no
+// unchecked indexing and no unwrap/expect. Tests are exempt.
+#![cfg_attr(
+    not(test),
+    deny(
+        clippy::indexing_slicing,
+        clippy::panic,
+        clippy::unwrap_used,
+        clippy::expect_used
+    )
+)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+// The derive macro names this crate by its external path
+#[cfg(all(test, feature = "derive"))]
+extern crate self as hamming_rs;
+
+pub mod bits;
+#[cfg(feature = "alloc")]
+mod blocks;
+#[cfg(feature = "bytes")]
+mod buf;
+mod cell;
+#[cfg(feature = "alloc")]
+mod channel;
+#[cfg(feature = "alloc")]
+mod checked;
+#[cfg(feature = "alloc")]
+mod chunked;
+mod codeword;
+#[cfg(feature = "std")]
+mod confidence;
+#[cfg(feature = "std")]
+mod copy;
+#[cfg(feature = "alloc")]
+mod corrections;
+#[cfg(feature = "alloc")]
+mod crosscheck;
+mod eccstatic;
+#[cfg(feature = "std")]
+mod eccvec;
+#[cfg(feature = "envelope")]
+mod envelope;
+#[cfg(feature = "alloc")]
+mod explain;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "alloc")]
+mod footprint;
+#[cfg(feature = "alloc")]
+mod framing;
+#[cfg(feature = "arbitrary")]
+mod fuzzing;
+#[cfg(feature = "alloc")]
 mod hamming;
+mod hamming127120;
 mod hamming1511;
 mod hamming74;
+mod hammingn;
+#[cfg(feature = "alloc")]
+mod hdl;
+#[cfg(feature = "std")]
+mod health;
+#[cfg(feature = "alloc")]
+mod interleaver;
+#[cfg(feature = "std")]
+mod journal;
+#[cfg(feature = "alloc")]
+mod link;
+#[cfg(feature = "alloc")]
+mod matrix;
+#[cfg(feature = "std")]
+mod memory;
+#[cfg(feature = "alloc")]
+mod padding;
+#[cfg(feature = "std")]
+mod protected;
+#[cfg(feature = "std")]
+mod reader;
+#[cfg(feature = "alloc")]
+mod record;
+#[cfg(feature = "alloc")]
+mod redundant;
+#[cfg(feature = "alloc")]
+mod registry;
+#[cfg(feature = "alloc")]
+mod resumable;
+#[cfg(feature = "std")]
+mod ring;
+#[cfg(feature = "alloc")]
+mod scrambler;
+#[cfg(feature = "std")]
+mod scrub;
+#[cfg(feature = "alloc")]
+mod secded;
+mod secded7264;
+#[cfg(feature = "std")]
+mod seekable;
+#[cfg(feature = "alloc")]
+mod selftest;
+#[cfg(feature = "std")]
+mod seu;
+#[cfg(feature = "std")]
+mod sidecar;
+#[cfg(feature = "std")]
+mod slots;
+#[cfg(feature = "alloc")]
+mod source;
+#[cfg(feature = "alloc")]
+mod spec;
+#[cfg(feature = "proptest")]
+pub mod strategies;
+#[cfg(feature = "alloc")]
+mod syndromes;
+#[cfg(feature = "alloc")]
+mod tables;
+#[cfg(feature = "std")]
+mod tagged;
+mod telemetry;
+#[cfg(feature = "vectors")]
+pub mod vectors;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "alloc")]
+mod wordcode;
+#[cfg(feature = "std")]
+mod writer;
+
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, format, string::String, vec::Vec};
 
 // Re-export
-pub use hamming::Hamming;
+pub use bits::BitOrder;
+#[cfg(feature = "alloc")]
+pub use blocks::{Blocks, RawBlock};
+#[cfg(feature = "bytes")]
+pub use buf::BufCodec;
+pub use cell::{EccCell, EccWord};
+#[cfg(feature = "alloc")]
+pub use channel::{BinarySymmetricChannel, ChannelRng, SplitMix64, flip_bit};
+#[cfg(feature = "alloc")]
+pub use checked::CheckedFrames;
+#[cfg(feature = "alloc")]
+pub use codeword::HammingWord;
+pub use codeword::{Codeword74, Codeword1511};
+#[cfg(feature = "std")]
+pub use confidence::Confidence;
+#[cfg(feature = "std")]
+pub use copy::{
+    CopyStats, decode_copy, decode_copy_terminated, encode_copy, encode_copy_terminated,
+};
+#[cfg(feature = "alloc")]
+pub use corrections::CorrectionOffsets;
+#[cfg(feature = "alloc")]
+pub use crosscheck::{CrossCheckReport, cross_check};
+pub use eccstatic::EccStatic;
+#[cfg(feature = "std")]
+pub use eccvec::EccVec;
+#[cfg(feature = "envelope")]
+pub use envelope::EccEnvelope;
+#[cfg(feature = "alloc")]
+pub use explain::BlockDiagram;
+#[cfg(feature = "alloc")]
+pub use footprint::EncodingFootprint;
+#[cfg(feature = "alloc")]
+pub use framing::Framer;
+#[cfg(feature = "arbitrary")]
+pub use fuzzing::CorruptedBuffer;
+#[cfg(feature = "alloc")]
+pub use hamming::{Hamming, Layout};
+#[cfg(feature = "derive")]
+pub use hamming_derive::HammingProtect;
 pub use hamming74::Hamming74;
 pub use hamming1511::Hamming1511;
+pub use hamming127120::Hamming127120;
+pub use hammingn::HammingN;
+#[cfg(feature = "alloc")]
+pub use hdl::{HdlLanguage, ParityEquations};
+#[cfg(feature = "std")]
+pub use health::Health;
+#[cfg(feature = "alloc")]
+pub use interleaver::{Interleaved, Interleaver};
+#[cfg(feature = "std")]
+pub use journal::{CorrectionJournal, JournalEntry};
+#[cfg(feature = "alloc")]
+pub use link::LinkProfile;
+#[cfg(feature = "alloc")]
+pub use matrix::CodeMatrices;
+#[cfg(feature = "std")]
+pub use memory::{MemoryRegion, MemoryStats};
+#[cfg(feature = "alloc")]
+pub use padding::Padding;
+#[cfg(feature = "std")]
+pub use protected::{AsBytes, Protected};
+#[cfg(feature = "std")]
+pub use reader::HammingReader;
+#[cfg(feature = "alloc")]
+pub use record::EccRecord;
+#[cfg(feature = "alloc")]
+pub use redundant::{RedundancyStats, Redundant};
+#[cfg(feature = "alloc")]
+pub use registry::CodecRegistry;
+#[cfg(feature = "alloc")]
+pub use resumable::{DecodeProgress, ResumableDecode};
+#[cfg(feature = "std")]
+pub use ring::EccRing;
+#[cfg(feature = "alloc")]
+pub use scrambler::Scrambler;
+#[cfg(feature = "std")]
+pub use scrub::{Scrub, ScrubReport, ScrubTarget, Scrubber, ScrubberHandle};
+#[cfg(feature = "alloc")]
+pub use secded::{SecdedDecode, SecdedStatus};
+pub use secded7264::Secded7264;
+#[cfg(feature = "std")]
+pub use seekable::{IndexEntry, SeekableIndex, encode_seekable};
+#[cfg(feature = "alloc")]
+pub use selftest::{SelfTestReport, self_test};
+#[cfg(feature = "std")]
+pub use seu::{SeuEnvironment, SeuReport};
+#[cfg(feature = "std")]
+pub use sidecar::{RepairReport, Sidecar, protect_file, repair_file, verify_file};
+#[cfg(feature = "std")]
+pub use slots::SlotLayout;
+#[cfg(feature = "alloc")]
+pub use source::{EncodeSource, Endian, Word, Words};
+#[cfg(feature = "alloc")]
+pub use spec::CodeSpec;
+#[cfg(feature = "alloc")]
+pub use syndromes::{SyndromeEntry, SyndromeTable};
+#[cfg(feature = "alloc")]
+pub use tables::{LookupTables, TableFormat};
+#[cfg(feature = "std")]
+pub use tagged::{decode_auto, encode_tagged};
+#[cfg(feature = "std")]
+pub use writer::HammingWriter;
 
+/// Errors of the codecs and the containers built on them
+///
+/// New variants may be added; match on [`HammingError::kind`] to handle
+/// whole categories.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
 pub enum HammingError {
     InvalidLength,
     UncorrectableErrors,
+    /// A SEC-DED code found exactly two errors in block `block`; the rest
+    /// of the stream may be fine, unlike with [`HammingError::UncorrectableErrors`]
+    DoubleErrorDetected {
+        block: usize,
+    },
+    SyncNotFound,
+    MissingTerminator,
+    Serialization,
+    BufferTooSmall,
+    Unsupported,
+    /// Every block decoded, but `corrected` bits needed correcting, more
+    /// than the [`CorrectionBudget`] allows
+    QualityExceeded {
+        corrected: usize,
+    },
+}
+
+impl core::fmt::Display for HammingError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let msg = match self {
+            HammingError::InvalidLength => "invalid encoded length",
+            HammingError::UncorrectableErrors => "uncorrectable errors",
+            HammingError::DoubleErrorDetected { block } => {
+                return write!(f, "double error detected in block {block}");
+            }
+            HammingError::SyncNotFound => "sync word not found",
+            HammingError::MissingTerminator => "missing end-of-stream terminator",
+            HammingError::Serialization => "serialization failed",
+            HammingError::BufferTooSmall => "output buffer too small",
+            HammingError::Unsupported => "unsupported code parameters",
+            HammingError::QualityExceeded { corrected } => {
+                return write!(f, "correction budget exceeded ({corrected} bits corrected)");
+            }
+        };
+        f.write_str(msg)
+    }
+}
+
+impl core::error::Error for HammingError {}
+
+/// Category of a [`HammingError`]
+///
+/// Every error variant, including ones added later, maps to one of these.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ErrorKind {
+    /// Input of the wrong length or structure, e.g. missing framing
+    InvalidInput,
+    /// Corruption beyond what the code corrects
+    Uncorrectable,
+    /// The output does not fit the buffer provided
+    BufferTooSmall,
+    /// Code parameters or format versions this crate does not handle
+    Unsupported,
+    Other,
+}
+
+impl HammingError {
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            HammingError::InvalidLength
+            | HammingError::SyncNotFound
+            | HammingError::MissingTerminator => ErrorKind::InvalidInput,
+            HammingError::UncorrectableErrors
+            | HammingError::DoubleErrorDetected { .. }
+            | HammingError::QualityExceeded { .. } => ErrorKind::Uncorrectable,
+            HammingError::BufferTooSmall => ErrorKind::BufferTooSmall,
+            HammingError::Unsupported => ErrorKind::Unsupported,
+            HammingError::Serialization => ErrorKind::Other,
+        }
+    }
+}
+
+/// What the decoder did with a single block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BlockOutcome {
+    /// Zero syndrome, the block was passed through
+    Clean,
+    /// A single-bit error at `bit` (0-based within the block) was fixed
+    Corrected { bit: usize },
+    /// The syndrome points outside the block
+    Uncorrectable,
+}
+
+/// A block seen by [`HammingCode::decode_observed`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BlockEvent {
+    /// Index of the block in the encoded stream
+    pub block: usize,
+    /// Offset of the block's first bit in the encoded stream
+    pub bit_offset: usize,
+    pub syndrome: usize,
+    pub outcome: BlockOutcome,
+}
+
+/// What a `correct_in_place` call, e.g. [`Hamming74::correct_in_place`],
+/// did to a stream
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CorrectionStats {
+    /// Codewords checked
+    pub blocks: usize,
+    /// Bits flipped back
+    pub corrected_bits: usize,
 }
 
+/// Most corrected bits a decode may need before the stream is rejected,
+/// see [`HammingCode::decode_with_budget`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CorrectionBudget {
+    /// At most this many corrected bits
+    Total(usize),
+    /// At most this many corrected bits per 1000 encoded bytes
+    PerKilobyte(usize),
+}
+
+impl CorrectionBudget {
+    /// Whether `corrected` bits fit the budget for `encoded_len` bytes
+    pub fn allows(&self, corrected: usize, encoded_len: usize) -> bool {
+        match *self {
+            CorrectionBudget::Total(max) => corrected <= max,
+            CorrectionBudget::PerKilobyte(max) => {
+                corrected.saturating_mul(1000) <= max.saturating_mul(encoded_len)
+            }
+        }
+    }
+}
+
+/// End-of-stream marker appended by [`HammingCode::encode_terminated`]
+pub const TERMINATOR: u8 = 0x80;
+
+/// A block code that encodes bytes and corrects decoding errors
+///
+/// The codecs of this crate hold only their parameters: blocks are computed
+/// on the fly, with no lookup tables, caches or interior mutability. They
+/// are all `Send + Sync` (checked at compile time below), so one instance
+/// can be shared in an `Arc` across a thread pool.
+#[cfg(feature = "alloc")]
 pub trait HammingCode {
     /// Encode data into Hamming-encoded blocks
     fn encode(&self, data: &[u8]) -> Vec<u8>;
 
+    /// Encode, or report input or parameters the codec cannot encode
+    ///
+    /// Codecs with constraints, such as fixed frame sizes, override this;
+    /// the default is [`HammingCode::encode`], which accepts any input.
+    fn try_encode(&self, data: &[u8]) -> Result<Vec<u8>, HammingError> {
+        Ok(self.encode(data))
+    }
+
+    /// Encode anything that has a byte representation, such as a `&str`
+    /// or a slice of integers, see [`EncodeSource`]
+    fn encode_from<S: EncodeSource + ?Sized>(&self, source: &S) -> Vec<u8>
+    where
+        Self: Sized,
+    {
+        self.encode(&source.source_bytes())
+    }
+
     /// Decode Hamming-encoded blocks back to data
-    fn decode(&self, encoded: &[u8]) -> Result<Vec<u8>, HammingError>;
+    fn decode(&self, encoded: &[u8]) -> Result<Vec<u8>, HammingError> {
+        self.decode_observed(encoded, &mut |_| {})
+    }
+
+    /// Decode into words of `endian` byte order, the inverse of
+    /// `encode_from(&Words::new(words, endian))`
+    ///
+    /// Padding bytes short of a whole word are dropped; padding that fills
+    /// whole words is kept, as [`HammingCode::decode`] keeps padding bytes.
+    fn decode_words<W: Word>(&self, encoded: &[u8], endian: Endian) -> Result<Vec<W>, HammingError>
+    where
+        Self: Sized,
+    {
+        Ok(source::read_words(&self.decode(encoded)?, endian))
+    }
+
+    /// Decode, calling `observer` for every block in stream order
+    ///
+    /// Decoding stops at the first uncorrectable block, after it has been
+    /// reported.
+    fn decode_observed(
+        &self,
+        encoded: &[u8],
+        observer: &mut dyn FnMut(&BlockEvent),
+    ) -> Result<Vec<u8>, HammingError>;
+
+    /// Encode, appending to `out`
+    ///
+    /// Reusing `out` across calls saves the allocation of
+    /// [`HammingCode::encode`] in codecs that encode straight into it: the
+    /// fixed-size codecs, [`HammingN`], and [`Hamming`] codes with blocks of
+    /// up to 128 bits. The default appends the result of `encode`.
+    fn encode_into(&self, data: &[u8], out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.encode(data));
+    }
+
+    /// Decode, appending to `out`, which is left as it was on error
+    ///
+    /// Allocation-free in the same codecs as [`HammingCode::encode_into`].
+    fn decode_into(&self, encoded: &[u8], out: &mut Vec<u8>) -> Result<(), HammingError> {
+        out.extend_from_slice(&self.decode(encoded)?);
+        Ok(())
+    }
+
+    /// Encode into the front of `out`, returning the bytes written, or
+    /// [`HammingError::BufferTooSmall`] if they do not fit
+    ///
+    /// The fixed-size codecs and [`HammingN`] write straight into `out`,
+    /// without allocating; their `const fn` `encoded_len` helpers size
+    /// arrays at compile time. They also have inherent `encode_to_slice` and
+    /// `decode_to_slice` methods that build without the `alloc` feature. The
+    /// default encodes into a temporary vector.
+    ///
+    /// ```
+    /// use hamming_rs::{Hamming1511, HammingCode};
+    ///
+    /// let mut out = [0; Hamming1511::encoded_len(4)];
+    /// assert_eq!(Hamming1511.encode_to_slice(b"data", &mut out), Ok(6));
+    /// ```
+    fn encode_to_slice(&self, data: &[u8], out: &mut [u8]) -> Result<usize, HammingError> {
+        let encoded = self.encode(data);
+        out.get_mut(..encoded.len())
+            .ok_or(HammingError::BufferTooSmall)?
+            .copy_from_slice(&encoded);
+        Ok(encoded.len())
+    }
+
+    /// Decode into the front of `out`, returning the bytes written, or
+    /// [`HammingError::BufferTooSmall`] if they do not fit
+    ///
+    /// Allocation-free in the same codecs as
+    /// [`HammingCode::encode_to_slice`], sized by their `decoded_len`.
+    fn decode_to_slice(&self, encoded: &[u8], out: &mut [u8]) -> Result<usize, HammingError> {
+        let decoded = self.decode(encoded)?;
+        out.get_mut(..decoded.len())
+            .ok_or(HammingError::BufferTooSmall)?
+            .copy_from_slice(&decoded);
+        Ok(decoded.len())
+    }
+
+    /// Decode, also returning the number of bits corrected, e.g. for
+    /// monitoring link quality frame by frame
+    fn decode_with_stats(&self, encoded: &[u8]) -> Result<(Vec<u8>, usize), HammingError> {
+        let mut corrected = 0;
+        let decoded = self.decode_observed(encoded, &mut |event| {
+            if let BlockOutcome::Corrected { .. } = event.outcome {
+                corrected += 1;
+            }
+        })?;
+        Ok((decoded, corrected))
+    }
+
+    /// Decode only the bytes `range` of the decoded data, from the blocks
+    /// covering them
+    ///
+    /// Decoding starts at a [`HammingCode::chunk_sizes`] boundary, so at
+    /// most one chunk before and after the range is decoded as well. A
+    /// range past the end of the data fails with
+    /// [`HammingError::InvalidLength`]. Blocks in
+    /// [`HammingError::DoubleErrorDetected`] count from the start of
+    /// `encoded`, as for a whole decode.
+    fn decode_range(
+        &self,
+        encoded: &[u8],
+        range: core::ops::Range<usize>,
+    ) -> Result<Vec<u8>, HammingError> {
+        if range.start > range.end {
+            return Err(HammingError::InvalidLength);
+        }
+        if range.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let (data_chunk, encoded_chunk) = self.chunk_sizes();
+        let (data_chunk, encoded_chunk) = (data_chunk.max(1), encoded_chunk.max(1));
+        let first = range.start / data_chunk;
+        let last = range.end.div_ceil(data_chunk);
+        let start = first.saturating_mul(encoded_chunk);
+        let end = last.saturating_mul(encoded_chunk).min(encoded.len());
+        let covering = encoded.get(start..end).ok_or(HammingError::InvalidLength)?;
+
+        // Blocks of the covering slice count from its start; report them
+        // from the start of the stream
+        let base = start.saturating_mul(8) / self.block_stride().max(1);
+        let offset = first * data_chunk;
+        let decoded = self.decode(covering).map_err(|err| match err {
+            HammingError::DoubleErrorDetected { block } => HammingError::DoubleErrorDetected {
+                block: block + base,
+            },
+            err => err,
+        })?;
+        decoded
+            .get(range.start - offset..range.end - offset)
+            .map(<[u8]>::to_vec)
+            .ok_or(HammingError::InvalidLength)
+    }
+
+    /// Decode, failing with [`HammingError::QualityExceeded`] if more bits
+    /// were corrected than `budget` allows, so a link that only works by
+    /// correcting heavily is rejected
+    fn decode_with_budget(
+        &self,
+        encoded: &[u8],
+        budget: CorrectionBudget,
+    ) -> Result<Vec<u8>, HammingError> {
+        let (decoded, corrected) = self.decode_with_stats(encoded)?;
+        if !budget.allows(corrected, encoded.len()) {
+            return Err(HammingError::QualityExceeded { corrected });
+        }
+        Ok(decoded)
+    }
+
+    /// Decode, also estimating from the corrections made how likely the
+    /// data is to still hold errors, see [`Confidence`]
+    #[cfg(feature = "std")]
+    fn decode_with_confidence(
+        &self,
+        encoded: &[u8],
+    ) -> Result<(Vec<u8>, Confidence), HammingError> {
+        let (mut blocks, mut corrected) = (0, 0);
+        let decoded = self.decode_observed(encoded, &mut |event| {
+            blocks += 1;
+            if let BlockOutcome::Corrected { .. } = event.outcome {
+                corrected += 1;
+            }
+        })?;
+        Ok((decoded, Confidence::estimate(self, blocks, corrected)))
+    }
+
+    /// Order in which data bits are taken from the input bytes and packed
+    /// into the decoded ones
+    fn bit_order(&self) -> BitOrder {
+        BitOrder::LsbFirst
+    }
+
+    /// Encode a stream of data bits, e.g. from a demodulator, without
+    /// packing them into bytes first
+    ///
+    /// Bits go into the blocks in iterator order. They are padded with zero
+    /// bits to a whole byte, so the result is the encoding of those bytes.
+    fn encode_from_bits(&self, bits: impl IntoIterator<Item = bool>) -> Vec<u8>
+    where
+        Self: Sized,
+    {
+        let mut writer = bits::BitWriter::new().with_order(self.bit_order());
+        for bit in bits {
+            writer.write_bit(bit);
+        }
+        self.encode(&writer.finish())
+    }
+
+    /// Decode to a stream of data bits, the inverse of
+    /// [`HammingCode::encode_from_bits`]; padding bits are included
+    fn decode_to_bits(&self, encoded: &[u8]) -> Result<Vec<bool>, HammingError> {
+        let decoded = self.decode(encoded)?;
+        Ok(bits::BitReader::new(&decoded)
+            .with_order(self.bit_order())
+            .collect())
+    }
+
+    /// Iterate over the codewords of `encoded` without decoding them, e.g.
+    /// to inspect or histogram them
+    ///
+    /// Fails with [`HammingError::Unsupported`] for blocks of more than 128
+    /// bits.
+    fn blocks<'a>(&self, encoded: &'a [u8]) -> Result<Blocks<'a>, HammingError> {
+        Blocks::new(encoded, self.block_size(), self.block_stride())
+    }
+
+    /// Copy of `encoded` with every correctable error fixed, still encoded,
+    /// see [`HammingCode::repair_in_place`]
+    fn repair(&self, encoded: &[u8]) -> Result<Vec<u8>, HammingError> {
+        let mut repaired = encoded.to_vec();
+        self.repair_in_place(&mut repaired)?;
+        Ok(repaired)
+    }
+
+    /// Fix every correctable error of `encoded` in place, leaving it
+    /// encoded, and return the number of bits corrected
+    ///
+    /// For relays refreshing a stream without handing out its data: the
+    /// stream is decoded a piece at a time only to locate the errors. On an
+    /// uncorrectable block the stream is left as it was. Bits outside any
+    /// block, such as the spare bits a non-strict decode ignores, are kept.
+    fn repair_in_place(&self, encoded: &mut [u8]) -> Result<usize, HammingError> {
+        Ok(self.correct_in_place(encoded)?.corrected_bits)
+    }
+
+    /// [`HammingCode::repair_in_place`], also counting the codewords
+    /// checked, e.g. for a periodic scrub of data kept encoded
+    fn correct_in_place(&self, encoded: &mut [u8]) -> Result<CorrectionStats, HammingError> {
+        let mut found = CorrectionOffsets::new(self, encoded);
+        let offsets = found.by_ref().collect::<Result<Vec<_>, _>>()?;
+        let blocks = found.blocks();
+        for &offset in &offsets {
+            if let Some(byte) = encoded.get_mut(offset / 8) {
+                *byte ^= 1 << (offset % 8);
+            }
+        }
+        Ok(CorrectionStats {
+            blocks,
+            corrected_bits: offsets.len(),
+        })
+    }
+
+    /// Lazily decode `encoded`, yielding the offset in the encoded stream of
+    /// every corrected bit, see [`CorrectionOffsets`]
+    fn correction_offsets<'a>(&'a self, encoded: &'a [u8]) -> CorrectionOffsets<'a, Self>
+    where
+        Self: Sized,
+    {
+        CorrectionOffsets::new(self, encoded)
+    }
+
+    /// Decode `encoded` over several calls of at most a given number of
+    /// blocks each, see [`ResumableDecode`]
+    fn resumable_decode<'a>(&'a self, encoded: &'a [u8]) -> ResumableDecode<'a, Self>
+    where
+        Self: Sized,
+    {
+        ResumableDecode::new(self, encoded)
+    }
+
+    /// Offset in an encoded stream of `encoded_len` bytes, counting
+    /// LSB-first within each byte, of bit `bit` of block `block`, as
+    /// reported by [`BlockOutcome::Corrected`] and [`BlockEvent::block`]
+    fn encoded_bit_offset(&self, _encoded_len: usize, block: usize, bit: usize) -> usize {
+        self.bit_order().position(
+            block
+                .saturating_mul(self.block_stride())
+                .saturating_add(bit),
+        )
+    }
+
+    /// Short identifier of the code, e.g. `hamming-15-11` or
+    /// `secded-72-64`, in the form [`CodecRegistry::codec_by_name`] takes
+    fn name(&self) -> String {
+        let kind = if self.detects_double_errors() {
+            "secded"
+        } else {
+            "hamming"
+        };
+        format!("{kind}-{}-{}", self.block_size(), self.data_bits())
+    }
+
+    /// Parameter summary for logs and error reports, e.g.
+    /// `Hamming(15,11), LSB-first, zero-pad`; the codecs' `Display` prints
+    /// it too
+    fn describe(&self) -> String {
+        let kind = if self.detects_double_errors() {
+            "SEC-DED"
+        } else {
+            "Hamming"
+        };
+        format!(
+            "{kind}({},{}), {}, zero-pad",
+            self.block_size(),
+            self.data_bits(),
+            self.bit_order()
+        )
+    }
 
     /// Get the block size in bits for this code
     fn block_size(&self) -> usize;
 
     /// Get the data bits per block
     fn data_bits(&self) -> usize;
+
+    /// Get the parity bits per block, an overall parity bit included
+    fn parity_bits(&self) -> usize {
+        self.block_size() - self.data_bits()
+    }
+
+    /// Whether two errors in a block are reported as
+    /// [`HammingError::DoubleErrorDetected`] rather than miscorrected
+    fn detects_double_errors(&self) -> bool {
+        false
+    }
+
+    /// Whether the decoder can be told which bits are erased (unreliable)
+    fn supports_erasures(&self) -> bool {
+        false
+    }
+
+    /// Whether the decoder takes per-bit reliabilities instead of bits
+    fn supports_soft_input(&self) -> bool {
+        false
+    }
+
+    /// Whether the data bits of each block sit in order in one contiguous
+    /// run, so they can be read without decoding
+    fn is_systematic(&self) -> bool {
+        false
+    }
+
+    /// Get the number of encoded-stream bits each block occupies
+    fn block_stride(&self) -> usize {
+        self.block_size()
+    }
+
+    /// Encoded size, padding and effective rate for `data_len` input
+    /// bytes, as [`HammingCode::encode`] produces them
+    fn expansion(&self, data_len: usize) -> EncodingFootprint {
+        EncodingFootprint::new(
+            data_len,
+            self.data_bits(),
+            self.block_size(),
+            self.block_stride(),
+        )
+    }
+
+    /// Smallest `(data bytes, encoded bytes)` unit that encodes to whole
+    /// blocks; input split on multiples of it can be coded piecewise
+    fn chunk_sizes(&self) -> (usize, usize) {
+        fn gcd(a: usize, b: usize) -> usize {
+            if b == 0 { a } else { gcd(b, a % b) }
+        }
+
+        let k = self.data_bits();
+        let stride = self.block_stride();
+        let data_blocks = 8 / gcd(8, k);
+        let encoded_blocks = 8 / gcd(8, stride);
+        let blocks = data_blocks * encoded_blocks / gcd(data_blocks, encoded_blocks);
+
+        (blocks * k / 8, blocks * stride / 8)
+    }
+
+    /// Encode with an explicit [`Padding`] policy, the same for every codec
+    fn encode_padded(&self, data: &[u8], padding: Padding) -> Vec<u8> {
+        padding.encode(self, data)
+    }
+
+    /// Decode a stream produced by [`HammingCode::encode_padded`] with the
+    /// same policy, removing the padding it can identify
+    fn decode_padded(&self, encoded: &[u8], padding: Padding) -> Result<Vec<u8>, HammingError> {
+        padding.decode(self, encoded)
+    }
+
+    /// Encode data followed by a [`TERMINATOR`] byte, so the decoder can tell
+    /// the payload from block padding without knowing its length
+    fn encode_terminated(&self, data: &[u8]) -> Vec<u8> {
+        let mut encoder = chunked::ChunkedEncoder::new(self, data.len() + 1);
+        encoder.push(data);
+        encoder.push(&[TERMINATOR]);
+        encoder.finish()
+    }
+
+    /// Decode a stream produced by [`HammingCode::encode_terminated`],
+    /// stripping the zero padding and the terminator
+    fn decode_terminated(&self, encoded: &[u8]) -> Result<Vec<u8>, HammingError> {
+        let mut decoded = self.decode(encoded)?;
+
+        while decoded.last() == Some(&0) {
+            decoded.pop();
+        }
+        if decoded.pop() != Some(TERMINATOR) {
+            return Err(HammingError::MissingTerminator);
+        }
+
+        Ok(decoded)
+    }
+}
+
+#[cfg(feature = "alloc")]
+// The thread-safety guarantee documented on `HammingCode`
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync + ?Sized>() {}
+    assert_send_sync::<Hamming74>();
+    assert_send_sync::<Hamming1511>();
+    assert_send_sync::<Hamming127120>();
+    assert_send_sync::<Secded7264>();
+    assert_send_sync::<Hamming>();
+    assert_send_sync::<HammingN<64>>();
+    assert_send_sync::<Interleaved<Hamming>>();
+    assert_send_sync::<CheckedFrames<Hamming>>();
+    #[cfg(feature = "std")]
+    assert_send_sync::<HammingReader<&[u8], Hamming>>();
+    #[cfg(feature = "std")]
+    assert_send_sync::<HammingWriter<Vec<u8>, Hamming>>();
+    assert_send_sync::<CodeSpec>();
+    assert_send_sync::<CodecRegistry>();
+    assert_send_sync::<Blocks<'static>>();
+    assert_send_sync::<Interleaver>();
+    assert_send_sync::<Scrambler>();
+    assert_send_sync::<Framer>();
+    assert_send_sync::<LinkProfile>();
+    assert_send_sync::<LookupTables>();
+};
+
+#[cfg(feature = "alloc")]
+impl core::fmt::Display for dyn HammingCode + '_ {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.describe())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::fmt::Display for dyn HammingCode + Send + Sync + '_ {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.describe())
+    }
+}
+
+/// Boxed codecs, e.g. from a [`CodecRegistry`], are codecs too, so the
+/// generic methods work on them
+#[cfg(feature = "alloc")]
+impl<C: HammingCode + ?Sized> HammingCode for Box<C> {
+    fn encode(&self, data: &[u8]) -> Vec<u8> {
+        (**self).encode(data)
+    }
+
+    fn try_encode(&self, data: &[u8]) -> Result<Vec<u8>, HammingError> {
+        (**self).try_encode(data)
+    }
+
+    fn decode(&self, encoded: &[u8]) -> Result<Vec<u8>, HammingError> {
+        (**self).decode(encoded)
+    }
+
+    fn decode_observed(
+        &self,
+        encoded: &[u8],
+        observer: &mut dyn FnMut(&BlockEvent),
+    ) -> Result<Vec<u8>, HammingError> {
+        (**self).decode_observed(encoded, observer)
+    }
+
+    fn encode_into(&self, data: &[u8], out: &mut Vec<u8>) {
+        (**self).encode_into(data, out)
+    }
+
+    fn decode_into(&self, encoded: &[u8], out: &mut Vec<u8>) -> Result<(), HammingError> {
+        (**self).decode_into(encoded, out)
+    }
+
+    fn encode_to_slice(&self, data: &[u8], out: &mut [u8]) -> Result<usize, HammingError> {
+        (**self).encode_to_slice(data, out)
+    }
+
+    fn decode_to_slice(&self, encoded: &[u8], out: &mut [u8]) -> Result<usize, HammingError> {
+        (**self).decode_to_slice(encoded, out)
+    }
+
+    fn decode_with_stats(&self, encoded: &[u8]) -> Result<(Vec<u8>, usize), HammingError> {
+        (**self).decode_with_stats(encoded)
+    }
+
+    fn decode_range(
+        &self,
+        encoded: &[u8],
+        range: core::ops::Range<usize>,
+    ) -> Result<Vec<u8>, HammingError> {
+        (**self).decode_range(encoded, range)
+    }
+
+    fn decode_with_budget(
+        &self,
+        encoded: &[u8],
+        budget: CorrectionBudget,
+    ) -> Result<Vec<u8>, HammingError> {
+        (**self).decode_with_budget(encoded, budget)
+    }
+
+    #[cfg(feature = "std")]
+    fn decode_with_confidence(
+        &self,
+        encoded: &[u8],
+    ) -> Result<(Vec<u8>, Confidence), HammingError> {
+        (**self).decode_with_confidence(encoded)
+    }
+
+    fn bit_order(&self) -> BitOrder {
+        (**self).bit_order()
+    }
+
+    fn decode_to_bits(&self, encoded: &[u8]) -> Result<Vec<bool>, HammingError> {
+        (**self).decode_to_bits(encoded)
+    }
+
+    fn blocks<'a>(&self, encoded: &'a [u8]) -> Result<Blocks<'a>, HammingError> {
+        (**self).blocks(encoded)
+    }
+
+    fn repair(&self, encoded: &[u8]) -> Result<Vec<u8>, HammingError> {
+        (**self).repair(encoded)
+    }
+
+    fn repair_in_place(&self, encoded: &mut [u8]) -> Result<usize, HammingError> {
+        (**self).repair_in_place(encoded)
+    }
+
+    fn correct_in_place(&self, encoded: &mut [u8]) -> Result<CorrectionStats, HammingError> {
+        (**self).correct_in_place(encoded)
+    }
+
+    fn name(&self) -> String {
+        (**self).name()
+    }
+
+    fn describe(&self) -> String {
+        (**self).describe()
+    }
+
+    fn encoded_bit_offset(&self, encoded_len: usize, block: usize, bit: usize) -> usize {
+        (**self).encoded_bit_offset(encoded_len, block, bit)
+    }
+
+    fn block_size(&self) -> usize {
+        (**self).block_size()
+    }
+
+    fn data_bits(&self) -> usize {
+        (**self).data_bits()
+    }
+
+    fn parity_bits(&self) -> usize {
+        (**self).parity_bits()
+    }
+
+    fn detects_double_errors(&self) -> bool {
+        (**self).detects_double_errors()
+    }
+
+    fn supports_erasures(&self) -> bool {
+        (**self).supports_erasures()
+    }
+
+    fn supports_soft_input(&self) -> bool {
+        (**self).supports_soft_input()
+    }
+
+    fn is_systematic(&self) -> bool {
+        (**self).is_systematic()
+    }
+
+    fn block_stride(&self) -> usize {
+        (**self).block_stride()
+    }
+
+    fn expansion(&self, data_len: usize) -> EncodingFootprint {
+        (**self).expansion(data_len)
+    }
+
+    fn chunk_sizes(&self) -> (usize, usize) {
+        (**self).chunk_sizes()
+    }
+
+    fn encode_padded(&self, data: &[u8], padding: Padding) -> Vec<u8> {
+        (**self).encode_padded(data, padding)
+    }
+
+    fn decode_padded(&self, encoded: &[u8], padding: Padding) -> Result<Vec<u8>, HammingError> {
+        (**self).decode_padded(encoded, padding)
+    }
+
+    fn encode_terminated(&self, data: &[u8]) -> Vec<u8> {
+        (**self).encode_terminated(data)
+    }
+
+    fn decode_terminated(&self, encoded: &[u8]) -> Result<Vec<u8>, HammingError> {
+        (**self).decode_terminated(encoded)
+    }
+}
+
+/// Codecs the tests run over: both fixed codes, the general code with and
+/// without the overall parity bit, and an interleaved code
+#[cfg(all(test, feature = "alloc"))]
+pub(crate) fn sample_codecs() -> Vec<Box<dyn HammingCode>> {
+    alloc::vec![
+        Box::new(Hamming74),
+        Box::new(Hamming1511),
+        Box::new(Hamming::new(26)),
+        Box::new(Hamming::new(64).with_extended(true)),
+        Box::new(Interleaved::new(Hamming1511, 3)),
+    ]
+}
+
+/// One [`CodeSpec`] string of each kind, for the tests going through specs
+#[cfg(all(test, feature = "alloc"))]
+pub(crate) const SAMPLE_SPECS: [&str; 4] = ["74", "1511", "general:26", "secded:64"];
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use alloc::{string::ToString, vec};
+
+    #[test]
+    fn test_terminated_exact_length() {
+        let codecs = sample_codecs();
+
+        for codec in &codecs {
+            for len in 0..24 {
+                let data: Vec<u8> = (0..len)
+                    .map(|i| if i % 3 == 0 { 0 } else { i as u8 })
+                    .collect();
+                let encoded = codec.encode_terminated(&data);
+                assert_eq!(codec.decode_terminated(&encoded).unwrap(), data);
+            }
+        }
+    }
+
+    #[test]
+    fn test_error_kind() {
+        assert_eq!(
+            Hamming74.decode(&[0x00]).unwrap_err().kind(),
+            ErrorKind::InvalidInput
+        );
+        assert_eq!(
+            HammingError::DoubleErrorDetected { block: 3 }.kind(),
+            ErrorKind::Uncorrectable
+        );
+        assert_eq!(HammingError::Unsupported.kind(), ErrorKind::Unsupported);
+    }
+
+    #[test]
+    fn test_try_encode() {
+        let data = b"fallible";
+        assert_eq!(Hamming74.try_encode(data), Ok(Hamming74.encode(data)));
+        let h = Hamming::new(26);
+        assert_eq!(h.try_encode(data), Ok(h.encode(data)));
+        assert_eq!(
+            Hamming::new(0).try_encode(data),
+            Err(HammingError::Unsupported)
+        );
+        assert_eq!(
+            Hamming::new(usize::MAX).try_encode(data),
+            Err(HammingError::Unsupported)
+        );
+        assert_eq!(
+            Hamming::new(Hamming::MAX_DATA_BITS + 1).try_encode(data),
+            Err(HammingError::Unsupported)
+        );
+    }
+
+    #[test]
+    fn test_capabilities() {
+        assert!(!Hamming74.detects_double_errors());
+        assert!(!Hamming1511.is_systematic());
+        let secded = Hamming::new(64).with_extended(true);
+        assert!(secded.detects_double_errors());
+        assert!(!secded.is_systematic());
+        assert!(!secded.supports_erasures() && !secded.supports_soft_input());
+
+        let matlab = Hamming::new(4).with_layout(Layout::Matlab);
+        assert!(matlab.is_systematic());
+        // The data bits follow the parity bits
+        assert_eq!(matlab.encode(&[0x0B])[0] >> 3, 0x0B);
+        assert!(!Interleaved::new(matlab, 2).is_systematic());
+        assert!(Interleaved::new(secded, 2).detects_double_errors());
+    }
+
+    #[test]
+    fn test_parity_bits() {
+        assert_eq!(Hamming74.parity_bits(), 3);
+        assert_eq!(Hamming1511.parity_bits(), 4);
+        assert_eq!(Hamming::new(64).with_extended(true).parity_bits(), 8);
+        assert_eq!(Hamming::default(), Hamming::new(4));
+    }
+
+    #[test]
+    fn test_chunk_sizes() {
+        assert_eq!(Hamming74.chunk_sizes(), (1, 2));
+        assert_eq!(Hamming1511.chunk_sizes(), (11, 16));
+        assert_eq!(Hamming::new(4).chunk_sizes(), (4, 7));
+        assert_eq!(Hamming::new(26).chunk_sizes(), (26, 31));
+
+        // Encoding chunk by chunk matches encoding in one go
+        let data: Vec<u8> = (0..52).map(|i| (i * 37) as u8).collect();
+        let h = Hamming::new(26);
+        let piecewise: Vec<u8> = data.chunks(26).flat_map(|c| h.encode(c)).collect();
+        assert_eq!(piecewise, h.encode(&data));
+    }
+
+    #[test]
+    fn test_decode_observed_events() {
+        let mut encoded = Hamming1511.encode(&[0x12, 0x34, 0x56, 0x78]);
+        encoded[2] ^= 0x10;
+
+        let mut events = Vec::new();
+        Hamming1511
+            .decode_observed(&encoded, &mut |event| events.push(*event))
+            .unwrap();
+
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].outcome, BlockOutcome::Clean);
+        assert_eq!(
+            events[1],
+            BlockEvent {
+                block: 1,
+                bit_offset: 16,
+                syndrome: 5,
+                outcome: BlockOutcome::Corrected { bit: 4 },
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_with_stats() {
+        let data = b"link quality".to_vec();
+        let codecs = sample_codecs();
+        for codec in &codecs {
+            let mut encoded = codec.encode(&data);
+            assert_eq!(codec.decode_with_stats(&encoded).unwrap().1, 0);
+            encoded[0] ^= 0x01;
+            // Far enough from the first flip to land in another block
+            let middle = encoded.len() / 2;
+            encoded[middle] ^= 0x04;
+            let (decoded, corrected) = codec.decode_with_stats(&encoded).unwrap();
+            assert_eq!(&decoded[..data.len()], &data[..]);
+            assert_eq!(corrected, 2);
+        }
+    }
+
+    #[test]
+    fn test_encode_from_bits() {
+        // 1011 goes into the textbook codeword 0110011 as written
+        let textbook = Hamming74::textbook();
+        let encoded = textbook.encode_from_bits([true, false, true, true]);
+        assert_eq!(encoded, [0x66, 0x00]);
+        assert_eq!(
+            textbook.decode_to_bits(&encoded).unwrap()[..4],
+            [true, false, true, true]
+        );
+
+        // LSB-first codecs see the bits packed bit 0 first
+        let bits: Vec<bool> = (0..29).map(|i| i % 3 == 0).collect();
+        let packed = [0x49, 0x92, 0x24, 0x09];
+        assert_eq!(
+            Hamming74.encode_from_bits(bits.clone()),
+            Hamming74.encode(&packed)
+        );
+        let encoded = Hamming1511.encode_from_bits(bits.iter().copied());
+        assert_eq!(encoded, Hamming1511.encode(&packed));
+        assert_eq!(
+            Hamming1511.decode_to_bits(&encoded).unwrap()[..29],
+            bits[..]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_shared_across_threads() {
+        let codec = std::sync::Arc::new(CodeSpec::Secded { data_bits: 64 }.build());
+        let handles: Vec<_> = (0..4u8)
+            .map(|i| {
+                let codec = std::sync::Arc::clone(&codec);
+                std::thread::spawn(move || {
+                    let data = vec![i; 100];
+                    codec.decode(&codec.encode(&data)).unwrap()[..100] == data[..]
+                })
+            })
+            .collect();
+        for handle in handles {
+            assert!(handle.join().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_decode_range() {
+        let data: Vec<u8> = (0..200).map(|i| (i * 13 + 1) as u8).collect();
+        let codecs = sample_codecs();
+        for codec in &codecs {
+            let mut encoded = codec.encode(&data);
+            // Corruption outside the range is never seen
+            let last = encoded.len() - 1;
+            encoded[last] ^= 0xFF;
+            for range in [0..1, 3..17, 50..51, 100..160, 120..120] {
+                assert_eq!(
+                    codec.decode_range(&encoded, range.clone()).unwrap(),
+                    data[range]
+                );
+            }
+            assert_eq!(
+                codec.decode_range(&codec.encode(&data), 190..400),
+                Err(HammingError::InvalidLength)
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_range_error_block() {
+        // 8 data bytes per 72-bit block; block 5 holds data bytes 40..48
+        let code = Hamming::new(64).with_extended(true);
+        let mut encoded = code.encode(&[0x5A; 64]);
+        encoded[50] ^= 0x03;
+        assert_eq!(
+            code.decode_range(&encoded, 40..48),
+            Err(HammingError::DoubleErrorDetected { block: 5 })
+        );
+        assert_eq!(code.decode_range(&encoded, 0..40), Ok(vec![0x5A; 40]));
+    }
+
+    #[test]
+    fn test_codec_names() {
+        let registry = CodecRegistry::new();
+        let codecs = sample_codecs();
+        for codec in &codecs {
+            match registry.codec_by_name(&codec.name()) {
+                Some(named) => assert_eq!(named.describe(), codec.describe()),
+                // Interleaving is not registered under its own names
+                None => assert!(codec.name().ends_with("-x3"), "{}", codec.name()),
+            }
+        }
+
+        assert_eq!(
+            Hamming1511.to_string(),
+            "Hamming(15,11), LSB-first, zero-pad"
+        );
+        assert_eq!(codecs[3].to_string(), "SEC-DED(72,64), LSB-first, zero-pad");
+        let textbook = Hamming74::textbook().with_layout(Layout::Matlab);
+        assert_eq!(
+            textbook.to_string(),
+            "Hamming(7,4), MSB-first, MATLAB layout, zero-pad"
+        );
+        let interleaved = Interleaved::new(Hamming74, 3);
+        assert_eq!(interleaved.name(), "hamming-7-4-x3");
+        assert_eq!(
+            interleaved.to_string(),
+            "Hamming(7,4), LSB-first, zero-pad, 3-way interleaved"
+        );
+    }
+
+    #[test]
+    fn test_encode_decode_into() {
+        let data: Vec<u8> = (0..100u8).map(|i| i.wrapping_mul(53) ^ 0x1F).collect();
+        let mut codecs = sample_codecs();
+        codecs.push(Box::new(Hamming127120));
+        codecs.push(Box::new(Secded7264));
+        codecs.push(Box::new(HammingN::<26>));
+        codecs.push(Box::new(Hamming::new(200)));
+        let (mut encoded, mut decoded) = (Vec::new(), Vec::new());
+        for codec in &codecs {
+            // Buffers reused across codecs, appended to
+            encoded.clear();
+            encoded.push(0xEE);
+            codec.encode_into(&data, &mut encoded);
+            assert_eq!(encoded[1..], codec.encode(&data), "{codec}");
+            encoded[5] ^= 0x10;
+
+            decoded.clear();
+            decoded.push(0xDD);
+            codec.decode_into(&encoded[1..], &mut decoded).unwrap();
+            assert_eq!(
+                decoded[1..],
+                codec.decode(&encoded[1..]).unwrap(),
+                "{codec}"
+            );
+            assert_eq!(decoded[1..101], data, "{codec}");
+
+            let mut out = [0; 512];
+            let len = codec.encode_to_slice(&data, &mut out).unwrap();
+            assert_eq!(out[..len], codec.encode(&data), "{codec}");
+            assert_eq!(
+                codec.encode_to_slice(&data, &mut out[..len - 1]),
+                Err(HammingError::BufferTooSmall)
+            );
+            let len = codec.decode_to_slice(&encoded[1..], &mut out).unwrap();
+            assert_eq!(out[..len], decoded[1..], "{codec}");
+        }
+
+        // A failed decode leaves the output as it was
+        let mut decoded = vec![1, 2, 3];
+        assert!(Hamming1511.decode_into(&[0; 3], &mut decoded).is_err());
+        let mut encoded = Hamming::new(64).with_extended(true).encode(&data);
+        encoded[30] ^= 0x01;
+        encoded[20] ^= 0x03;
+        assert!(
+            Hamming::new(64)
+                .with_extended(true)
+                .decode_into(&encoded, &mut decoded)
+                .is_err()
+        );
+        assert_eq!(decoded, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_repair() {
+        let data: Vec<u8> = (0..100u8).map(|i| i.wrapping_mul(37)).collect();
+        let codecs = sample_codecs();
+        for codec in &codecs {
+            let clean = codec.encode(&data);
+            let mut encoded = clean.clone();
+            encoded[0] ^= 0x02;
+            encoded[60] ^= 0x20;
+            assert_eq!(codec.repair(&encoded).unwrap(), clean);
+            assert_eq!(codec.repair_in_place(&mut encoded), Ok(2));
+            assert_eq!(encoded, clean);
+            assert_eq!(codec.repair_in_place(&mut encoded), Ok(0));
+
+            encoded[30] ^= 0x08;
+            let stats = codec.correct_in_place(&mut encoded).unwrap();
+            assert_eq!(stats.corrected_bits, 1);
+            assert_eq!(stats.blocks, codec.blocks(&clean).unwrap().count());
+            assert_eq!(encoded, clean);
+        }
+
+        // An uncorrectable block leaves the stream untouched
+        let code = Hamming::new(64).with_extended(true);
+        let mut encoded = code.encode(&data);
+        encoded[20] ^= 0x40;
+        encoded[0] ^= 0x03;
+        let damaged = encoded.clone();
+        assert_eq!(
+            code.repair_in_place(&mut encoded),
+            Err(HammingError::DoubleErrorDetected { block: 0 })
+        );
+        assert_eq!(encoded, damaged);
+    }
+
+    #[test]
+    fn test_decode_with_budget() {
+        let data = vec![0x5A; 500];
+        let mut encoded = Hamming74.encode(&data);
+        for i in 0..10 {
+            encoded[i * 100] ^= 0x10;
+        }
+
+        assert_eq!(
+            Hamming74.decode_with_budget(&encoded, CorrectionBudget::Total(10)),
+            Ok(data.clone())
+        );
+        assert_eq!(
+            Hamming74.decode_with_budget(&encoded, CorrectionBudget::Total(9)),
+            Err(HammingError::QualityExceeded { corrected: 10 })
+        );
+        // 10 corrections in 1000 encoded bytes
+        assert!(
+            Hamming74
+                .decode_with_budget(&encoded, CorrectionBudget::PerKilobyte(10))
+                .is_ok()
+        );
+        let err = Hamming74
+            .decode_with_budget(&encoded, CorrectionBudget::PerKilobyte(9))
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Uncorrectable);
+    }
+
+    #[test]
+    fn test_decode_garbage() {
+        let mut codecs: Vec<Box<dyn HammingCode>> =
+            vec![Box::new(Hamming74), Box::new(Hamming1511)];
+        codecs.extend((0..=64).map(|k| Box::new(Hamming::new(k)) as Box<dyn HammingCode>));
+        // Unvalidated parameters, e.g. a hand-written CodeSpec
+        for data_bits in [Hamming::MAX_DATA_BITS + 1, usize::MAX] {
+            codecs.push(Box::new(Hamming::new(data_bits)));
+            codecs.push(CodeSpec::General { data_bits }.build());
+            codecs.push(CodeSpec::Secded { data_bits }.build());
+        }
+
+        // Simple LCG so every length sees different bytes
+        let mut state = 0x2545_F491u32;
+        for len in 0..64 {
+            let garbage: Vec<u8> = (0..len)
+                .map(|_| {
+                    state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                    (state >> 24) as u8
+                })
+                .collect();
+
+            for codec in &codecs {
+                let _ = codec.decode(&garbage);
+                let _ = codec.decode_terminated(&garbage);
+            }
+            let _ = LinkProfile::radio().receive(&garbage);
+        }
+    }
+
+    #[test]
+    fn test_missing_terminator() {
+        let encoded = Hamming1511.encode(&[0x12, 0x34, 0x00]);
+        assert_eq!(
+            Hamming1511.decode_terminated(&encoded),
+            Err(HammingError::MissingTerminator)
+        );
+    }
 }
@@ -0,0 +1,111 @@
+use crate::{Framer, Hamming1511, HammingCode, HammingError, Interleaver, Scrambler};
+use alloc::vec::Vec;
+
+/// Reference radio link configuration
+///
+/// Transmit chain: length prefix, scrambler, Hamming(15,11), bit interleaver
+/// and sync-word framing. `receive` undoes each stage in reverse order.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LinkProfile {
+    scrambler: Scrambler,
+    interleaver: Interleaver,
+    framer: Framer,
+}
+
+impl LinkProfile {
+    /// The blessed radio profile: `x^15 + x^14 + 1` scrambler, depth-16
+    /// interleaver, Hamming(15,11) and the CCSDS sync word `0x1ACFFC1D`
+    pub fn radio() -> Self {
+        Self {
+            scrambler: Scrambler::new(0xC001, 0x4A80),
+            interleaver: Interleaver::new(16),
+            framer: Framer::new(0x1ACF_FC1D, 3),
+        }
+    }
+
+    /// Build a complete frame for the payload
+    ///
+    /// The length prefix is 32 bits, so payloads over `u32::MAX` bytes are
+    /// rejected with [`HammingError::InvalidLength`].
+    pub fn transmit(&self, payload: &[u8]) -> Result<Vec<u8>, HammingError> {
+        let mut body = Vec::with_capacity(payload.len().saturating_add(4));
+        body.extend_from_slice(&length_prefix(payload.len())?);
+        body.extend_from_slice(payload);
+
+        let scrambled = self.scrambler.apply(&body);
+        let encoded = Hamming1511.encode(&scrambled);
+        let interleaved = self.interleaver.interleave(&encoded);
+        Ok(self.framer.frame(&interleaved))
+    }
+
+    /// Recover the payload from a frame produced by [`LinkProfile::transmit`]
+    pub fn receive(&self, frame: &[u8]) -> Result<Vec<u8>, HammingError> {
+        let interleaved = self.framer.deframe(frame)?;
+        let encoded = self.interleaver.deinterleave(interleaved);
+        let scrambled = Hamming1511.decode(&encoded)?;
+        let body = self.scrambler.apply(&scrambled);
+
+        let [a, b, c, d, rest @ ..] = body.as_slice() else {
+            return Err(HammingError::InvalidLength);
+        };
+        let len = u32::from_le_bytes([*a, *b, *c, *d]) as usize;
+
+        rest.get(..len)
+            .map(<[u8]>::to_vec)
+            .ok_or(HammingError::InvalidLength)
+    }
+}
+
+// The payload length as the frame's little-endian 32-bit prefix
+fn length_prefix(len: usize) -> Result<[u8; 4], HammingError> {
+    u32::try_from(len)
+        .map(u32::to_le_bytes)
+        .map_err(|_| HammingError::InvalidLength)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_link_roundtrip() {
+        let link = LinkProfile::radio();
+        for len in [0, 1, 2, 3, 10, 11, 100] {
+            let payload: Vec<u8> = (0..len).map(|i| (i * 31) as u8).collect();
+            let frame = link.transmit(&payload).unwrap();
+            assert_eq!(link.receive(&frame).unwrap(), payload);
+        }
+    }
+
+    #[test]
+    fn test_link_length_prefix_limit() {
+        assert_eq!(length_prefix(258), Ok([2, 1, 0, 0]));
+        assert_eq!(length_prefix(u32::MAX as usize), Ok([0xFF; 4]));
+        if let Some(len) = (u32::MAX as usize).checked_add(1) {
+            assert_eq!(length_prefix(len), Err(HammingError::InvalidLength));
+        }
+    }
+
+    #[test]
+    fn test_link_corrects_burst() {
+        let link = LinkProfile::radio();
+        let payload = b"The quick brown fox jumps over the lazy dog".to_vec();
+
+        let mut frame = link.transmit(&payload).unwrap();
+        // 16-bit burst right after the sync word
+        frame[20] ^= 0xFF;
+        frame[21] ^= 0xFF;
+
+        assert_eq!(link.receive(&frame).unwrap(), payload);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_link_profile_serde() {
+        let json = serde_json::to_string(&LinkProfile::radio()).unwrap();
+        let link: LinkProfile = serde_json::from_str(&json).unwrap();
+
+        let frame = LinkProfile::radio().transmit(b"config").unwrap();
+        assert_eq!(link.receive(&frame).unwrap(), b"config");
+    }
+}
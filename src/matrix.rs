@@ -0,0 +1,130 @@
+use crate::{CodeSpec, Hamming, HammingCode, HammingError};
+use alloc::{string::String, vec::Vec};
+use core::fmt::Write;
+
+/// Generator and parity-check matrices of a code
+///
+/// Matrix column `i` is codeword position `i`, the bit order of
+/// [`LookupTables`](crate::LookupTables) and the HDL vectors: a codeword is
+/// `data * G`, and `H * codeword` is the syndrome (with the overall parity
+/// as its last row for extended codes).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeMatrices {
+    /// `k` rows of `n` bits
+    pub generator: Vec<Vec<bool>>,
+    /// `n - k` rows of `n` bits
+    pub parity_check: Vec<Vec<bool>>,
+}
+
+impl CodeMatrices {
+    /// Matrices of a codec selection
+    pub fn new(spec: &CodeSpec) -> Result<Self, HammingError> {
+        Self::for_code(&spec.general_equivalent().0)
+    }
+
+    /// Matrices of a configured general code
+    pub fn for_code(code: &Hamming) -> Result<Self, HammingError> {
+        if code.data_bits() == 0 {
+            return Err(HammingError::Unsupported);
+        }
+        Ok(Self {
+            generator: (0..code.data_bits())
+                .map(|bit| code.generator_row(bit))
+                .collect(),
+            parity_check: code.parity_check_rows(),
+        })
+    }
+
+    /// Both matrices as rows of 0/1 digits (position 0 first) followed by
+    /// the row as a hex mask (position `i` in bit `i`)
+    pub fn render(&self) -> String {
+        let n = self.generator.first().map_or(0, Vec::len);
+        let k = self.generator.len();
+        let mut out = String::new();
+
+        for (title, rows) in [("G", &self.generator), ("H", &self.parity_check)] {
+            let _ = writeln!(out, "{title} ({} x {n}) of Hamming({n},{k})", rows.len());
+            for row in rows {
+                let bits: String = row.iter().map(|&b| if b { '1' } else { '0' }).collect();
+                let _ = writeln!(out, "  {bits}  0x{}", hex_mask(row));
+            }
+            out.push('\n');
+        }
+        out.pop();
+        out
+    }
+}
+
+// Row as a hexadecimal number with position `i` in bit `i`
+fn hex_mask(row: &[bool]) -> String {
+    let digits: Vec<u8> = row
+        .chunks(4)
+        .map(|nibble| {
+            nibble
+                .iter()
+                .enumerate()
+                .fold(0, |digit, (i, &bit)| digit | (bit as u8) << i)
+        })
+        .collect();
+    digits
+        .iter()
+        .rev()
+        .map(|&d| {
+            char::from_digit(d as u32, 16)
+                .unwrap_or('0')
+                .to_ascii_uppercase()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Multiply a data word by G, then check H times the codeword is zero
+    fn check(matrices: &CodeMatrices, data: &[bool]) -> Vec<bool> {
+        let n = matrices.generator[0].len();
+        let codeword: Vec<bool> = (0..n)
+            .map(|pos| {
+                data.iter()
+                    .zip(&matrices.generator)
+                    .fold(false, |acc, (&d, row)| acc ^ (d && row[pos]))
+            })
+            .collect();
+        for row in &matrices.parity_check {
+            let parity = row
+                .iter()
+                .zip(&codeword)
+                .fold(false, |acc, (&h, &c)| acc ^ (h && c));
+            assert!(!parity);
+        }
+        codeword
+    }
+
+    #[test]
+    fn test_hamming74_matrices() {
+        let m = CodeMatrices::new(&CodeSpec::Hamming74).unwrap();
+        let text = m.render();
+        // Data bit 0 encodes to 0x07 (see LookupTables)
+        assert!(text.contains("G (4 x 7) of Hamming(7,4)\n  1110000  0x07\n"));
+        assert!(text.contains("H (3 x 7) of Hamming(7,4)\n  1010101  0x55\n"));
+
+        let codeword = check(&m, &[true, false, true, true]);
+        let encoded = Hamming::new(4).encode(&[0x0D]);
+        let word = codeword
+            .iter()
+            .enumerate()
+            .fold(0u8, |w, (i, &b)| w | (b as u8) << i);
+        assert_eq!(encoded[0] & 0x7F, word);
+    }
+
+    #[test]
+    fn test_secded_matrices() {
+        let m = CodeMatrices::new(&CodeSpec::Secded { data_bits: 64 }).unwrap();
+        assert_eq!(m.generator.len(), 64);
+        assert_eq!(m.parity_check.len(), 8);
+        assert!(m.parity_check.iter().all(|row| row.len() == 72));
+        check(&m, &[true; 64]);
+        assert!(m.render().contains("0xFFFFFFFFFFFFFFFFFF\n"));
+    }
+}
@@ -0,0 +1,264 @@
+use crate::{
+    BinarySymmetricChannel, BlockOutcome, ChannelRng, Hamming, HammingCode, HammingError, Health,
+};
+use std::time::SystemTime;
+
+// Data bits per word; with the check bits a word takes 72 bits
+const WORD_BITS: usize = 64;
+
+/// Counters of a [`MemoryRegion`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MemoryStats {
+    pub ticks: u64,
+    /// Stored bits flipped by injected upsets
+    pub upsets: u64,
+    pub reads: u64,
+    /// Reads that corrected a single-bit error
+    pub read_corrections: u64,
+    /// Reads that failed on a detected double error
+    pub read_failures: u64,
+    /// Words checked by the scrubber
+    pub scrubbed_words: u64,
+    /// Single-bit errors fixed by the scrubber
+    pub scrub_corrections: u64,
+    /// Uncorrectable words found by the scrubber
+    pub scrub_failures: u64,
+    /// Reads and scrubs that produced a value other than the one written
+    /// without detecting it (three or more errors in a word)
+    pub silent_errors: u64,
+}
+
+/// Simulated memory of 64-bit words stored with SEC-DED(72,64) check bits
+///
+/// Upsets are injected into the stored codewords, one by one or at a
+/// per-bit rate on every [`MemoryRegion::tick`]. Reads correct single
+/// errors and write the corrected word back. An optional scrubber visits a
+/// number of words every few ticks, so single errors are removed before a
+/// second upset turns them into a double error; the statistics compare
+/// scrub schedules.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemoryRegion {
+    code: Hamming,
+    cells: Vec<u128>,
+    /// Value last written to each word, to tell miscorrections
+    written: Vec<u64>,
+    scrub_interval: u64,
+    scrub_words: usize,
+    cursor: usize,
+    stats: MemoryStats,
+    last_correction: Option<SystemTime>,
+}
+
+impl MemoryRegion {
+    /// Zero-filled region of `words` words, without scrubbing
+    pub fn new(words: usize) -> Self {
+        let code = Hamming::new(WORD_BITS).with_extended(true);
+        let zero = code.encode_word(0);
+        Self {
+            code,
+            cells: vec![zero; words],
+            written: vec![0; words],
+            scrub_interval: 0,
+            scrub_words: 0,
+            cursor: 0,
+            stats: MemoryStats::default(),
+            last_correction: None,
+        }
+    }
+
+    /// Scrub `words` words, continuing round the region, every `interval`
+    /// ticks; an interval of 0 disables scrubbing
+    pub fn with_scrub(mut self, interval: u64, words: usize) -> Self {
+        self.scrub_interval = interval;
+        self.scrub_words = words;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn stats(&self) -> MemoryStats {
+        self.stats
+    }
+
+    /// Corrections and failures of reads and scrubs since the region was
+    /// created
+    pub fn health(&self) -> Health {
+        let stats = &self.stats;
+        Health {
+            corrections: stats.read_corrections + stats.scrub_corrections,
+            uncorrectable: stats.read_failures + stats.scrub_failures,
+            last_correction: self.last_correction,
+        }
+    }
+
+    /// Store `value` at word `addr`
+    pub fn write(&mut self, addr: usize, value: u64) -> Result<(), HammingError> {
+        let (cell, written) = self
+            .cells
+            .get_mut(addr)
+            .zip(self.written.get_mut(addr))
+            .ok_or(HammingError::InvalidLength)?;
+        *cell = self.code.encode_word(value.into());
+        *written = value;
+        Ok(())
+    }
+
+    /// Read word `addr`, correcting and writing back a single-bit error; a
+    /// double error is reported with `addr` as the block
+    pub fn read(&mut self, addr: usize) -> Result<u64, HammingError> {
+        self.stats.reads += 1;
+        match self.check(addr)? {
+            BlockOutcome::Clean => {}
+            BlockOutcome::Corrected { .. } => self.stats.read_corrections += 1,
+            BlockOutcome::Uncorrectable => {
+                self.stats.read_failures += 1;
+                let cell = self.cells.get(addr).copied().unwrap_or_default();
+                let (_, syndrome, _) = self.code.decode_word(cell);
+                return Err(self.code.uncorrectable(addr, syndrome));
+            }
+        }
+        let cell = self.cells.get(addr).copied().unwrap_or_default();
+        Ok(self.code.decode_word(cell).0 as u64)
+    }
+
+    /// Flip bit `bit` (0..72) of the codeword stored at word `addr`
+    pub fn upset(&mut self, addr: usize, bit: u32) {
+        if let Some(cell) = self.cells.get_mut(addr)
+            && (bit as usize) < self.code.block_size()
+        {
+            *cell ^= 1 << bit;
+            self.stats.upsets += 1;
+        }
+    }
+
+    /// Advance time by one tick: every stored bit is flipped with the
+    /// channel's error rate, then the scrubber runs if it is due
+    pub fn tick<R: ChannelRng>(&mut self, channel: &mut BinarySymmetricChannel<R>) {
+        let n = self.code.block_size();
+        let mask = (1u128 << n) - 1;
+        for cell in &mut self.cells {
+            let mut bytes = cell.to_le_bytes();
+            let flipped = channel.corrupt(bytes.get_mut(..n.div_ceil(8)).unwrap_or_default());
+            if flipped > 0 {
+                // Flips in the last byte's unused bits are not upsets
+                let upset = u128::from_le_bytes(bytes) & mask;
+                self.stats.upsets += u64::from((upset ^ *cell).count_ones());
+                *cell = upset;
+            }
+        }
+
+        self.stats.ticks += 1;
+        if self.scrub_interval > 0 && self.stats.ticks.is_multiple_of(self.scrub_interval) {
+            self.scrub(self.scrub_words);
+        }
+    }
+
+    /// Check and correct the next `words` words of the scrub cycle
+    pub fn scrub(&mut self, words: usize) {
+        for _ in 0..words.min(self.cells.len()) {
+            let addr = self.cursor;
+            self.cursor = (self.cursor + 1) % self.cells.len();
+            self.stats.scrubbed_words += 1;
+            match self.check(addr) {
+                Ok(BlockOutcome::Corrected { .. }) => self.stats.scrub_corrections += 1,
+                Ok(BlockOutcome::Uncorrectable) => self.stats.scrub_failures += 1,
+                _ => {}
+            }
+        }
+    }
+
+    // Decode word `addr`, write back a correction and count silent errors
+    fn check(&mut self, addr: usize) -> Result<BlockOutcome, HammingError> {
+        let (cell, written) = self
+            .cells
+            .get_mut(addr)
+            .zip(self.written.get(addr))
+            .ok_or(HammingError::InvalidLength)?;
+        let (data, _, outcome) = self.code.decode_word(*cell);
+        if outcome == BlockOutcome::Uncorrectable {
+            return Ok(outcome);
+        }
+
+        if data as u64 != *written {
+            self.stats.silent_errors += 1;
+        }
+        if let BlockOutcome::Corrected { .. } = outcome {
+            *cell = self.code.encode_word(data);
+            self.last_correction = Some(SystemTime::now());
+        }
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_read_corrects() {
+        let mut memory = MemoryRegion::new(4);
+        memory.write(1, 0xDEAD_BEEF_0123_4567).unwrap();
+        memory.upset(1, 70);
+        assert_eq!(memory.read(1), Ok(0xDEAD_BEEF_0123_4567));
+        // Written back: the second read is clean
+        assert_eq!(memory.read(1), Ok(0xDEAD_BEEF_0123_4567));
+
+        memory.upset(2, 3);
+        memory.upset(2, 40);
+        assert_eq!(
+            memory.read(2),
+            Err(HammingError::DoubleErrorDetected { block: 2 })
+        );
+        assert_eq!(memory.read(4), Err(HammingError::InvalidLength));
+
+        let stats = memory.stats();
+        assert_eq!((stats.upsets, stats.reads), (3, 4));
+        assert_eq!((stats.read_corrections, stats.read_failures), (1, 1));
+        let health = memory.health();
+        assert_eq!((health.corrections, health.uncorrectable), (1, 1));
+        assert!(health.last_correction.is_some());
+    }
+
+    #[test]
+    fn test_memory_scrubbing() {
+        // Two upsets in a word 5 ticks apart: scrubbing every word every 2
+        // ticks fixes the first before the second arrives
+        let run = |scrub: u64| {
+            let mut memory = MemoryRegion::new(8).with_scrub(scrub, 8);
+            let mut quiet = BinarySymmetricChannel::new(0.0, 0);
+            memory.upset(5, 10);
+            for _ in 0..5 {
+                memory.tick(&mut quiet);
+            }
+            memory.upset(5, 20);
+            memory.read(5).map(|_| memory.stats())
+        };
+
+        let stats = run(2).unwrap();
+        assert_eq!(stats.scrub_corrections, 1);
+        assert_eq!(stats.scrubbed_words, 16);
+        assert_eq!(stats.read_corrections, 1);
+        assert_eq!(run(0), Err(HammingError::DoubleErrorDetected { block: 5 }));
+    }
+
+    #[test]
+    fn test_memory_random_upsets() {
+        let mut memory = MemoryRegion::new(64).with_scrub(1, 64);
+        let mut channel = BinarySymmetricChannel::new(1e-3, 7);
+        for _ in 0..100 {
+            memory.tick(&mut channel);
+        }
+        // 64 words * 72 bits * 100 ticks at 1e-3: about 460 upsets
+        let stats = memory.stats();
+        assert!((350..600).contains(&stats.upsets), "{stats:?}");
+        assert_eq!(stats.scrubbed_words, 6400);
+        assert!(stats.scrub_corrections > 0);
+    }
+}
@@ -0,0 +1,116 @@
+use crate::channel::splitmix64;
+use crate::chunked::ChunkedEncoder;
+use crate::{HammingCode, HammingError};
+use alloc::vec::Vec;
+
+// Data length (u64, little-endian) in front of randomly padded data
+const LEN_BYTES: usize = 8;
+
+/// How the data is padded to whole blocks, see
+/// [`HammingCode::encode_padded`]
+///
+/// [`HammingCode::encode`] always fills with zero bits, for every codec, so
+/// the decoded data ends in padding unless its length is known otherwise.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Padding {
+    /// Zero bits; decoding keeps them, as [`HammingCode::decode`] does
+    #[default]
+    Zero,
+    /// A [`TERMINATOR`](crate::TERMINATOR) byte, then zero bits, as
+    /// [`HammingCode::encode_terminated`] writes; decoding strips both
+    Terminator,
+    /// The data length in front, then the data and pseudo-random bytes from
+    /// `seed` up to a whole [`HammingCode::chunk_sizes`] unit, so padding
+    /// does not leave long runs of zero codewords; decoding cuts the data to
+    /// the recorded length
+    Random { seed: u64 },
+}
+
+impl Padding {
+    pub(crate) fn encode<C: HammingCode + ?Sized>(&self, code: &C, data: &[u8]) -> Vec<u8> {
+        match *self {
+            Padding::Zero => code.encode(data),
+            Padding::Terminator => code.encode_terminated(data),
+            Padding::Random { mut seed } => {
+                let chunk = code.chunk_sizes().0.max(1);
+                let len = LEN_BYTES + data.len();
+                let fill: Vec<u8> = core::iter::repeat_with(|| splitmix64(&mut seed).to_le_bytes())
+                    .flatten()
+                    .take(len.next_multiple_of(chunk) - len)
+                    .collect();
+
+                let mut encoder = ChunkedEncoder::new(code, len + fill.len());
+                encoder.push(&(data.len() as u64).to_le_bytes());
+                encoder.push(data);
+                encoder.push(&fill);
+                encoder.finish()
+            }
+        }
+    }
+
+    pub(crate) fn decode<C: HammingCode + ?Sized>(
+        &self,
+        code: &C,
+        encoded: &[u8],
+    ) -> Result<Vec<u8>, HammingError> {
+        match self {
+            Padding::Zero => code.decode(encoded),
+            Padding::Terminator => code.decode_terminated(encoded),
+            Padding::Random { .. } => {
+                let decoded = code.decode(encoded)?;
+                let (len, data) = decoded
+                    .split_first_chunk::<LEN_BYTES>()
+                    .ok_or(HammingError::InvalidLength)?;
+                let len = usize::try_from(u64::from_le_bytes(*len))
+                    .map_err(|_| HammingError::InvalidLength)?;
+                data.get(..len)
+                    .map(<[u8]>::to_vec)
+                    .ok_or(HammingError::InvalidLength)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Hamming1511, sample_codecs};
+
+    #[test]
+    fn test_padding_roundtrip() {
+        let codecs = sample_codecs();
+        let data = b"padded\0\0";
+        for codec in &codecs {
+            for padding in [Padding::Terminator, Padding::Random { seed: 7 }] {
+                let encoded = codec.encode_padded(data, padding);
+                assert_eq!(codec.decode_padded(&encoded, padding).unwrap(), data);
+            }
+            let decoded = codec
+                .decode_padded(&codec.encode_padded(data, Padding::Zero), Padding::Zero)
+                .unwrap();
+            assert!(decoded.starts_with(data));
+        }
+    }
+
+    #[test]
+    fn test_random_padding() {
+        let random = Padding::Random { seed: 1 };
+        let encoded = Hamming1511.encode_padded(b"x", random);
+        // Length, data and fill make up one whole chunk
+        assert_eq!(encoded.len(), Hamming1511.chunk_sizes().1);
+        assert_ne!(
+            encoded,
+            Hamming1511.encode_padded(b"x", Padding::Random { seed: 2 })
+        );
+        assert_eq!(
+            Hamming1511.decode_padded(&Hamming1511.encode(&[0xFF; 11]), random),
+            Err(HammingError::InvalidLength)
+        );
+    }
+}
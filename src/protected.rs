@@ -0,0 +1,222 @@
+use crate::{BlockOutcome, Hamming74, HammingCode, HammingError, Health};
+use std::fmt;
+use std::marker::PhantomData;
+
+/// Plain values with a fixed little-endian byte representation, as stored
+/// by [`Protected`]
+///
+/// With the `derive` feature, `#[derive(HammingProtect)]` implements it for
+/// structs whose fields all implement it.
+pub trait AsBytes: Sized {
+    /// Append the bytes of the value to `out`
+    fn write_bytes(&self, out: &mut Vec<u8>);
+
+    /// Take a value from the front of `bytes`, advancing past it; `None` if
+    /// `bytes` is too short
+    fn read_bytes(bytes: &mut &[u8]) -> Option<Self>;
+
+    /// The value's bytes, Hamming(7,4)-encoded
+    fn to_encoded(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.write_bytes(&mut bytes);
+        Hamming74.encode(&bytes)
+    }
+
+    /// Correct and decode [`AsBytes::to_encoded`] output
+    fn from_encoded(encoded: &[u8]) -> Result<Self, HammingError> {
+        let bytes = Hamming74.decode(encoded)?;
+        Self::read_bytes(&mut bytes.as_slice()).ok_or(HammingError::InvalidLength)
+    }
+}
+
+macro_rules! impl_as_bytes {
+    ($($t:ty),*) => {$(
+        impl AsBytes for $t {
+            fn write_bytes(&self, out: &mut Vec<u8>) {
+                out.extend_from_slice(&self.to_le_bytes());
+            }
+
+            fn read_bytes(bytes: &mut &[u8]) -> Option<Self> {
+                let (value, rest) = bytes.split_first_chunk()?;
+                *bytes = rest;
+                Some(<$t>::from_le_bytes(*value))
+            }
+        }
+    )*};
+}
+
+impl_as_bytes!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+impl AsBytes for bool {
+    fn write_bytes(&self, out: &mut Vec<u8>) {
+        out.push(u8::from(*self));
+    }
+
+    fn read_bytes(bytes: &mut &[u8]) -> Option<Self> {
+        u8::read_bytes(bytes).map(|b| b != 0)
+    }
+}
+
+impl<T: AsBytes, const N: usize> AsBytes for [T; N] {
+    fn write_bytes(&self, out: &mut Vec<u8>) {
+        for item in self {
+            item.write_bytes(out);
+        }
+    }
+
+    fn read_bytes(bytes: &mut &[u8]) -> Option<Self> {
+        let items: Vec<T> = (0..N)
+            .map(|_| T::read_bytes(bytes))
+            .collect::<Option<_>>()?;
+        items.try_into().ok()
+    }
+}
+
+/// A value kept Hamming(7,4)-encoded in memory
+///
+/// Every [`Protected::get`] decodes the stored codewords, writes back the
+/// corrected encoding if any bit had flipped and counts the corrected bits,
+/// so single-bit upsets do not accumulate between accesses.
+pub struct Protected<T> {
+    encoded: Vec<u8>,
+    health: Health,
+    value: PhantomData<T>,
+}
+
+impl<T: AsBytes> Protected<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            encoded: value.to_encoded(),
+            health: Health::default(),
+            value: PhantomData,
+        }
+    }
+
+    /// The stored value, corrected
+    pub fn get(&mut self) -> Result<T, HammingError> {
+        let mut corrected = 0;
+        let decoded = Hamming74.decode_observed(&self.encoded, &mut |event| {
+            if let BlockOutcome::Corrected { .. } = event.outcome {
+                corrected += 1;
+            }
+        });
+        let value = decoded.and_then(|bytes| {
+            let value = T::read_bytes(&mut bytes.as_slice()).ok_or(HammingError::InvalidLength)?;
+            if corrected > 0 {
+                self.health.corrected(corrected);
+                self.encoded = Hamming74.encode(&bytes);
+            }
+            Ok(value)
+        });
+        if value.is_err() {
+            self.health.failed();
+        }
+        value
+    }
+
+    /// Replace the stored value
+    pub fn set(&mut self, value: T) {
+        self.encoded = value.to_encoded();
+    }
+}
+
+impl<T> Protected<T> {
+    /// Bits corrected by [`Protected::get`] since the value was created
+    pub fn corrections(&self) -> u64 {
+        self.health.corrections
+    }
+
+    /// Corruption counters since the value was created
+    pub fn health(&self) -> Health {
+        self.health
+    }
+
+    /// The encoded storage, for fault injection
+    pub fn encoded_mut(&mut self) -> &mut [u8] {
+        &mut self.encoded
+    }
+}
+
+impl<T> fmt::Debug for Protected<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Protected")
+            .field("encoded", &self.encoded)
+            .field("health", &self.health)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_protected_corrects_and_counts() {
+        let mut altitude = Protected::new(12_345u32);
+        assert_eq!(altitude.get(), Ok(12_345));
+
+        altitude.encoded_mut()[0] ^= 0x04;
+        altitude.encoded_mut()[5] ^= 0x20;
+        assert_eq!(altitude.get(), Ok(12_345));
+        assert_eq!(altitude.corrections(), 2);
+
+        // The corrected encoding was written back
+        assert_eq!(altitude.get(), Ok(12_345));
+        assert_eq!(altitude.corrections(), 2);
+
+        altitude.set(500);
+        assert_eq!(altitude.get(), Ok(500));
+        assert!(altitude.health().last_correction.is_some());
+    }
+
+    #[test]
+    fn test_as_bytes_types() {
+        let mut gains = Protected::new([1.5f32, -2.25, 0.0]);
+        for byte in gains.encoded_mut().iter_mut().step_by(3) {
+            *byte ^= 0x01;
+        }
+        assert_eq!(gains.get(), Ok([1.5, -2.25, 0.0]));
+
+        let mut armed = Protected::new([true, false]);
+        assert_eq!(armed.get(), Ok([true, false]));
+
+        let mut bytes = Vec::new();
+        (-2i16).write_bytes(&mut bytes);
+        assert_eq!(bytes, [0xFE, 0xFF]);
+        assert_eq!(u32::read_bytes(&mut bytes.as_slice()), None);
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derive_hamming_protect() {
+        use crate::HammingProtect;
+
+        #[derive(Debug, Clone, Copy, PartialEq, HammingProtect)]
+        struct Attitude(i16, i16, i16);
+
+        #[derive(Debug, PartialEq, HammingProtect)]
+        struct State {
+            mode: u8,
+            armed: bool,
+            attitude: Attitude,
+            gains: [f32; 2],
+        }
+
+        let state = State {
+            mode: 3,
+            armed: true,
+            attitude: Attitude(-100, 0, 2000),
+            gains: [0.5, -1.25],
+        };
+        let mut encoded = state.to_encoded();
+        // 1 + 1 + 6 + 8 bytes, two codewords each
+        assert_eq!(encoded.len(), 32);
+        encoded[9] ^= 0x40;
+        assert_eq!(State::from_encoded(&encoded), Ok(state));
+
+        let mut attitude = Protected::new(Attitude(1, 2, 3));
+        attitude.encoded_mut()[4] ^= 0x01;
+        assert_eq!(attitude.get(), Ok(Attitude(1, 2, 3)));
+        assert_eq!(attitude.corrections(), 1);
+    }
+}
@@ -0,0 +1,200 @@
+use crate::HammingCode;
+use std::io::{self, Read};
+
+// Most encoded bytes decoded at a time; rounded to whole chunk units
+const BUFFER_SIZE: usize = 8192;
+
+/// [`Read`] adapter decoding an encoded stream from the inner reader and
+/// handing out the corrected data
+///
+/// Encoded bytes are decoded in whole [`HammingCode::chunk_sizes`] units as
+/// they arrive, a few KiB at a time; a short unit at the end of the input
+/// is decoded as the tail of the stream, so everything read equals
+/// `code.decode` of the whole input. A decode failure is returned as
+/// [`io::ErrorKind::InvalidData`] wrapping the [`HammingError`]
+/// (recovered with `err.get_ref()` and `downcast_ref`); the encoded bytes
+/// of that batch are dropped, and later reads resume after them.
+///
+/// [`HammingError`]: crate::HammingError
+///
+/// ```
+/// use hamming_rs::{Hamming1511, HammingCode, HammingReader};
+/// use std::io::Read;
+///
+/// let mut encoded = Hamming1511.encode(b"read back corrected");
+/// encoded[5] ^= 0x08;
+/// let mut decoded = Vec::new();
+/// let mut reader = HammingReader::new(&encoded[..], Hamming1511);
+/// reader.read_to_end(&mut decoded)?;
+/// assert_eq!(decoded[..19], *b"read back corrected");
+/// assert_eq!(reader.corrected(), 1);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct HammingReader<R: Read, C: HammingCode> {
+    inner: R,
+    code: C,
+    encoded: Vec<u8>,
+    decoded: Vec<u8>,
+    position: usize,
+    corrected: usize,
+}
+
+impl<R: Read, C: HammingCode> HammingReader<R, C> {
+    pub fn new(inner: R, code: C) -> Self {
+        Self {
+            inner,
+            code,
+            encoded: Vec::new(),
+            decoded: Vec::new(),
+            position: 0,
+            corrected: 0,
+        }
+    }
+
+    pub fn code(&self) -> &C {
+        &self.code
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// The inner reader; reading from it directly skips encoded bytes
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Bits corrected so far
+    pub fn corrected(&self) -> usize {
+        self.corrected
+    }
+
+    // Decode the next batch of whole units, or the tail of the input;
+    // `false` once the input is exhausted
+    fn fill(&mut self) -> io::Result<bool> {
+        let (_, unit) = self.code.chunk_sizes();
+        let unit = unit.max(1);
+        let capacity = BUFFER_SIZE.div_ceil(unit) * unit;
+
+        // Read until a whole unit is in or the input ends
+        let mut ended = false;
+        while self.encoded.len() < unit {
+            let start = self.encoded.len();
+            self.encoded.resize(capacity, 0);
+            let read = self
+                .inner
+                .read(self.encoded.get_mut(start..).unwrap_or_default());
+            self.encoded
+                .truncate(start + read.as_ref().copied().unwrap_or(0));
+            match read {
+                Ok(0) => {
+                    ended = true;
+                    break;
+                }
+                Ok(_) => {}
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        let whole = if ended {
+            self.encoded.len()
+        } else {
+            self.encoded.len() / unit * unit
+        };
+        if whole == 0 {
+            return Ok(false);
+        }
+        let batch: Vec<u8> = self.encoded.drain(..whole).collect();
+        let (decoded, corrected) = self.code.decode_with_stats(&batch)?;
+        self.decoded = decoded;
+        self.position = 0;
+        self.corrected += corrected;
+        Ok(true)
+    }
+}
+
+impl<R: Read, C: HammingCode> Read for HammingReader<R, C> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        loop {
+            let available = self.decoded.get(self.position..).unwrap_or_default();
+            if !available.is_empty() {
+                let n = available.len().min(buf.len());
+                buf.get_mut(..n)
+                    .unwrap_or_default()
+                    .copy_from_slice(available.get(..n).unwrap_or_default());
+                self.position += n;
+                return Ok(n);
+            }
+            if !self.fill()? {
+                return Ok(0);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Hamming, Hamming74, HammingError, sample_codecs};
+
+    // Hands out at most `step` bytes per read
+    struct Trickle<'a> {
+        data: &'a [u8],
+        step: usize,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.step.min(buf.len()).min(self.data.len());
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn test_reader_matches_slice_api() {
+        let data: Vec<u8> = (0..20_000).map(|i| (i * 17 + 3) as u8).collect();
+        let codecs = sample_codecs();
+
+        for codec in codecs {
+            let mut encoded = codec.encode(&data);
+            encoded[100] ^= 0x02;
+            encoded[10_000] ^= 0x40;
+            let expected = codec.decode(&encoded).unwrap();
+
+            let source = Trickle {
+                data: &encoded,
+                step: 5,
+            };
+            let mut reader = HammingReader::new(source, codec);
+            let mut decoded = Vec::new();
+            reader.read_to_end(&mut decoded).unwrap();
+            assert_eq!(decoded, expected);
+            assert_eq!(reader.corrected(), 2);
+        }
+    }
+
+    #[test]
+    fn test_reader_errors() {
+        let mut encoded = Hamming::new(64)
+            .with_extended(true)
+            .encode(b"two bit flips");
+        encoded[0] ^= 0x03;
+        let mut reader = HammingReader::new(&encoded[..], Hamming::new(64).with_extended(true));
+        let err = reader.read_to_end(&mut Vec::new()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(
+            err.get_ref().and_then(|e| e.downcast_ref::<HammingError>()),
+            Some(&HammingError::DoubleErrorDetected { block: 0 })
+        );
+
+        let mut reader = HammingReader::new(&[0u8; 3][..], Hamming74);
+        assert!(reader.read_to_end(&mut Vec::new()).is_err());
+    }
+}
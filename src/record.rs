@@ -0,0 +1,189 @@
+use crate::{BlockOutcome, Hamming74, HammingCode, HammingError};
+use alloc::{string::String, vec, vec::Vec};
+
+const FORMAT: u8 = 1;
+// Format, record version and key length
+const PREFIX_LEN: usize = 3;
+const CRC_LEN: usize = 4;
+
+/// A key and value for EEPROM/NVS-style storage, protected as one record
+///
+/// The record is stored as these fields, all Hamming(7,4)-encoded:
+///
+/// | bytes | field                                   |
+/// |-------|-----------------------------------------|
+/// | 1     | format (1)                              |
+/// | 1     | record version, for the caller's schema |
+/// | 1     | key length K                            |
+/// | K     | key, UTF-8                              |
+/// | 2     | value length V, little-endian           |
+/// | V     | value                                   |
+/// | 4     | CRC-32 of the fields above, LE          |
+///
+/// Single-bit errors in every block are corrected, and the CRC rejects
+/// records with more errors than that instead of returning a miscorrected
+/// value. Records carry their length, so they can be stored back to back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EccRecord {
+    version: u8,
+    key: String,
+    value: Vec<u8>,
+}
+
+impl EccRecord {
+    /// Keys are limited to 255 bytes and values to 65535 bytes
+    pub fn new(
+        version: u8,
+        key: impl Into<String>,
+        value: impl Into<Vec<u8>>,
+    ) -> Result<Self, HammingError> {
+        let (key, value) = (key.into(), value.into());
+        if key.len() > u8::MAX as usize || value.len() > u16::MAX as usize {
+            return Err(HammingError::InvalidLength);
+        }
+        Ok(Self {
+            version,
+            key,
+            value,
+        })
+    }
+
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn value(&self) -> &[u8] {
+        &self.value
+    }
+
+    /// Length of [`EccRecord::to_bytes`] output
+    pub fn encoded_len(&self) -> usize {
+        Hamming74::encoded_len(PREFIX_LEN + self.key.len() + 2 + self.value.len() + CRC_LEN)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut fields = vec![FORMAT, self.version, self.key.len() as u8];
+        fields.extend_from_slice(self.key.as_bytes());
+        fields.extend_from_slice(&(self.value.len() as u16).to_le_bytes());
+        fields.extend_from_slice(&self.value);
+        fields.extend_from_slice(&crc32(&fields).to_le_bytes());
+        Hamming74.encode(&fields)
+    }
+
+    /// Read the record at the start of `bytes`, returning it with the number
+    /// of bits corrected
+    ///
+    /// Bytes after the record are ignored; [`EccRecord::encoded_len`] gives
+    /// the offset of the next one. A CRC mismatch is reported as
+    /// [`HammingError::UncorrectableErrors`]. Rewrite the record when bits
+    /// were corrected, before they accumulate.
+    pub fn from_bytes(bytes: &[u8]) -> Result<(Self, usize), HammingError> {
+        let mut corrected = 0;
+        let mut fields = Vec::new();
+        // Decode the next `len` bytes of fields
+        let mut decode = |fields: &mut Vec<u8>, len: usize| -> Result<Vec<u8>, HammingError> {
+            let start = Hamming74::encoded_len(fields.len());
+            let encoded = bytes
+                .get(start..start + Hamming74::encoded_len(len))
+                .ok_or(HammingError::InvalidLength)?;
+            let decoded = Hamming74.decode_observed(encoded, &mut |event| {
+                if let BlockOutcome::Corrected { .. } = event.outcome {
+                    corrected += 1;
+                }
+            })?;
+            fields.extend_from_slice(&decoded);
+            Ok(decoded)
+        };
+
+        let [format, version, key_len] = decode(&mut fields, PREFIX_LEN)?[..] else {
+            return Err(HammingError::InvalidLength);
+        };
+        if format != FORMAT {
+            return Err(HammingError::Unsupported);
+        }
+        let mut key = decode(&mut fields, key_len as usize + 2)?;
+        let value_len = key.split_off(key_len as usize);
+        let value_len = u16::from_le_bytes(value_len.try_into().unwrap_or_default()) as usize;
+        let mut value = decode(&mut fields, value_len + CRC_LEN)?;
+        let crc = value.split_off(value_len);
+
+        let body = fields.get(..fields.len() - CRC_LEN).unwrap_or_default();
+        if crc.try_into().map(u32::from_le_bytes) != Ok(crc32(body)) {
+            return Err(HammingError::UncorrectableErrors);
+        }
+        let record = Self {
+            version,
+            key: String::from_utf8(key).map_err(|_| HammingError::Serialization)?,
+            value,
+        };
+        Ok((record, corrected))
+    }
+}
+
+// CRC-32 (IEEE 802.3, as in zlib and Ethernet), bit by bit
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xEDB8_8320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_round_trip() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+
+        let record = EccRecord::new(3, "wifi.ssid", b"hamming-lab".to_vec()).unwrap();
+        let next = EccRecord::new(1, "boot.count", 42u32.to_le_bytes()).unwrap();
+        let mut bytes = record.to_bytes();
+        assert_eq!(bytes.len(), record.encoded_len());
+        bytes.extend(next.to_bytes());
+
+        assert_eq!(EccRecord::from_bytes(&bytes), Ok((record.clone(), 0)));
+        let rest = &bytes[record.encoded_len()..];
+        assert_eq!(EccRecord::from_bytes(rest), Ok((next, 0)));
+
+        // One flip per block in the prefix, the key, the value and the CRC
+        for (i, bit) in [(0, 2), (7, 5), (30, 0), (record.encoded_len() - 1, 6)] {
+            bytes[i] ^= 1 << bit;
+        }
+        assert_eq!(EccRecord::from_bytes(&bytes), Ok((record, 4)));
+    }
+
+    #[test]
+    fn test_record_rejects_damage() {
+        let record = EccRecord::new(0, "k", vec![0x5A; 10]).unwrap();
+        let bytes = record.to_bytes();
+
+        // Two flips in one block are miscorrected; the CRC catches it
+        let mut damaged = bytes.clone();
+        damaged[12] ^= 0x03;
+        assert_eq!(
+            EccRecord::from_bytes(&damaged),
+            Err(HammingError::UncorrectableErrors)
+        );
+        assert_eq!(
+            EccRecord::from_bytes(&bytes[..bytes.len() - 2]),
+            Err(HammingError::InvalidLength)
+        );
+        assert_eq!(
+            EccRecord::from_bytes(&Hamming74.encode(&[2, 0, 0])),
+            Err(HammingError::Unsupported)
+        );
+        assert_eq!(
+            EccRecord::new(0, "k".repeat(256), []),
+            Err(HammingError::InvalidLength)
+        );
+    }
+}
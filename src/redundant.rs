@@ -0,0 +1,228 @@
+use crate::{HammingCode, HammingError, bits};
+use alloc::vec::Vec;
+
+// Most bits on which two damaged copies of a chunk may disagree before the
+// vote gives up; every way of settling them is tried
+const MAX_TIE_BITS: usize = 8;
+
+/// What decoding a [`Redundant`] stream took
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RedundancyStats {
+    /// Bits corrected in the chunks used
+    pub corrected: usize,
+    /// Chunks taken from a copy other than the first
+    pub fallbacks: usize,
+    /// Chunks every copy needed correcting in, taken from a bitwise vote
+    pub voted: usize,
+}
+
+/// Two or three independently encoded copies of the data, one after the
+/// other, for data that must survive more than one code can correct
+///
+/// Decoding goes [`HammingCode::chunk_sizes`] chunk by chunk and takes each
+/// chunk from a copy that decodes it cleanly, so damage to different chunks
+/// of different copies is survived. When every copy needs correcting, which
+/// for a block with two errors may be a silent miscorrection, the copies
+/// are voted bit by bit: the majority of three, or for two copies every way
+/// of settling the bits they disagree on. The vote is used unless it takes
+/// more corrections than the best copy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Redundant<C> {
+    code: C,
+    copies: usize,
+}
+
+impl<C: HammingCode> Redundant<C> {
+    /// `copies` copies (clamped to 2 or 3) encoded with `code`
+    pub fn new(code: C, copies: usize) -> Self {
+        Self {
+            code,
+            copies: copies.clamp(2, 3),
+        }
+    }
+
+    pub fn copies(&self) -> usize {
+        self.copies
+    }
+
+    pub fn code(&self) -> &C {
+        &self.code
+    }
+
+    /// Encode `data` once and store it `copies` times
+    pub fn encode(&self, data: &[u8]) -> Vec<u8> {
+        self.code.encode(data).repeat(self.copies)
+    }
+
+    /// Decode, recovering every chunk from the copies as described above
+    ///
+    /// A stream that does not split into equal copies fails with
+    /// [`HammingError::InvalidLength`], a chunk neither a copy nor the vote
+    /// decodes with [`HammingError::UncorrectableErrors`].
+    pub fn decode(&self, encoded: &[u8]) -> Result<Vec<u8>, HammingError> {
+        Ok(self.decode_with_stats(encoded)?.0)
+    }
+
+    /// Decode, also returning how the chunks were recovered
+    pub fn decode_with_stats(
+        &self,
+        encoded: &[u8],
+    ) -> Result<(Vec<u8>, RedundancyStats), HammingError> {
+        if !encoded.len().is_multiple_of(self.copies) {
+            return Err(HammingError::InvalidLength);
+        }
+        let copy_len = encoded.len() / self.copies;
+        let copies: Vec<&[u8]> = encoded.chunks(copy_len.max(1)).collect();
+        let chunk = self.code.chunk_sizes().1.max(1);
+
+        let mut data = Vec::with_capacity(copy_len);
+        let mut stats = RedundancyStats::default();
+        let mut start = 0;
+        while start < copy_len {
+            let end = (start + chunk).min(copy_len);
+            let pieces: Vec<&[u8]> = copies
+                .iter()
+                .filter_map(|copy| copy.get(start..end))
+                .collect();
+
+            // The copy decoding with the fewest corrections, the first on
+            // a tie
+            let mut best: Option<(usize, (Vec<u8>, usize))> = None;
+            for (i, piece) in pieces.iter().enumerate() {
+                if let Ok(decoded) = self.code.decode_with_stats(piece)
+                    && best
+                        .as_ref()
+                        .is_none_or(|(_, (_, fewest))| decoded.1 < *fewest)
+                {
+                    best = Some((i, decoded));
+                }
+            }
+            // Every copy needed correcting, which may have been a
+            // miscorrection: the vote wins unless it needs more
+            let vote = match best {
+                Some((_, (_, 0))) => None,
+                _ => self.vote(&pieces).filter(|(_, corrected)| {
+                    best.as_ref()
+                        .is_none_or(|(_, (_, fewest))| corrected <= fewest)
+                }),
+            };
+            let (decoded, corrected) = match (vote, best) {
+                (Some(vote), _) => {
+                    stats.voted += 1;
+                    vote
+                }
+                (None, Some((i, decoded))) => {
+                    stats.fallbacks += usize::from(i > 0);
+                    decoded
+                }
+                (None, None) => return Err(HammingError::UncorrectableErrors),
+            };
+            stats.corrected += corrected;
+            data.extend(decoded);
+            start = end;
+        }
+        Ok((data, stats))
+    }
+
+    // Decode the bitwise vote of the copies of a chunk, with the fewest
+    // corrections it takes
+    fn vote(&self, pieces: &[&[u8]]) -> Option<(Vec<u8>, usize)> {
+        match *pieces {
+            [a, b, c] => {
+                let majority: Vec<u8> = a
+                    .iter()
+                    .zip(b)
+                    .zip(c)
+                    .map(|((a, b), c)| (a & b) | (a & c) | (b & c))
+                    .collect();
+                self.code.decode_with_stats(&majority).ok()
+            }
+            [a, b] => {
+                let ties: Vec<usize> = (0..a.len() * 8)
+                    .filter(|&bit| bits::get(a, bit) != bits::get(b, bit))
+                    .collect();
+                if ties.len() > MAX_TIE_BITS {
+                    return None;
+                }
+
+                let mut candidate = a.to_vec();
+                (0..1u32 << ties.len())
+                    .filter_map(|choice| {
+                        for (i, &bit) in ties.iter().enumerate() {
+                            let source = if choice >> i & 1 == 1 { b } else { a };
+                            bits::assign(&mut candidate, bit, bits::get(source, bit));
+                        }
+                        self.code.decode_with_stats(&candidate).ok()
+                    })
+                    .min_by_key(|(_, corrected)| *corrected)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Hamming, Hamming74, Hamming1511, flip_bit};
+
+    #[test]
+    fn test_redundant_prefers_clean_copy() {
+        let data: Vec<u8> = (0..100u8).map(|i| i.wrapping_mul(73) ^ 0x5A).collect();
+        let redundant = Redundant::new(Hamming1511, 2);
+        let mut encoded = redundant.encode(&data);
+        let copy_len = encoded.len() / 2;
+        // Two errors in one block of the first copy, one error in another
+        // chunk of the second
+        encoded[3] ^= 0x11;
+        encoded[copy_len + 70] ^= 0x01;
+
+        let (decoded, stats) = redundant.decode_with_stats(&encoded).unwrap();
+        assert_eq!(
+            decoded,
+            Hamming1511.decode(&Hamming1511.encode(&data)).unwrap()
+        );
+        assert_eq!(
+            stats,
+            RedundancyStats {
+                corrected: 0,
+                fallbacks: 1,
+                voted: 0
+            }
+        );
+
+        let secded = Redundant::new(Hamming::new(64).with_extended(true), 5);
+        assert_eq!(secded.copies(), 3);
+        let mut encoded = secded.encode(&data);
+        encoded[20] ^= 0x03;
+        assert_eq!(secded.decode(&encoded).unwrap()[..100], data);
+        assert_eq!(secded.decode(&[0; 10]), Err(HammingError::InvalidLength));
+    }
+
+    #[test]
+    fn test_redundant_votes() {
+        // A chunk of Hamming(7,4) is one data byte in two blocks; two errors
+        // in a block miscorrect
+        let data = b"vote";
+        let two = Redundant::new(Hamming74, 2);
+        let mut encoded = two.encode(data);
+        encoded[0] ^= 0x03;
+        encoded[9] ^= 0x05;
+        let (decoded, stats) = two.decode_with_stats(&encoded).unwrap();
+        assert_eq!(decoded, data);
+        assert_eq!((stats.voted, stats.corrected), (1, 0));
+
+        let three = Redundant::new(Hamming74, 3);
+        let mut encoded = three.encode(data);
+        for (byte, bits) in [(0, [0, 1]), (9, [0, 1]), (16, [2, 3])] {
+            for bit in bits {
+                flip_bit(&mut encoded, byte, bit);
+            }
+        }
+        assert_ne!(Hamming74.decode(&encoded[..8]).unwrap(), data);
+        let (decoded, stats) = three.decode_with_stats(&encoded).unwrap();
+        assert_eq!(decoded, data);
+        assert_eq!(stats.voted, 1);
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{CodeSpec, Hamming, Hamming74, Hamming1511, Hamming127120, HammingCode};
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+// Codecs handed out are `Send + Sync` like the built-in ones
+type Codec = Box<dyn HammingCode + Send + Sync>;
+type Factory = Box<dyn Fn() -> Codec + Send + Sync>;
+
+struct Entry {
+    name: String,
+    block_size: usize,
+    data_bits: usize,
+    factory: Factory,
+}
+
+/// Codecs looked up by name or by `(n, k)`, e.g. from configuration strings
+///
+/// Built in are `hamming-7-4` and `hamming-15-11` (the fixed-size codes),
+/// `hamming-N-K` and `secded-N-K` for the general codes, and the
+/// [`CodeSpec`] short forms (`74`, `general:26`, ...). Registered codes are
+/// looked up first, so they can also replace a built-in one.
+#[derive(Default)]
+pub struct CodecRegistry {
+    custom: Vec<Entry>,
+}
+
+impl CodecRegistry {
+    /// Registry of the built-in codes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the codes `factory` builds under `name`; its block size and
+    /// data bits are taken from one instance
+    pub fn register(&mut self, name: &str, factory: impl Fn() -> Codec + Send + Sync + 'static) {
+        let code = factory();
+        self.custom.push(Entry {
+            name: name.to_string(),
+            block_size: code.block_size(),
+            data_bits: code.data_bits(),
+            factory: Box::new(factory),
+        });
+    }
+
+    pub fn codec_by_name(&self, name: &str) -> Option<Codec> {
+        if let Some(entry) = self.custom.iter().rev().find(|e| e.name == name) {
+            return Some((entry.factory)());
+        }
+        if let Ok(spec) = name.parse::<CodeSpec>() {
+            return Some(spec.build());
+        }
+
+        let (extended, params) = match name.split_once('-')? {
+            ("hamming", params) => (false, params),
+            ("secded", params) => (true, params),
+            _ => return None,
+        };
+        let (n, k) = params.split_once('-')?;
+        let (n, k) = (n.parse().ok()?, k.parse().ok()?);
+        match (extended, n, k) {
+            (false, 7, 4) => Some(Box::new(Hamming74)),
+            (false, 15, 11) => Some(Box::new(Hamming1511)),
+            (false, 127, 120) => Some(Box::new(Hamming127120)),
+            _ => general(n, k, extended),
+        }
+    }
+
+    /// Codec with `n`-bit blocks of `k` data bits: a registered one, else a
+    /// built-in Hamming code, else its SEC-DED extension
+    pub fn codec_by_params(&self, n: usize, k: usize) -> Option<Codec> {
+        if let Some(entry) = self
+            .custom
+            .iter()
+            .rev()
+            .find(|e| (e.block_size, e.data_bits) == (n, k))
+        {
+            return Some((entry.factory)());
+        }
+        match (n, k) {
+            (7, 4) => Some(Box::new(Hamming74)),
+            (15, 11) => Some(Box::new(Hamming1511)),
+            (127, 120) => Some(Box::new(Hamming127120)),
+            _ => general(n, k, false).or_else(|| general(n, k, true)),
+        }
+    }
+
+    /// Names of the registered codes
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.custom.iter().map(|e| e.name.as_str())
+    }
+}
+
+// The general code with `k` data bits, if its blocks have `n` bits
+fn general(n: usize, k: usize, extended: bool) -> Option<Codec> {
+    let code = Hamming::try_new(k).ok()?.with_extended(extended);
+    (code.block_size() == n).then(|| Box::new(code) as Codec)
+}
+
+impl fmt::Debug for CodecRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CodecRegistry")
+            .field("custom", &self.names().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Interleaved;
+    use alloc::vec;
+
+    #[test]
+    fn test_registry_builtin() {
+        let registry = CodecRegistry::new();
+        for (name, n, k) in [
+            ("hamming-7-4", 7, 4),
+            ("hamming-15-11", 15, 11),
+            ("hamming-31-26", 31, 26),
+            ("secded-72-64", 72, 64),
+            ("general:26", 31, 26),
+            ("74", 7, 4),
+        ] {
+            let code = registry.codec_by_name(name).unwrap();
+            assert_eq!((code.block_size(), code.data_bits()), (n, k), "{name}");
+            let code = registry.codec_by_params(n, k).unwrap();
+            assert_eq!((code.block_size(), code.data_bits()), (n, k));
+        }
+
+        for name in [
+            "hamming-7-5",
+            "hamming-0-0",
+            "secded-7-4",
+            "golay-23-12",
+            "hamming-x",
+        ] {
+            assert!(registry.codec_by_name(name).is_none(), "{name}");
+        }
+        assert!(registry.codec_by_params(9, 4).is_none());
+    }
+
+    #[test]
+    fn test_registry_custom() {
+        let mut registry = CodecRegistry::new();
+        registry.register("dram-74", || Box::new(Interleaved::new(Hamming74, 2)));
+        assert_eq!(registry.names().collect::<Vec<_>>(), vec!["dram-74"]);
+
+        let data = b"plugin";
+        let code = registry.codec_by_name("dram-74").unwrap();
+        assert_eq!(
+            code.encode(data),
+            Interleaved::new(Hamming74, 2).encode(data)
+        );
+        // Registered codes come first
+        let code = registry.codec_by_params(7, 4).unwrap();
+        assert_ne!(code.encode(data), Hamming74.encode(data));
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{BlockOutcome, HammingCode, HammingError};
+use alloc::vec::Vec;
+
+/// How far a [`ResumableDecode`] has got
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DecodeProgress {
+    /// Encoded bytes decoded so far
+    pub consumed: usize,
+    /// Encoded bytes in total
+    pub total: usize,
+    /// Blocks decoded so far
+    pub blocks: usize,
+    /// Bits corrected so far
+    pub corrected: usize,
+}
+
+impl DecodeProgress {
+    /// Whether the whole stream has been decoded
+    pub fn is_done(&self) -> bool {
+        self.consumed >= self.total
+    }
+}
+
+/// A decode spread over several calls, see
+/// [`HammingCode::resumable_decode`]
+///
+/// Each [`step`](ResumableDecode::step) decodes at most `max_blocks` more
+/// blocks, so a long decode can share a cooperative main loop or executor
+/// with other work. Steps cut the stream on [`HammingCode::chunk_sizes`]
+/// boundaries, so a step decodes at least one chunk, even if that holds
+/// more than `max_blocks` blocks. The data joined is what
+/// [`HammingCode::decode`] returns.
+#[derive(Debug)]
+pub struct ResumableDecode<'a, C: ?Sized> {
+    code: &'a C,
+    encoded: &'a [u8],
+    decoded: Vec<u8>,
+    progress: DecodeProgress,
+}
+
+impl<'a, C: HammingCode + ?Sized> ResumableDecode<'a, C> {
+    pub(crate) fn new(code: &'a C, encoded: &'a [u8]) -> Self {
+        Self {
+            code,
+            encoded,
+            decoded: Vec::with_capacity(encoded.len()),
+            progress: DecodeProgress {
+                total: encoded.len(),
+                ..DecodeProgress::default()
+            },
+        }
+    }
+
+    pub fn progress(&self) -> DecodeProgress {
+        self.progress
+    }
+
+    /// Data decoded so far
+    pub fn decoded(&self) -> &[u8] {
+        &self.decoded
+    }
+
+    /// Decode at most `max_blocks` more blocks, rounded up to a whole chunk
+    ///
+    /// Once the stream is decoded, further steps do nothing. An
+    /// uncorrectable block fails the step, with block numbers counted from
+    /// the start of the stream, and leaves the decode where it was.
+    pub fn step(&mut self, max_blocks: usize) -> Result<DecodeProgress, HammingError> {
+        let rest = self.progress.total - self.progress.consumed;
+        if rest == 0 {
+            return Ok(self.progress);
+        }
+
+        let (_, chunk) = self.code.chunk_sizes();
+        let chunk = chunk.max(1);
+        let chunk_blocks = (chunk * 8 / self.code.block_stride().max(1)).max(1);
+        let len = (max_blocks / chunk_blocks)
+            .max(1)
+            .saturating_mul(chunk)
+            .min(rest);
+        let start = self.progress.consumed;
+        let piece = self.encoded.get(start..start + len).unwrap_or_default();
+
+        let (base, mut blocks, mut corrected) = (self.progress.blocks, 0, 0);
+        let part = self
+            .code
+            .decode_observed(piece, &mut |event| {
+                blocks += 1;
+                if let BlockOutcome::Corrected { .. } = event.outcome {
+                    corrected += 1;
+                }
+            })
+            .map_err(|err| match err {
+                HammingError::DoubleErrorDetected { block } => HammingError::DoubleErrorDetected {
+                    block: block + base,
+                },
+                err => err,
+            })?;
+
+        self.decoded.extend(part);
+        self.progress.consumed += len;
+        self.progress.blocks += blocks;
+        self.progress.corrected += corrected;
+        Ok(self.progress)
+    }
+
+    /// Decode whatever is left in one go and return the data
+    pub fn finish(mut self) -> Result<Vec<u8>, HammingError> {
+        self.step(usize::MAX)?;
+        Ok(self.decoded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Hamming, Hamming1511, HammingCode, HammingError, flip_bit, sample_codecs};
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_resumable_matches_decode() {
+        let data: Vec<u8> = (0..1000).map(|i| (i * 37 + i / 5) as u8).collect();
+        let codecs = sample_codecs();
+        for codec in &codecs {
+            for len in [0, 1, 11, 100, 1000] {
+                let mut encoded = codec.encode(&data[..len]);
+                if len > 0 {
+                    flip_bit(&mut encoded, 0, 2);
+                }
+                for max_blocks in [0, 1, 8, 50, 1000] {
+                    let mut decode = codec.resumable_decode(&encoded);
+                    let mut steps = 0;
+                    while !decode.step(max_blocks).unwrap().is_done() {
+                        steps += 1;
+                    }
+                    assert!(steps <= encoded.len());
+                    assert_eq!(decode.progress().corrected, usize::from(len > 0));
+                    assert_eq!(decode.decoded(), codec.decode(&encoded).unwrap());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_resumable_steps() {
+        // 8 blocks of 16 bits, spare bit included, in each 16-byte chunk
+        let encoded = Hamming1511.encode(&[0xA5; 110]);
+        let mut decode = Hamming1511.resumable_decode(&encoded);
+        let progress = decode.step(20).unwrap();
+        assert_eq!((progress.consumed, progress.blocks), (32, 16));
+        assert_eq!(decode.decoded(), [0xA5; 22]);
+        assert_eq!(decode.finish().unwrap(), [0xA5; 110]);
+
+        let code = Hamming::new(64).with_extended(true);
+        let mut encoded = code.encode(&[0x5A; 64]);
+        encoded[50] ^= 0x03;
+        let mut decode = code.resumable_decode(&encoded);
+        assert!(decode.step(5).is_ok());
+        for _ in 0..2 {
+            assert_eq!(
+                decode.step(5),
+                Err(HammingError::DoubleErrorDetected { block: 5 })
+            );
+        }
+        assert_eq!(decode.progress().blocks, 5);
+    }
+}
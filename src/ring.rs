@@ -0,0 +1,161 @@
+use crate::{AsBytes, EccCell, HammingError, Health, Protected};
+use std::fmt;
+
+/// Fixed-capacity ring buffer of records kept Hamming(7,4)-encoded, e.g. a
+/// black-box recorder's log
+///
+/// Every record is a [`Protected`] value, so a bit flip between write and
+/// readout costs a correction rather than the record. Once full, a push
+/// overwrites the oldest record. The write position is an [`EccCell`]; if
+/// it is ever lost to a double error, the buffer keeps going from slot 0
+/// and only the order of the readout suffers.
+pub struct EccRing<T> {
+    slots: Vec<Protected<T>>,
+    capacity: usize,
+    // Slot the next push overwrites once the buffer is full
+    next: EccCell<u64>,
+    // Counters of slots dropped by `clear`
+    cleared: Health,
+}
+
+impl<T: AsBytes> EccRing<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            slots: Vec::with_capacity(capacity),
+            capacity,
+            next: EccCell::new(0),
+            cleared: Health::default(),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// Append `record`, overwriting the oldest one if the buffer is full
+    pub fn push(&mut self, record: T) {
+        if self.slots.len() < self.capacity {
+            self.slots.push(Protected::new(record));
+            return;
+        }
+        let next = self.next();
+        if let Some(slot) = self.slots.get_mut(next) {
+            slot.set(record);
+            self.next.store(((next + 1) % self.capacity) as u64);
+        }
+    }
+
+    /// The corrected record `index` places after the oldest one
+    pub fn get(&mut self, index: usize) -> Option<Result<T, HammingError>> {
+        let slot = self.slot(index)?;
+        self.slots.get_mut(slot).map(Protected::get)
+    }
+
+    /// All records, oldest first, each corrected on its own
+    pub fn records(&mut self) -> Vec<Result<T, HammingError>> {
+        (0..self.len()).filter_map(|i| self.get(i)).collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.cleared = self.health();
+        self.slots.clear();
+        self.next.store(0);
+    }
+
+    /// Bits corrected since the buffer was created
+    pub fn corrections(&self) -> u64 {
+        self.health().corrections
+    }
+
+    /// Corruption counters of all slots since the buffer was created
+    pub fn health(&self) -> Health {
+        let mut health = self.cleared;
+        for slot in &self.slots {
+            health.merge(&slot.health());
+        }
+        health
+    }
+
+    /// The encoded storage of record `index`, for fault injection
+    pub fn encoded_mut(&mut self, index: usize) -> Option<&mut [u8]> {
+        let slot = self.slot(index)?;
+        self.slots.get_mut(slot).map(Protected::encoded_mut)
+    }
+
+    // Slot of the record `index` places after the oldest one
+    fn slot(&self, index: usize) -> Option<usize> {
+        (index < self.slots.len()).then(|| (self.next() + index) % self.slots.len())
+    }
+
+    // Oldest slot, which the next push overwrites once the buffer is full
+    fn next(&self) -> usize {
+        let next = self.next.load().map_or(0, |(next, _)| next as usize);
+        next % self.slots.len().max(1)
+    }
+}
+
+impl<T> fmt::Debug for EccRing<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EccRing")
+            .field("len", &self.slots.len())
+            .field("capacity", &self.capacity)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_overwrites_oldest() {
+        let mut ring = EccRing::new(3);
+        assert!(ring.is_empty());
+        for record in 1..=5u32 {
+            ring.push(record);
+        }
+        assert_eq!(ring.len(), 3);
+        assert_eq!(ring.records(), vec![Ok(3), Ok(4), Ok(5)]);
+        assert_eq!(ring.get(0), Some(Ok(3)));
+        assert_eq!(ring.get(3), None);
+
+        ring.clear();
+        ring.push(9);
+        assert_eq!(ring.records(), vec![Ok(9)]);
+
+        let mut empty = EccRing::new(0);
+        empty.push(1u8);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_ring_corrects_records() {
+        let mut ring = EccRing::new(4);
+        for record in [[1u16, 2], [3, 4], [5, 6], [7, 8], [9, 10]] {
+            ring.push(record);
+        }
+        // One flip per codeword of the oldest record, and one in the newest
+        for byte in ring.encoded_mut(0).unwrap() {
+            *byte ^= 0x10;
+        }
+        ring.encoded_mut(3).unwrap()[2] ^= 0x01;
+
+        assert_eq!(
+            ring.records(),
+            vec![Ok([3, 4]), Ok([5, 6]), Ok([7, 8]), Ok([9, 10])]
+        );
+        assert_eq!(ring.corrections(), 9);
+        assert_eq!(ring.records()[0], Ok([3, 4]));
+        assert_eq!(ring.corrections(), 9);
+        ring.clear();
+        assert_eq!(ring.health().corrections, 9);
+    }
+}
@@ -0,0 +1,67 @@
+use alloc::vec::Vec;
+/// Additive LFSR scrambler (data whitening)
+///
+/// The polynomial is given as a bit mask with bit `i` set for each term `x^i`,
+/// e.g. `x^15 + x^14 + 1` is `0xC001`. Scrambling is its own inverse.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Scrambler {
+    poly: u32,
+    seed: u32,
+}
+
+impl Scrambler {
+    pub fn new(poly: u32, seed: u32) -> Self {
+        Self { poly, seed }
+    }
+
+    /// Degree of the feedback polynomial
+    pub fn degree(&self) -> u32 {
+        31 - self.poly.leading_zeros().min(31)
+    }
+
+    /// XOR the data with the LFSR keystream (LSB-first within each byte)
+    pub fn apply(&self, data: &[u8]) -> Vec<u8> {
+        let degree = self.degree();
+        let mask = (1u32 << degree) - 1;
+        let taps = (self.poly >> 1) & mask;
+        let mut state = self.seed & mask;
+
+        let mut out = Vec::with_capacity(data.len());
+        for &byte in data {
+            let mut key = 0u8;
+            for bit_idx in 0..8 {
+                let bit = (state & taps).count_ones() & 1;
+                state = ((state << 1) | bit) & mask;
+                key |= (bit as u8) << bit_idx;
+            }
+            out.push(byte ^ key);
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scrambler_roundtrip() {
+        let s = Scrambler::new(0xC001, 0x4A80);
+        let data = b"Hello, scrambler!".to_vec();
+
+        let scrambled = s.apply(&data);
+        assert_ne!(scrambled, data);
+        assert_eq!(s.apply(&scrambled), data);
+    }
+
+    #[test]
+    fn test_scrambler_whitens_zeros() {
+        let s = Scrambler::new(0x91, 0x7F); // x^7 + x^4 + 1
+        let scrambled = s.apply(&[0u8; 32]);
+
+        // A maximal-length 7-bit LFSR has period 127, so the keystream is balanced
+        let ones: u32 = scrambled.iter().map(|b| b.count_ones()).sum();
+        assert!(ones > 96 && ones < 160);
+    }
+}
@@ -0,0 +1,208 @@
+use crate::{EccVec, MemoryRegion, telemetry};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Protected storage a [`Scrubber`] can check
+pub trait Scrub {
+    /// Check all of the storage, writing corrections back
+    fn scrub_all(&mut self) -> ScrubReport;
+}
+
+/// Result of scrubbing, added up over targets and passes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ScrubReport {
+    /// Elements checked: bytes of an [`EccVec`], words of a [`MemoryRegion`]
+    pub checked: u64,
+    /// Single-bit errors corrected
+    pub corrected: u64,
+    /// Words with more errors than the code corrects
+    pub uncorrectable: u64,
+}
+
+impl ScrubReport {
+    fn add(&mut self, other: &ScrubReport) {
+        self.checked += other.checked;
+        self.corrected += other.corrected;
+        self.uncorrectable += other.uncorrectable;
+    }
+}
+
+impl Scrub for EccVec {
+    fn scrub_all(&mut self) -> ScrubReport {
+        ScrubReport {
+            checked: self.len() as u64,
+            corrected: self.scrub() as u64,
+            uncorrectable: 0,
+        }
+    }
+}
+
+impl Scrub for MemoryRegion {
+    /// Scrubs every word, continuing the region's own scrub cycle; the
+    /// counts are also added to [`MemoryRegion::stats`]
+    fn scrub_all(&mut self) -> ScrubReport {
+        let before = self.stats();
+        self.scrub(self.len());
+        let after = self.stats();
+        ScrubReport {
+            checked: after.scrubbed_words - before.scrubbed_words,
+            corrected: after.scrub_corrections - before.scrub_corrections,
+            uncorrectable: after.scrub_failures - before.scrub_failures,
+        }
+    }
+}
+
+/// Shared storage registered with a [`Scrubber`]
+pub type ScrubTarget = Arc<Mutex<dyn Scrub + Send>>;
+
+type Observer = Box<dyn FnMut(&ScrubReport) + Send>;
+
+/// Periodically scrubs registered storage so latent single-bit errors are
+/// corrected before a second upset makes them uncorrectable
+///
+/// Targets are shared as `Arc<Mutex<_>>`, so the application keeps using
+/// them between passes. [`Scrubber::spawn`] runs the passes on a thread; in
+/// an async runtime, call [`Scrubber::scrub_once`] from a periodic task
+/// instead. Every pass is reported to the observer, and with the `metrics`
+/// feature to the `hamming_scrub_corrections` and `hamming_scrub_failures`
+/// counters.
+pub struct Scrubber {
+    targets: Vec<ScrubTarget>,
+    interval: Duration,
+    observer: Option<Observer>,
+    total: ScrubReport,
+}
+
+impl Scrubber {
+    /// Scrubber running a pass every `interval` once spawned
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            targets: Vec::new(),
+            interval,
+            observer: None,
+            total: ScrubReport::default(),
+        }
+    }
+
+    /// Call `observer` with the report of every pass
+    pub fn with_observer(mut self, observer: impl FnMut(&ScrubReport) + Send + 'static) -> Self {
+        self.observer = Some(Box::new(observer));
+        self
+    }
+
+    pub fn register(&mut self, target: ScrubTarget) {
+        self.targets.push(target);
+    }
+
+    /// Scrub every target once, locking one at a time
+    pub fn scrub_once(&mut self) -> ScrubReport {
+        let mut report = ScrubReport::default();
+        for target in &self.targets {
+            // A panic elsewhere does not make the storage less worth scrubbing
+            let mut target = target.lock().unwrap_or_else(PoisonError::into_inner);
+            report.add(&target.scrub_all());
+        }
+
+        self.total.add(&report);
+        telemetry::scrubbed(&report);
+        if let Some(observer) = &mut self.observer {
+            observer(&report);
+        }
+        report
+    }
+
+    /// Totals over all passes so far
+    pub fn total(&self) -> ScrubReport {
+        self.total
+    }
+
+    /// Run a pass now and then every interval on a new thread, until the
+    /// handle is stopped or dropped
+    pub fn spawn(mut self) -> ScrubberHandle {
+        let (stop, stopped) = mpsc::channel();
+        let thread = thread::spawn(move || {
+            loop {
+                self.scrub_once();
+                match stopped.recv_timeout(self.interval) {
+                    Err(RecvTimeoutError::Timeout) => continue,
+                    _ => return self,
+                }
+            }
+        });
+        ScrubberHandle { stop, thread }
+    }
+}
+
+/// A [`Scrubber`] running on its own thread
+pub struct ScrubberHandle {
+    stop: Sender<()>,
+    thread: JoinHandle<Scrubber>,
+}
+
+impl ScrubberHandle {
+    /// Stop after the current pass and return the scrubber, or `None` if
+    /// the observer panicked
+    pub fn stop(self) -> Option<Scrubber> {
+        let _ = self.stop.send(());
+        self.thread.join().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scrub_once() {
+        let vec = Arc::new(Mutex::new(EccVec::from(&b"scrub me"[..])));
+        let memory = Arc::new(Mutex::new(MemoryRegion::new(16)));
+        let mut scrubber = Scrubber::new(Duration::from_secs(1));
+        scrubber.register(vec.clone());
+        scrubber.register(memory.clone());
+
+        vec.lock().unwrap().encoded_mut()[3] ^= 0x02;
+        memory.lock().unwrap().upset(2, 5);
+        memory.lock().unwrap().upset(9, 1);
+        memory.lock().unwrap().upset(9, 60);
+        let report = scrubber.scrub_once();
+        assert_eq!(
+            report,
+            ScrubReport {
+                checked: 24,
+                corrected: 2,
+                uncorrectable: 1
+            }
+        );
+        assert_eq!(
+            vec.lock().unwrap().encoded(),
+            EccVec::from(&b"scrub me"[..]).encoded()
+        );
+        assert_eq!(memory.lock().unwrap().stats().scrub_corrections, 1);
+
+        scrubber.scrub_once();
+        assert_eq!(scrubber.total().corrected, 2);
+        assert_eq!(scrubber.total().uncorrectable, 2);
+    }
+
+    #[test]
+    fn test_scrubber_thread() {
+        let memory = Arc::new(Mutex::new(MemoryRegion::new(4)));
+        let (passes, seen) = mpsc::channel();
+        let mut scrubber = Scrubber::new(Duration::from_millis(1)).with_observer(move |report| {
+            let _ = passes.send(*report);
+        });
+        scrubber.register(memory.clone());
+        memory.lock().unwrap().upset(1, 7);
+
+        let handle = scrubber.spawn();
+        // Wait for a few passes
+        assert_eq!(seen.recv().unwrap().corrected, 1);
+        assert_eq!(seen.recv().unwrap().corrected, 0);
+        let scrubber = handle.stop().unwrap();
+        assert!(scrubber.total().checked >= 8);
+        assert_eq!(memory.lock().unwrap().read(1), Ok(0));
+    }
+}
@@ -0,0 +1,87 @@
+use alloc::vec::Vec;
+/// Overall result of [`Hamming::decode_secded`](crate::Hamming::decode_secded)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SecdedStatus {
+    /// Every block decoded without correction
+    Clean,
+    /// Single errors were corrected; the data is good
+    Corrected,
+    /// At least one block held a double error; the data is suspect
+    DoubleErrorDetected,
+}
+
+/// Data of a SEC-DED decode, with what it took to get it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SecdedDecode {
+    pub data: Vec<u8>,
+    /// Blocks with a corrected single error
+    pub corrected: usize,
+    /// Indices of the blocks with a detected double error, whose data bits
+    /// are as received
+    pub double_errors: Vec<usize>,
+}
+
+impl SecdedDecode {
+    pub fn status(&self) -> SecdedStatus {
+        if !self.double_errors.is_empty() {
+            SecdedStatus::DoubleErrorDetected
+        } else if self.corrected > 0 {
+            SecdedStatus::Corrected
+        } else {
+            SecdedStatus::Clean
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Hamming, HammingCode, HammingError};
+
+    #[test]
+    fn test_decode_secded_status() {
+        let code = Hamming::new(64).with_extended(true);
+        let data: Vec<u8> = (0..32).map(|i| i * 5 + 1).collect();
+        let mut encoded = code.encode(&data);
+        let block_bytes = code.block_size() / 8;
+
+        let clean = code.decode_secded(&encoded).unwrap();
+        assert_eq!(
+            (clean.status(), clean.data),
+            (SecdedStatus::Clean, data.clone())
+        );
+
+        encoded[3] ^= 0x10;
+        let corrected = code.decode_secded(&encoded).unwrap();
+        assert_eq!(corrected.status(), SecdedStatus::Corrected);
+        assert_eq!((corrected.corrected, corrected.data), (1, data.clone()));
+
+        // A double error in block 2 is reported, not fatal; the other
+        // blocks still decode
+        encoded[2 * block_bytes + 1] ^= 0x81;
+        let suspect = code.decode_secded(&encoded).unwrap();
+        assert_eq!(suspect.status(), SecdedStatus::DoubleErrorDetected);
+        assert_eq!(suspect.double_errors, [2]);
+        assert_eq!(suspect.data[..16], data[..16]);
+        assert_eq!(suspect.data[24..], data[24..]);
+        assert_eq!(
+            code.decode(&encoded),
+            Err(HammingError::DoubleErrorDetected { block: 2 })
+        );
+    }
+
+    #[test]
+    fn test_decode_secded_plain_code() {
+        let code = Hamming::new(11);
+        let mut encoded = code.encode(b"plain");
+        encoded[0] ^= 0x04;
+        let decoded = code.decode_secded(&encoded).unwrap();
+        assert_eq!(decoded.status(), SecdedStatus::Corrected);
+        assert!(decoded.data.starts_with(b"plain"));
+        assert_eq!(
+            code.decode_secded(&[]).unwrap().status(),
+            SecdedStatus::Clean
+        );
+    }
+}
@@ -0,0 +1,336 @@
+#[cfg(feature = "alloc")]
+use crate::HammingCode;
+use crate::bits::SliceSink;
+#[cfg(feature = "alloc")]
+use crate::bits::VecSink;
+use crate::cell::{check_bits, correct, syndrome};
+use crate::{BlockEvent, BlockOutcome, HammingError, telemetry};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+// Encoded bytes per block: the data word, then its check byte
+const BLOCK_BYTES: usize = 9;
+
+/// SEC-DED(72,64), the code of ECC memory: 64-bit words stored as they are
+/// next to 8 check bits
+///
+/// The check byte holds 7 Hamming check bits and, in bit 7, the overall
+/// parity, the same code as `Hamming::new(64).with_extended(true)` with the
+/// data and check bits kept apart. The word API
+/// ([`Secded7264::encode_word`], [`Secded7264::decode_word`]) works on a
+/// `u64` and its check byte, as a memory controller stores them; the byte
+/// stream of [`HammingCode`] is each word's 8 data bytes (little-endian)
+/// followed by its check byte.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Secded7264;
+
+#[cfg(feature = "alloc")]
+impl core::fmt::Display for Secded7264 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.describe())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl HammingCode for Secded7264 {
+    fn encode(&self, data: &[u8]) -> Vec<u8> {
+        let mut encoded = Vec::with_capacity(Self::encoded_len(data.len()));
+        Self::encode_blocks(data, |block| encoded.extend_from_slice(&block));
+        encoded
+    }
+
+    fn decode_observed(
+        &self,
+        encoded: &[u8],
+        observer: &mut dyn FnMut(&BlockEvent),
+    ) -> Result<Vec<u8>, HammingError> {
+        if !encoded.len().is_multiple_of(BLOCK_BYTES) {
+            return Err(HammingError::InvalidLength);
+        }
+
+        telemetry::instrumented(observer, |observer| {
+            let decoded = Vec::with_capacity(Self::decoded_len(encoded.len()));
+            Self::decode_blocks(encoded, observer, decoded)
+        })
+    }
+
+    fn encode_into(&self, data: &[u8], out: &mut Vec<u8>) {
+        out.reserve(Self::encoded_len(data.len()));
+        Self::encode_blocks(data, |block| out.extend_from_slice(&block));
+    }
+
+    fn decode_into(&self, encoded: &[u8], out: &mut Vec<u8>) -> Result<(), HammingError> {
+        if !encoded.len().is_multiple_of(BLOCK_BYTES) {
+            return Err(HammingError::InvalidLength);
+        }
+
+        let start = out.len();
+        out.reserve(Self::decoded_len(encoded.len()));
+        telemetry::instrumented(&mut |_| {}, |observer| {
+            Self::decode_blocks(encoded, observer, VecSink(&mut *out)).map(drop)
+        })
+        .inspect_err(|_| out.truncate(start))
+    }
+
+    fn encode_to_slice(&self, data: &[u8], out: &mut [u8]) -> Result<usize, HammingError> {
+        Secded7264::encode_to_slice(self, data, out)
+    }
+
+    fn decode_to_slice(&self, encoded: &[u8], out: &mut [u8]) -> Result<usize, HammingError> {
+        Secded7264::decode_to_slice(self, encoded, out)
+    }
+
+    fn block_size(&self) -> usize {
+        72
+    }
+
+    fn data_bits(&self) -> usize {
+        64
+    }
+
+    fn detects_double_errors(&self) -> bool {
+        true
+    }
+
+    fn is_systematic(&self) -> bool {
+        true
+    }
+}
+
+impl Secded7264 {
+    /// Encoded size in bytes for `data_len` input bytes
+    pub const fn encoded_len(data_len: usize) -> usize {
+        data_len.div_ceil(8) * BLOCK_BYTES
+    }
+
+    /// Decoded size in bytes for `encoded_len` encoded bytes
+    pub const fn decoded_len(encoded_len: usize) -> usize {
+        encoded_len / BLOCK_BYTES * 8
+    }
+
+    /// Encode into the front of `out`, returning the bytes written, or
+    /// [`HammingError::BufferTooSmall`] if they do not fit
+    pub fn encode_to_slice(&self, data: &[u8], out: &mut [u8]) -> Result<usize, HammingError> {
+        let len = Self::encoded_len(data.len());
+        let mut blocks = out
+            .get_mut(..len)
+            .ok_or(HammingError::BufferTooSmall)?
+            .as_chunks_mut::<BLOCK_BYTES>()
+            .0
+            .iter_mut();
+        Self::encode_blocks(data, |block| {
+            if let Some(bytes) = blocks.next() {
+                *bytes = block;
+            }
+        });
+        Ok(len)
+    }
+
+    /// Decode into the front of `out`, returning the bytes written, or
+    /// [`HammingError::BufferTooSmall`] if they do not fit
+    pub fn decode_to_slice(&self, encoded: &[u8], out: &mut [u8]) -> Result<usize, HammingError> {
+        if !encoded.len().is_multiple_of(BLOCK_BYTES) {
+            return Err(HammingError::InvalidLength);
+        }
+        let len = Self::decoded_len(encoded.len());
+        let out = out.get_mut(..len).ok_or(HammingError::BufferTooSmall)?;
+
+        telemetry::instrumented(&mut |_| {}, |observer| {
+            Ok(Self::decode_blocks(encoded, observer, SliceSink::new(out))?.len())
+        })
+    }
+
+    /// Check byte of `word`: Hamming check bits in bits 0..7, overall
+    /// parity in bit 7
+    pub fn check_bits(word: u64) -> u8 {
+        check_bits::<u64>(word)
+    }
+
+    /// `word` and its check byte, to be stored side by side
+    pub fn encode_word(word: u64) -> (u64, u8) {
+        (word, Self::check_bits(word))
+    }
+
+    /// Correct `word` stored with `check`, returning the word and what was
+    /// done to it
+    ///
+    /// [`BlockOutcome::Corrected`] counts data bits 0..64, then check bits
+    /// 64..72. Two flipped bits fail with
+    /// [`HammingError::DoubleErrorDetected`] (as block 0), a syndrome
+    /// pointing outside the codeword with
+    /// [`HammingError::UncorrectableErrors`].
+    pub fn decode_word(word: u64, check: u8) -> Result<(u64, BlockOutcome), HammingError> {
+        let (word, fixed) = correct::<u64>(word, check)?;
+        let outcome = match fixed {
+            Some(bit) => BlockOutcome::Corrected { bit: bit as usize },
+            None => BlockOutcome::Clean,
+        };
+        Ok((word, outcome))
+    }
+
+    // One encoded block per 8 data bytes; the last word is padded with zero
+    // bytes
+    fn encode_blocks(data: &[u8], mut emit: impl FnMut([u8; BLOCK_BYTES])) {
+        for word in data.chunks(8) {
+            let mut bytes = [0; BLOCK_BYTES];
+            bytes
+                .get_mut(..word.len())
+                .unwrap_or_default()
+                .copy_from_slice(word);
+            let word = u64::from_le_bytes(bytes.first_chunk().copied().unwrap_or_default());
+            if let Some(check) = bytes.last_mut() {
+                *check = Self::check_bits(word);
+            }
+            emit(bytes);
+        }
+    }
+
+    // Decode 9-byte blocks, reporting each one, and append their words to
+    // `out`
+    fn decode_blocks<B: Extend<u8>>(
+        encoded: &[u8],
+        observer: &mut dyn FnMut(&BlockEvent),
+        mut out: B,
+    ) -> Result<B, HammingError> {
+        for (block, bytes) in encoded.as_chunks::<BLOCK_BYTES>().0.iter().enumerate() {
+            let (word, check) = bytes.split_at(8);
+            let word = u64::from_le_bytes(word.try_into().unwrap_or_default());
+            let check = check.first().copied().unwrap_or_default();
+
+            let result = correct::<u64>(word, check);
+            observer(&BlockEvent {
+                block,
+                bit_offset: block * BLOCK_BYTES * 8,
+                syndrome: usize::from(syndrome::<u64>(word, check)),
+                outcome: match result {
+                    Ok((_, None)) => BlockOutcome::Clean,
+                    Ok((_, Some(bit))) => BlockOutcome::Corrected { bit: bit as usize },
+                    Err(_) => BlockOutcome::Uncorrectable,
+                },
+            });
+            let (word, _) = result.map_err(|err| match err {
+                HammingError::DoubleErrorDetected { .. } => {
+                    HammingError::DoubleErrorDetected { block }
+                }
+                err => err,
+            })?;
+            out.extend(word.to_le_bytes());
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::{Hamming, flip_bit};
+
+    #[test]
+    fn test_secded7264_words() {
+        let word = 0x0123_4567_89AB_CDEF;
+        let (stored, check) = Secded7264::encode_word(word);
+        assert_eq!(stored, word);
+        assert_eq!(
+            Secded7264::decode_word(stored, check),
+            Ok((word, BlockOutcome::Clean))
+        );
+        for bit in 0..64 {
+            assert_eq!(
+                Secded7264::decode_word(word ^ (1 << bit), check),
+                Ok((word, BlockOutcome::Corrected { bit }))
+            );
+        }
+        for bit in 0..8 {
+            assert_eq!(
+                Secded7264::decode_word(word, check ^ (1 << bit)),
+                Ok((word, BlockOutcome::Corrected { bit: 64 + bit }))
+            );
+        }
+        assert_eq!(
+            Secded7264::decode_word(word ^ 0x11, check),
+            Err(HammingError::DoubleErrorDetected { block: 0 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_secded7264_matches_general() {
+        // The check byte is that of the extended Hamming code, gathered from
+        // the power-of-two positions and the overall parity at the top
+        let word = 0x0123_4567_89AB_CDEF;
+        let (_, check) = Secded7264::encode_word(word);
+        let codeword = Hamming::new(64)
+            .with_extended(true)
+            .encode_word(word.into());
+        let gathered = [0, 1, 3, 7, 15, 31, 63, 71]
+            .iter()
+            .enumerate()
+            .fold(0, |check, (j, &bit)| {
+                check | ((codeword >> bit) as u8 & 1) << j
+            });
+        assert_eq!(gathered, check);
+    }
+
+    #[test]
+    fn test_secded7264_slices() {
+        let data: [u8; 20] = core::array::from_fn(|i| (i as u8).wrapping_mul(41));
+        let mut encoded = [0; Secded7264::encoded_len(20)];
+        assert_eq!(Secded7264.encode_to_slice(&data, &mut encoded), Ok(27));
+        assert_eq!(encoded[..8], data[..8]);
+
+        encoded[11] ^= 0x04;
+        encoded[26] ^= 0x01;
+        let mut decoded = [0; Secded7264::decoded_len(27)];
+        assert_eq!(Secded7264.decode_to_slice(&encoded, &mut decoded), Ok(24));
+        assert_eq!(decoded[..20], data);
+        assert_eq!(
+            Secded7264.decode_to_slice(&encoded, &mut decoded[..23]),
+            Err(HammingError::BufferTooSmall)
+        );
+
+        encoded[12] ^= 0x20;
+        assert_eq!(
+            Secded7264.decode_to_slice(&encoded, &mut decoded),
+            Err(HammingError::DoubleErrorDetected { block: 1 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_secded7264_stream() {
+        let data: Vec<u8> = (0..20u8).map(|i| i.wrapping_mul(41)).collect();
+        let mut encoded = Secded7264.encode(&data);
+        assert_eq!(encoded.len(), Secded7264::encoded_len(20));
+        assert_eq!(encoded[..8], data[..8]);
+
+        flip_bit(&mut encoded, 11, 2);
+        flip_bit(&mut encoded, 26, 0);
+        let offsets: Vec<usize> = Secded7264
+            .correction_offsets(&encoded)
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(offsets, [11 * 8 + 2, 26 * 8]);
+        let decoded = Secded7264.decode(&encoded).unwrap();
+        assert_eq!(decoded[..20], data);
+        assert_eq!(decoded.len(), 24);
+        let mut out = [0; Secded7264::decoded_len(27)];
+        assert_eq!(Secded7264.decode_to_slice(&encoded, &mut out), Ok(24));
+        assert_eq!(out[..], decoded[..]);
+        let mut out = [0; Secded7264::encoded_len(20)];
+        assert_eq!(Secded7264.encode_to_slice(&data, &mut out), Ok(27));
+        assert_eq!(out[..], Secded7264.encode(&data)[..]);
+
+        flip_bit(&mut encoded, 12, 5);
+        assert_eq!(
+            Secded7264.decode(&encoded),
+            Err(HammingError::DoubleErrorDetected { block: 1 })
+        );
+        assert_eq!(
+            Secded7264.decode(&encoded[..10]),
+            Err(HammingError::InvalidLength)
+        );
+        assert_eq!(Secded7264.name(), "secded-72-64");
+    }
+}
@@ -0,0 +1,268 @@
+use crate::record::crc32;
+use crate::sidecar::{PREFIX_LEN, header, parse_header};
+use crate::{CodeSpec, Hamming74, HammingCode, HammingError};
+use std::ops::Range;
+
+const MAGIC: &[u8; 4] = b"HSEK";
+// Segment length (u32) before encoding
+const SEGMENT_LEN_BYTES: usize = 4;
+// Offset (u64) and CRC-32 (u32) of a segment before encoding
+const ENTRY_BYTES: usize = 12;
+
+/// Where a segment of a seekable stream is stored, and the CRC-32 of its
+/// data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct IndexEntry {
+    /// Offset of the encoded segment from the start of the stream
+    pub offset: u64,
+    pub crc: u32,
+}
+
+/// Index of a stream written by [`encode_seekable`]
+///
+/// The data is cut into segments of `segment_len` bytes, each encoded on its
+/// own. A header naming the code and an index of the segments' offsets and
+/// CRCs, both Hamming(7,4) protected, come first, so any byte range can be
+/// read by decoding just the segments covering it, and a damaged segment
+/// can be repaired without touching the rest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeekableIndex {
+    spec: CodeSpec,
+    data_len: u64,
+    segment_len: usize,
+    entries: Vec<IndexEntry>,
+}
+
+/// Encode `data` with the code `spec` selects in independently decodable
+/// segments of `segment_len` bytes (at least 1), behind a block index
+pub fn encode_seekable(spec: &CodeSpec, data: &[u8], segment_len: usize) -> Vec<u8> {
+    let segment_len = segment_len.clamp(1, u32::MAX as usize);
+    let code = spec.build();
+    let segments: Vec<Vec<u8>> = data
+        .chunks(segment_len)
+        .map(|segment| code.encode(segment))
+        .collect();
+
+    let mut index = (segment_len as u32).to_le_bytes().to_vec();
+    let mut offset = index_end(segments.len()).unwrap_or_default() as u64;
+    for (segment, encoded) in data.chunks(segment_len).zip(&segments) {
+        index.extend_from_slice(&offset.to_le_bytes());
+        index.extend_from_slice(&crc32(segment).to_le_bytes());
+        offset += encoded.len() as u64;
+    }
+
+    let mut out = header(MAGIC, spec, data.len() as u64);
+    out.extend(Hamming74.encode(&index));
+    out.extend(segments.concat());
+    out
+}
+
+impl SeekableIndex {
+    /// Read and correct the header and index of a stream written by
+    /// [`encode_seekable`]; the segments are not read
+    pub fn read(encoded: &[u8]) -> Result<Self, HammingError> {
+        let (spec, data_len) = parse_header(MAGIC, encoded)?;
+        let rest = encoded.get(PREFIX_LEN..).unwrap_or_default();
+        let segment_len = Hamming74.decode(
+            rest.get(..Hamming74::encoded_len(SEGMENT_LEN_BYTES))
+                .ok_or(HammingError::InvalidLength)?,
+        )?;
+        let segment_len = u32::from_le_bytes(segment_len.try_into().unwrap_or_default()) as usize;
+        if segment_len == 0 {
+            return Err(HammingError::InvalidLength);
+        }
+
+        let count = usize::try_from(data_len.div_ceil(segment_len as u64))
+            .map_err(|_| HammingError::InvalidLength)?;
+        let index = index_end(count)
+            .and_then(|end| encoded.get(PREFIX_LEN..end))
+            .ok_or(HammingError::InvalidLength)?;
+        let index = Hamming74.decode(index)?;
+        let entries = index
+            .get(SEGMENT_LEN_BYTES..)
+            .unwrap_or_default()
+            .as_chunks::<ENTRY_BYTES>()
+            .0
+            .iter()
+            .map(|entry| {
+                let (offset, crc) = entry.split_at(8);
+                IndexEntry {
+                    offset: u64::from_le_bytes(offset.try_into().unwrap_or_default()),
+                    crc: u32::from_le_bytes(crc.try_into().unwrap_or_default()),
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            spec,
+            data_len,
+            segment_len,
+            entries,
+        })
+    }
+
+    pub fn spec(&self) -> CodeSpec {
+        self.spec
+    }
+
+    /// Length of the data in bytes
+    pub fn data_len(&self) -> u64 {
+        self.data_len
+    }
+
+    /// Data bytes per segment; only the last one may be shorter
+    pub fn segment_len(&self) -> usize {
+        self.segment_len
+    }
+
+    pub fn entries(&self) -> &[IndexEntry] {
+        &self.entries
+    }
+
+    /// The segment holding data byte `offset`
+    pub fn segment_of(&self, offset: u64) -> usize {
+        usize::try_from(offset / self.segment_len as u64).unwrap_or(usize::MAX)
+    }
+
+    /// Data bytes `range`, decoding only the segments covering it
+    ///
+    /// Segments whose CRC does not match after correction fail with
+    /// [`HammingError::UncorrectableErrors`].
+    pub fn read_range(&self, encoded: &[u8], range: Range<u64>) -> Result<Vec<u8>, HammingError> {
+        if range.start > range.end || range.end > self.data_len {
+            return Err(HammingError::InvalidLength);
+        }
+        if range.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let first = self.segment_of(range.start);
+        let mut data = Vec::new();
+        for segment in first..=self.segment_of(range.end - 1) {
+            data.extend(self.decode_segment(encoded, segment)?);
+        }
+        let skip = (range.start - first as u64 * self.segment_len as u64) as usize;
+        let len = (range.end - range.start) as usize;
+        data.get(skip..skip + len)
+            .map(<[u8]>::to_vec)
+            .ok_or(HammingError::InvalidLength)
+    }
+
+    /// Correct segment `segment` in place, returning whether it needed
+    /// correcting
+    pub fn repair_segment(&self, encoded: &mut [u8], segment: usize) -> Result<bool, HammingError> {
+        let data = self.decode_segment(encoded, segment)?;
+        let stored = encoded
+            .get_mut(self.segment_range(segment)?)
+            .ok_or(HammingError::InvalidLength)?;
+        let canonical = self.spec.build().encode(&data);
+        if canonical == stored {
+            return Ok(false);
+        }
+        if canonical.len() != stored.len() {
+            return Err(HammingError::InvalidLength);
+        }
+        stored.copy_from_slice(&canonical);
+        Ok(true)
+    }
+
+    // The checked data of `segment`
+    fn decode_segment(&self, encoded: &[u8], segment: usize) -> Result<Vec<u8>, HammingError> {
+        let entry = self
+            .entries
+            .get(segment)
+            .ok_or(HammingError::InvalidLength)?;
+        let stored = encoded
+            .get(self.segment_range(segment)?)
+            .ok_or(HammingError::InvalidLength)?;
+
+        let start = segment as u64 * self.segment_len as u64;
+        let len = (self.data_len - start).min(self.segment_len as u64) as usize;
+        let mut data = self.spec.build().decode(stored)?;
+        if data.len() < len {
+            return Err(HammingError::InvalidLength);
+        }
+        data.truncate(len);
+        if crc32(&data) != entry.crc {
+            return Err(HammingError::UncorrectableErrors);
+        }
+        Ok(data)
+    }
+
+    // Encoded bytes of `segment`
+    fn segment_range(&self, segment: usize) -> Result<Range<usize>, HammingError> {
+        let offset = |entry: &IndexEntry| usize::try_from(entry.offset).ok();
+        let start = self.entries.get(segment).and_then(offset);
+        let end = match self.entries.get(segment + 1) {
+            Some(next) => offset(next),
+            None => {
+                let tail = self.data_len - segment as u64 * self.segment_len as u64;
+                let len = self.spec.build().expansion(tail as usize).encoded_len;
+                start.and_then(|start| start.checked_add(len))
+            }
+        };
+        match (start, end) {
+            (Some(start), Some(end)) if start <= end => Ok(start..end),
+            _ => Err(HammingError::InvalidLength),
+        }
+    }
+}
+
+// End of the encoded index of `count` segments, if addressable
+fn index_end(count: usize) -> Option<usize> {
+    let index_len = count
+        .checked_mul(ENTRY_BYTES)?
+        .checked_add(SEGMENT_LEN_BYTES)?;
+    index_len.checked_mul(2)?.checked_add(PREFIX_LEN)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seekable_read_range() {
+        let data: Vec<u8> = (0..1000).map(|i| (i * 7 + 3) as u8).collect();
+        for spec in [CodeSpec::Hamming1511, CodeSpec::Secded { data_bits: 64 }] {
+            let mut encoded = encode_seekable(&spec, &data, 128);
+            let index = SeekableIndex::read(&encoded).unwrap();
+            assert_eq!(index.entries().len(), 8);
+            assert_eq!(index.segment_of(300), 2);
+
+            // Wreck segment 0; other segments still read
+            let first = index.entries()[0].offset as usize;
+            encoded[first..first + 8].fill(0xFF);
+            assert_eq!(
+                index.read_range(&encoded, 300..700).unwrap(),
+                data[300..700]
+            );
+            assert_eq!(index.read_range(&encoded, 990..1000).unwrap(), data[990..]);
+            assert!(index.read_range(&encoded, 0..10).is_err());
+            assert_eq!(
+                index.read_range(&encoded, 999..1001),
+                Err(HammingError::InvalidLength)
+            );
+        }
+    }
+
+    #[test]
+    fn test_seekable_repair_segment() {
+        let data = b"segments are repaired one at a time".repeat(10);
+        let mut encoded = encode_seekable(&CodeSpec::Hamming74, &data, 64);
+        let clean = encoded.clone();
+        // One flip in the index, one in segment 3
+        encoded[PREFIX_LEN + 5] ^= 0x02;
+        let index = SeekableIndex::read(&encoded).unwrap();
+        let offset = index.entries()[3].offset as usize;
+        encoded[offset + 10] ^= 0x08;
+
+        assert_eq!(index.repair_segment(&mut encoded, 2), Ok(false));
+        assert_eq!(index.repair_segment(&mut encoded, 3), Ok(true));
+        assert_eq!(encoded[offset..], clean[offset..]);
+        assert_eq!(
+            index.repair_segment(&mut encoded, 6),
+            Err(HammingError::InvalidLength)
+        );
+    }
+}
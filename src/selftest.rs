@@ -0,0 +1,122 @@
+use crate::CodeSpec;
+use alloc::{boxed::Box, vec::Vec};
+
+// Codes with at most this many data bits are tested on every data value
+const EXHAUSTIVE_DATA_BITS: usize = 16;
+// Extra pseudo-random data values for larger codes
+const RANDOM_VALUES: usize = 4096;
+
+/// Result of [`self_test`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SelfTestReport {
+    pub spec: CodeSpec,
+    /// Data values tested
+    pub data_values: u64,
+    /// Whether `data_values` covers every value of a block
+    pub exhaustive: bool,
+    /// Clean and single-error decodes performed
+    pub cases: u64,
+    /// Decodes that did not return the original data
+    pub failures: u64,
+}
+
+impl SelfTestReport {
+    pub fn passed(&self) -> bool {
+        self.failures == 0
+    }
+}
+
+/// Functional check of a codec on the running target
+///
+/// Encodes every data value of a block (a fixed set of patterns plus
+/// pseudo-random values for codes of more than 16 data bits), then decodes
+/// the codeword clean and with each single-bit error in it.
+pub fn self_test(spec: &CodeSpec) -> SelfTestReport {
+    let code = spec.build();
+    let (n, k) = (code.block_size(), code.data_bits());
+    let exhaustive = k <= EXHAUSTIVE_DATA_BITS;
+    let mut report = SelfTestReport {
+        spec: *spec,
+        data_values: 0,
+        exhaustive,
+        cases: 0,
+        failures: 0,
+    };
+
+    for value in data_values(k) {
+        report.data_values += 1;
+        let data: Vec<u8> = (0..k.div_ceil(8))
+            .map(|byte| value.checked_shr(8 * byte as u32).unwrap_or(0) as u8)
+            .collect();
+        let encoded = code.encode(&data);
+
+        // Block 0 starts at bit 0 of every codec's stream
+        for flip in core::iter::once(None).chain((0..n).map(Some)) {
+            let mut received = encoded.clone();
+            if let Some(bit) = flip
+                && let Some(byte) = received.get_mut(bit / 8)
+            {
+                *byte ^= 1 << (bit % 8);
+            }
+            report.cases += 1;
+            let intact = code
+                .decode(&received)
+                .is_ok_and(|decoded| decoded.starts_with(&data));
+            if !intact {
+                report.failures += 1;
+            }
+        }
+    }
+    report
+}
+
+// Every `bits`-bit value, or a deterministic sample of them
+pub(crate) fn data_values(bits: usize) -> Box<dyn Iterator<Item = u64>> {
+    let mask = u64::MAX.checked_shr(64 - bits.min(64) as u32).unwrap_or(0);
+    if bits <= EXHAUSTIVE_DATA_BITS {
+        return Box::new(0..=mask);
+    }
+
+    let patterns = [
+        0,
+        mask,
+        0x5555_5555_5555_5555 & mask,
+        0xAAAA_AAAA_AAAA_AAAA & mask,
+    ];
+    let walking = (0..bits.min(64)).flat_map(move |i| [1 << i, mask ^ (1 << i)]);
+    let mut state = 0x2545_F491_4F6C_DD1Du64;
+    let random = (0..RANDOM_VALUES).map(move |_| {
+        // xorshift64
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state & mask
+    });
+    Box::new(patterns.into_iter().chain(walking).chain(random))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_self_test_passes() {
+        let report = self_test(&CodeSpec::Hamming74);
+        assert_eq!(report.data_values, 16);
+        assert_eq!(report.cases, 16 * 8);
+        assert!(report.exhaustive && report.passed());
+
+        for spec in [CodeSpec::Hamming1511, CodeSpec::Secded { data_bits: 8 }] {
+            assert!(self_test(&spec).passed(), "{spec}");
+        }
+    }
+
+    #[test]
+    fn test_data_values() {
+        assert_eq!(data_values(4).count(), 16);
+        let sample: Vec<u64> = data_values(26).collect();
+        assert_eq!(sample.len(), 4 + 2 * 26 + RANDOM_VALUES);
+        assert!(sample.iter().all(|&v| v < 1 << 26));
+    }
+}
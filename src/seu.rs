@@ -0,0 +1,237 @@
+use crate::{ChannelRng, HammingCode, flip_bit};
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+const SECS_PER_HOUR: f64 = 3600.0;
+
+/// Outcome of one [`SeuEnvironment::run`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SeuReport {
+    /// Time of the first upset after which the image no longer decodes to
+    /// the data, or `None` if it lasted the whole run
+    pub time_to_failure: Option<Duration>,
+    /// Whether the decoder reported that failure, rather than miscorrecting
+    /// silently
+    pub failure_detected: bool,
+    /// Upset events, each flipping one burst of bits
+    pub upsets: u64,
+    pub flipped_bits: u64,
+    /// Scrub passes over the image
+    pub scrubs: u64,
+    /// Bits fixed by those passes
+    pub scrub_corrections: u64,
+}
+
+/// Single-event upset environment: upsets arriving at random times at a
+/// rate per bit per hour, each flipping a burst of adjacent bits
+///
+/// [`SeuEnvironment::run`] exposes a protected memory image to it, scrubbed
+/// at a fixed interval, and reports when the image first became
+/// unrecoverable, for comparing codes, interleaving and scrub intervals.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeuEnvironment {
+    rate: f64,
+    multiplicity: Vec<f64>,
+}
+
+impl SeuEnvironment {
+    /// Single-bit upsets at `rate` per bit per hour (negative rates count
+    /// as 0)
+    pub fn new(rate: f64) -> Self {
+        Self {
+            rate: if rate.is_nan() { 0.0 } else { rate.max(0.0) },
+            multiplicity: vec![1.0],
+        }
+    }
+
+    /// Relative weights of an upset flipping 1, 2, 3, ... adjacent bits,
+    /// e.g. `[0.9, 0.08, 0.02]`; negative weights count as 0, and weights
+    /// that are all 0 mean single-bit upsets
+    pub fn with_multiplicity(mut self, weights: &[f64]) -> Self {
+        self.multiplicity = weights
+            .iter()
+            .map(|&weight| if weight > 0.0 { weight } else { 0.0 })
+            .collect();
+        if self.multiplicity.iter().sum::<f64>() <= 0.0 {
+            self.multiplicity = vec![1.0];
+        }
+        self
+    }
+
+    /// Upsets per bit per hour
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    pub fn multiplicity(&self) -> &[f64] {
+        &self.multiplicity
+    }
+
+    /// Expose `data`, encoded with `code`, to upsets for at most `horizon`,
+    /// scrubbing it every `scrub_interval` (`Duration::ZERO` disables
+    /// scrubbing)
+    ///
+    /// Upset times and positions are drawn from `rng`; bursts land on any
+    /// bit of the encoded image, in stream order. After every upset the
+    /// [`HammingCode::chunk_sizes`] chunks it touched are decoded, and the
+    /// run ends at the first one that fails or decodes to other data. A
+    /// scrub pass repairs the chunks hit since the last one.
+    pub fn run<C: HammingCode + ?Sized, R: ChannelRng>(
+        &self,
+        code: &C,
+        data: &[u8],
+        scrub_interval: Duration,
+        horizon: Duration,
+        rng: &mut R,
+    ) -> SeuReport {
+        let mut report = SeuReport::default();
+        let mut image = code.encode(data);
+        let reference = code.decode(&image).unwrap_or_default();
+        let total_bits = image.len().saturating_mul(8);
+        // Upsets per hour over the whole image
+        let events = self.rate * total_bits as f64;
+        if events <= 0.0 {
+            return report;
+        }
+
+        let (data_chunk, chunk) = code.chunk_sizes();
+        let (data_chunk, chunk) = (data_chunk.max(1), chunk.max(1));
+        let horizon = horizon.as_secs_f64() / SECS_PER_HOUR;
+        let interval = scrub_interval.as_secs_f64() / SECS_PER_HOUR;
+        let mut dirty: BTreeSet<usize> = BTreeSet::new();
+        let mut time = 0.0;
+        loop {
+            // Exponential gaps between upsets
+            time += -(1.0 - uniform(rng)).ln() / events;
+            if time >= horizon {
+                break;
+            }
+
+            if interval > 0.0 {
+                // Every pass due by now; all but the first find nothing
+                let due = (time / interval) as u64;
+                if due > report.scrubs {
+                    for &index in &dirty {
+                        let start = index * chunk;
+                        let end = (start + chunk).min(image.len());
+                        if let Some(piece) = image.get_mut(start..end) {
+                            let fixed = code.repair_in_place(piece).unwrap_or_default();
+                            report.scrub_corrections += fixed as u64;
+                        }
+                    }
+                    dirty.clear();
+                    report.scrubs = due;
+                }
+            }
+
+            let first = (rng.next_u64() % total_bits as u64) as usize;
+            let last = (first + self.burst(rng)).min(total_bits);
+            for bit in first..last {
+                flip_bit(&mut image, bit / 8, (bit % 8) as u32);
+            }
+            report.upsets += 1;
+            report.flipped_bits += (last - first) as u64;
+
+            for index in first / 8 / chunk..=(last - 1) / 8 / chunk {
+                dirty.insert(index);
+                let start = index * chunk;
+                let piece = image
+                    .get(start..(start + chunk).min(image.len()))
+                    .unwrap_or_default();
+                let intact = code.decode(piece).map(|decoded| {
+                    let from = index * data_chunk;
+                    reference.get(from..from + decoded.len()) == Some(&decoded[..])
+                });
+                if intact != Ok(true) {
+                    report.time_to_failure = Some(Duration::from_secs_f64(time * SECS_PER_HOUR));
+                    report.failure_detected = intact.is_err();
+                    return report;
+                }
+            }
+        }
+
+        if interval > 0.0 {
+            report.scrubs = report.scrubs.max((horizon / interval) as u64);
+        }
+        report
+    }
+
+    // Bits flipped by the next upset
+    fn burst<R: ChannelRng>(&self, rng: &mut R) -> usize {
+        let mut pick = uniform(rng) * self.multiplicity.iter().sum::<f64>();
+        for (i, &weight) in self.multiplicity.iter().enumerate() {
+            if pick < weight {
+                return i + 1;
+            }
+            pick -= weight;
+        }
+        self.multiplicity
+            .iter()
+            .rposition(|&weight| weight > 0.0)
+            .map_or(1, |i| i + 1)
+    }
+}
+
+// Uniform in [0, 1)
+fn uniform<R: ChannelRng>(rng: &mut R) -> f64 {
+    (rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Hamming, Hamming1511, Interleaved, SplitMix64};
+
+    const HOUR: Duration = Duration::from_secs(3600);
+
+    #[test]
+    fn test_seu_scrubbing_extends_life() {
+        let data = vec![0x3C; 256];
+        let environment = SeuEnvironment::new(1e-3);
+        // Total hours to failure over several runs, unfailed runs counting
+        // the whole horizon
+        let hours = |scrub: Duration| -> f64 {
+            let mut rng = SplitMix64::new(11);
+            (0..10)
+                .map(|_| {
+                    let report = environment.run(&Hamming1511, &data, scrub, 1000 * HOUR, &mut rng);
+                    report.time_to_failure.unwrap_or(1000 * HOUR).as_secs_f64() / 3600.0
+                })
+                .sum()
+        };
+        let unscrubbed = hours(Duration::ZERO);
+        let scrubbed = hours(HOUR / 10);
+        assert!(scrubbed > 10.0 * unscrubbed, "{scrubbed} vs {unscrubbed}");
+
+        let quiet = SeuEnvironment::new(0.0).run(
+            &Hamming1511,
+            &data,
+            HOUR,
+            10 * HOUR,
+            &mut SplitMix64::new(1),
+        );
+        assert_eq!(quiet, SeuReport::default());
+    }
+
+    #[test]
+    fn test_seu_bursts_and_interleaving() {
+        let data = vec![0xA5; 512];
+        let secded = Hamming::new(64).with_extended(true);
+        let doubles = SeuEnvironment::new(1e-4).with_multiplicity(&[0.0, 1.0]);
+        assert_eq!(doubles.multiplicity(), [0.0, 1.0]);
+
+        let mut rng = SplitMix64::new(5);
+        let report = doubles.run(&secded, &data, HOUR, 1000 * HOUR, &mut rng);
+        assert!(report.failure_detected);
+        assert!(report.upsets <= 3, "{report:?}");
+        assert_eq!(report.flipped_bits, 2 * report.upsets);
+
+        // Two-way interleaving puts the two bits of a burst in different
+        // codewords
+        let interleaved = Interleaved::new(secded, 2);
+        let report = doubles.run(&interleaved, &data, HOUR, 1000 * HOUR, &mut rng);
+        assert!(report.upsets > 100, "{report:?}");
+        assert!(report.scrub_corrections > 0);
+    }
+}
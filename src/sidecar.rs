@@ -0,0 +1,467 @@
+use crate::{
+    BlockOutcome, CodeSpec, Hamming, Hamming74, HammingCode, HammingError, bits, flip_bit,
+};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+const MAGIC: &[u8; 4] = b"HECC";
+const VERSION: u8 = 1;
+// Version, code kind, data bits (u32) and data length (u64) before encoding
+const HEADER_LEN: usize = 14;
+// Magic plus the Hamming(7,4)-encoded header
+pub(crate) const PREFIX_LEN: usize = MAGIC.len() + 2 * HEADER_LEN;
+// Approximate data bytes read at a time by the file functions; rounded to
+// whole blocks with byte-aligned parity
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Parity bits for data stored elsewhere, e.g. in a `.ecc` file next to the
+/// file it protects
+///
+/// Holds the parity bits every block of the code would add to the data, so
+/// one bit error per block can be repaired while the data itself stays
+/// unencoded. The parity bits are covered by their own blocks and the
+/// serialized header is Hamming(7,4) protected, so bit rot in the sidecar
+/// is repaired too.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sidecar {
+    spec: CodeSpec,
+    data_len: u64,
+    parity: Vec<u8>,
+}
+
+/// Result of [`Sidecar::verify`] and [`Sidecar::repair`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RepairReport {
+    pub blocks: u64,
+    /// Bit errors found in the data
+    pub data_bits: u64,
+    /// Bit errors found in the parity bits
+    pub parity_bits: u64,
+    /// Blocks with more errors than the code corrects; left unchanged
+    pub uncorrectable_blocks: u64,
+}
+
+impl RepairReport {
+    /// Whether no errors were found
+    pub fn is_clean(&self) -> bool {
+        self.data_bits == 0 && self.parity_bits == 0 && self.uncorrectable_blocks == 0
+    }
+}
+
+impl Sidecar {
+    /// Compute the parity bits of `data`
+    pub fn protect(spec: &CodeSpec, data: &[u8]) -> Self {
+        Self {
+            spec: *spec,
+            data_len: data.len() as u64,
+            parity: chunk_parity(&spec.general_equivalent().0, data),
+        }
+    }
+
+    pub fn spec(&self) -> CodeSpec {
+        self.spec
+    }
+
+    /// Length of the protected data in bytes
+    pub fn data_len(&self) -> u64 {
+        self.data_len
+    }
+
+    /// Check `data` against the parity bits without changing either
+    pub fn verify(&self, data: &[u8]) -> Result<RepairReport, HammingError> {
+        self.clone().repair(&mut data.to_vec())
+    }
+
+    /// Correct single-bit errors per block in `data` and in the parity bits
+    pub fn repair(&mut self, data: &mut [u8]) -> Result<RepairReport, HammingError> {
+        if data.len() as u64 != self.data_len {
+            return Err(HammingError::InvalidLength);
+        }
+        let mut report = RepairReport::default();
+        repair_chunk(
+            &self.spec.general_equivalent().0,
+            data,
+            &mut self.parity,
+            &mut report,
+        );
+        Ok(report)
+    }
+
+    /// Serialized form, as written to a `.ecc` file
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = header(MAGIC, &self.spec, self.data_len);
+        out.extend_from_slice(&self.parity);
+        out
+    }
+
+    /// Parse [`Sidecar::to_bytes`] output, correcting the header
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, HammingError> {
+        let (spec, data_len) = parse_header(MAGIC, bytes)?;
+        let parity = bytes.get(PREFIX_LEN..).unwrap_or_default();
+        if parity_len(&spec, data_len) != Some(parity.len() as u64) {
+            return Err(HammingError::InvalidLength);
+        }
+        Ok(Self {
+            spec,
+            data_len,
+            parity: parity.to_vec(),
+        })
+    }
+}
+
+/// Write the sidecar of the file at `path` to `sidecar`
+///
+/// The file is read in chunks and the parity bits are written as they are
+/// computed, so neither needs to fit in memory. `progress` is called after
+/// every chunk with the bytes done so far and the file size.
+pub fn protect_file(
+    path: impl AsRef<Path>,
+    sidecar: impl AsRef<Path>,
+    spec: &CodeSpec,
+    progress: &mut dyn FnMut(u64, u64),
+) -> io::Result<()> {
+    let mut file = File::open(path)?;
+    let total = file.metadata()?.len();
+    let code = spec.general_equivalent().0;
+    let mut out = BufWriter::new(File::create(sidecar)?);
+    out.write_all(&header(MAGIC, spec, total))?;
+
+    let mut data = Vec::new();
+    let mut done = 0;
+    while read_chunk(&mut file, &mut data, chunk_size(&code))? > 0 {
+        out.write_all(&chunk_parity(&code, &data))?;
+        done += data.len() as u64;
+        progress(done, total);
+    }
+    // The file changed size while it was read
+    if done != total {
+        return Err(HammingError::InvalidLength.into());
+    }
+    out.flush()
+}
+
+/// Check the file at `path` against its sidecar without changing either
+///
+/// `progress` is called as for [`protect_file`].
+pub fn verify_file(
+    path: impl AsRef<Path>,
+    sidecar: impl AsRef<Path>,
+    progress: &mut dyn FnMut(u64, u64),
+) -> io::Result<RepairReport> {
+    check_file(path.as_ref(), sidecar.as_ref(), false, progress)
+}
+
+/// Correct single-bit errors per block in the file at `path` and in its
+/// sidecar, in place
+///
+/// Only chunks with corrections are written back, and the sidecar header
+/// is rewritten if it had errors. `progress` is called as for
+/// [`protect_file`].
+pub fn repair_file(
+    path: impl AsRef<Path>,
+    sidecar: impl AsRef<Path>,
+    progress: &mut dyn FnMut(u64, u64),
+) -> io::Result<RepairReport> {
+    check_file(path.as_ref(), sidecar.as_ref(), true, progress)
+}
+
+fn check_file(
+    path: &Path,
+    sidecar_path: &Path,
+    repair: bool,
+    progress: &mut dyn FnMut(u64, u64),
+) -> io::Result<RepairReport> {
+    let open = |path: &Path| OpenOptions::new().read(true).write(repair).open(path);
+    let (mut file, mut sidecar) = (open(path)?, open(sidecar_path)?);
+
+    let mut stored = Vec::new();
+    read_chunk(&mut sidecar, &mut stored, PREFIX_LEN)?;
+    let (spec, total) = parse_header(MAGIC, &stored)?;
+    let parity_bytes = sidecar.metadata()?.len() - PREFIX_LEN as u64;
+    if file.metadata()?.len() != total || parity_len(&spec, total) != Some(parity_bytes) {
+        return Err(HammingError::InvalidLength.into());
+    }
+    let canonical = header(MAGIC, &spec, total);
+    if repair && canonical != stored {
+        sidecar.seek(SeekFrom::Start(0))?;
+        sidecar.write_all(&canonical)?;
+    }
+
+    let code = spec.general_equivalent().0;
+    let (k, r) = block_bits(&code);
+    let (mut data, mut parity) = (Vec::new(), Vec::new());
+    let mut report = RepairReport::default();
+    let mut done = 0;
+    while read_chunk(&mut file, &mut data, chunk_size(&code))? > 0 {
+        let parity_len = ((data.len() * 8).div_ceil(k) * r).div_ceil(8);
+        if read_chunk(&mut sidecar, &mut parity, parity_len)? < parity_len {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+
+        let before = report;
+        repair_chunk(&code, &mut data, &mut parity, &mut report);
+        if repair && report.data_bits > before.data_bits {
+            file.seek(SeekFrom::Current(-(data.len() as i64)))?;
+            file.write_all(&data)?;
+        }
+        if repair && report.parity_bits > before.parity_bits {
+            sidecar.seek(SeekFrom::Current(-(parity.len() as i64)))?;
+            sidecar.write_all(&parity)?;
+        }
+        done += data.len() as u64;
+        progress(done, total);
+    }
+    if done != total {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(report)
+}
+
+// Read up to `len` bytes into `buffer`, replacing its contents
+fn read_chunk(reader: &mut impl Read, buffer: &mut Vec<u8>, len: usize) -> io::Result<usize> {
+    buffer.clear();
+    reader.take(len as u64).read_to_end(buffer)
+}
+
+// Serialized header: magic plus the Hamming(7,4)-encoded fields
+pub(crate) fn header(magic: &[u8; 4], spec: &CodeSpec, data_len: u64) -> Vec<u8> {
+    let (kind, data_bits) = match *spec {
+        CodeSpec::Hamming74 => (0, 0),
+        CodeSpec::Hamming1511 => (1, 0),
+        CodeSpec::General { data_bits } => (2, data_bits),
+        CodeSpec::Secded { data_bits } => (3, data_bits),
+    };
+    let mut fields = vec![VERSION, kind];
+    fields.extend_from_slice(&(data_bits as u32).to_le_bytes());
+    fields.extend_from_slice(&data_len.to_le_bytes());
+
+    let mut out = magic.to_vec();
+    out.extend(Hamming74.encode(&fields));
+    out
+}
+
+// Code and data length from a header starting with `magic`, correcting the
+// header
+pub(crate) fn parse_header(magic: &[u8; 4], bytes: &[u8]) -> Result<(CodeSpec, u64), HammingError> {
+    let (found, rest) = bytes
+        .split_at_checked(magic.len())
+        .ok_or(HammingError::InvalidLength)?;
+    if found != magic {
+        return Err(HammingError::Unsupported);
+    }
+    let encoded = rest
+        .get(..PREFIX_LEN - MAGIC.len())
+        .ok_or(HammingError::InvalidLength)?;
+
+    let fields = Hamming74.decode(encoded)?;
+    let field = |range: std::ops::Range<usize>| fields.get(range).unwrap_or_default();
+    let data_bits = u32::from_le_bytes(field(2..6).try_into().unwrap_or_default()) as usize;
+    let data_len = u64::from_le_bytes(field(6..14).try_into().unwrap_or_default());
+    let spec = match field(0..2) {
+        [VERSION, 0] => CodeSpec::Hamming74,
+        [VERSION, 1] => CodeSpec::Hamming1511,
+        [VERSION, 2] if Hamming::try_new(data_bits).is_ok() => CodeSpec::General { data_bits },
+        [VERSION, 3] if Hamming::try_new(data_bits).is_ok() => CodeSpec::Secded { data_bits },
+        _ => return Err(HammingError::Unsupported),
+    };
+    Ok((spec, data_len))
+}
+
+// Parity bytes for `data_len` bytes of data, if that fits in a u64
+fn parity_len(spec: &CodeSpec, data_len: u64) -> Option<u64> {
+    let (k, r) = block_bits(&spec.general_equivalent().0);
+    data_len
+        .checked_mul(8)
+        .and_then(|bits| bits.div_ceil(k as u64).checked_mul(r as u64))
+        .map(|bits| bits.div_ceil(8))
+}
+
+// Data and parity bits per block
+fn block_bits(code: &Hamming) -> (usize, usize) {
+    (code.data_bits(), code.block_size() - code.data_bits())
+}
+
+// Bytes per chunk of the file functions: `k` bytes are 8 whole blocks, so
+// every chunk but the last one has parity ending on a byte boundary
+fn chunk_size(code: &Hamming) -> usize {
+    let (k, _) = block_bits(code);
+    (CHUNK_SIZE / k).max(1) * k
+}
+
+// Parity bits of the blocks of `data`, packed from bit 0
+pub(crate) fn chunk_parity(code: &Hamming, data: &[u8]) -> Vec<u8> {
+    let (k, r) = block_bits(code);
+    let blocks = (data.len() * 8).div_ceil(k);
+    let mut parity = vec![0u8; (blocks * r).div_ceil(8)];
+
+    let mut data_bit = 0;
+    for block in 0..blocks {
+        let block_parity = code.parity_of(|| {
+            data_bit += 1;
+            bits::get(data, data_bit - 1)
+        });
+        for (i, bit) in block_parity.into_iter().enumerate() {
+            if bit {
+                bits::set(&mut parity, block * r + i);
+            }
+        }
+    }
+    parity
+}
+
+// Correct the blocks of `data` against `parity` from [`chunk_parity`]
+pub(crate) fn repair_chunk(
+    code: &Hamming,
+    data: &mut [u8],
+    parity: &mut [u8],
+    report: &mut RepairReport,
+) {
+    let (k, r) = block_bits(code);
+    let total_bits = data.len() * 8;
+
+    for block in 0..total_bits.div_ceil(k) {
+        // Bits past the end of the data read as the zero padding
+        let mut block_data: Vec<bool> = (block * k..(block + 1) * k)
+            .map(|i| bits::get(data, i))
+            .collect();
+        let mut block_parity: Vec<bool> = (block * r..(block + 1) * r)
+            .map(|i| bits::get(parity, i))
+            .collect();
+        let (old_data, old_parity) = (block_data.clone(), block_parity.clone());
+        report.blocks += 1;
+
+        match code.correct_split(&mut block_data, &mut block_parity) {
+            BlockOutcome::Clean => continue,
+            BlockOutcome::Uncorrectable => {
+                report.uncorrectable_blocks += 1;
+                continue;
+            }
+            BlockOutcome::Corrected { .. } => {}
+        }
+        let changed = |new: &[bool], old: &[bool]| new.iter().zip(old).position(|(a, b)| a != b);
+        if let Some(i) = changed(&block_data, &old_data) {
+            let bit = block * k + i;
+            // A correction in the padding means the block had more errors
+            if bit >= total_bits {
+                report.uncorrectable_blocks += 1;
+                continue;
+            }
+            flip_bit(data, bit / 8, (bit % 8) as u32);
+            report.data_bits += 1;
+        } else if let Some(i) = changed(&block_parity, &old_parity) {
+            let bit = block * r + i;
+            flip_bit(parity, bit / 8, (bit % 8) as u32);
+            report.parity_bits += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SAMPLE_SPECS;
+
+    fn data() -> Vec<u8> {
+        (0..=255).cycle().take(1001).collect()
+    }
+
+    #[test]
+    fn test_sidecar_repair() {
+        for spec in SAMPLE_SPECS {
+            let spec: CodeSpec = spec.parse().unwrap();
+            let original = data();
+            let mut sidecar = Sidecar::protect(&spec, &original);
+            assert!(sidecar.verify(&original).unwrap().is_clean());
+
+            let mut damaged = original.clone();
+            damaged[3] ^= 0x10;
+            damaged[900] ^= 0x01;
+            sidecar.parity[7] ^= 0x80;
+            let report = sidecar.repair(&mut damaged).unwrap();
+            assert_eq!((report.data_bits, report.parity_bits), (2, 1), "{spec}");
+            assert_eq!(damaged, original);
+            assert_eq!(sidecar, Sidecar::protect(&spec, &original));
+        }
+    }
+
+    #[test]
+    fn test_sidecar_bytes() {
+        let sidecar = Sidecar::protect(&CodeSpec::Secded { data_bits: 64 }, &data());
+        let mut bytes = sidecar.to_bytes();
+        // One flip in the encoded header is corrected
+        bytes[10] ^= 0x04;
+        assert_eq!(Sidecar::from_bytes(&bytes).unwrap(), sidecar);
+
+        assert_eq!(
+            Sidecar::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(HammingError::InvalidLength)
+        );
+        assert_eq!(Sidecar::from_bytes(b"HECX"), Err(HammingError::Unsupported));
+        assert_eq!(
+            sidecar.verify(&data()[1..]),
+            Err(HammingError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn test_sidecar_double_error() {
+        let spec = CodeSpec::Secded { data_bits: 64 };
+        let original = data();
+        let sidecar = Sidecar::protect(&spec, &original);
+        let mut damaged = original.clone();
+        damaged[8] ^= 0x03;
+        let report = sidecar.verify(&damaged).unwrap();
+        assert_eq!(report.uncorrectable_blocks, 1);
+        assert_eq!(report.data_bits, 0);
+    }
+
+    #[test]
+    fn test_sidecar_files() {
+        let dir = std::env::temp_dir();
+        let file = dir.join(format!("hamming-sidecar-{}.bin", std::process::id()));
+        let ecc = file.with_extension("ecc");
+        // Several chunks, the last one partial
+        let original: Vec<u8> = (0..140_000u32).map(|i| (i * 7 + i / 251) as u8).collect();
+        std::fs::write(&file, &original).unwrap();
+
+        for spec in ["74", "general:26"] {
+            let spec: CodeSpec = spec.parse().unwrap();
+            let mut calls = Vec::new();
+            protect_file(&file, &ecc, &spec, &mut |done, total| {
+                calls.push((done, total))
+            })
+            .unwrap();
+            let stored = std::fs::read(&ecc).unwrap();
+            assert_eq!(stored, Sidecar::protect(&spec, &original).to_bytes());
+            assert_eq!(calls.last(), Some(&(140_000, 140_000)));
+            assert!(calls.len() > 2);
+
+            let mut damaged = original.clone();
+            damaged[10] ^= 0x01;
+            damaged[100_001] ^= 0x40;
+            damaged[139_999] ^= 0x80;
+            std::fs::write(&file, &damaged).unwrap();
+            let mut bad_ecc = stored.clone();
+            bad_ecc[10] ^= 0x04;
+            bad_ecc[PREFIX_LEN + 5000] ^= 0x02;
+            std::fs::write(&ecc, &bad_ecc).unwrap();
+
+            let report = verify_file(&file, &ecc, &mut |_, _| {}).unwrap();
+            assert_eq!((report.data_bits, report.parity_bits), (3, 1), "{spec}");
+            assert_eq!(std::fs::read(&file).unwrap(), damaged);
+
+            let repaired = repair_file(&file, &ecc, &mut |_, _| {}).unwrap();
+            assert_eq!(repaired, report);
+            assert_eq!(std::fs::read(&file).unwrap(), original);
+            assert_eq!(std::fs::read(&ecc).unwrap(), stored);
+        }
+
+        std::fs::write(&file, &original[1..]).unwrap();
+        let err = verify_file(&file, &ecc, &mut |_, _| {}).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        std::fs::remove_file(&file).unwrap();
+        std::fs::remove_file(&ecc).unwrap();
+    }
+}
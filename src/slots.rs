@@ -0,0 +1,235 @@
+use crate::sidecar::{chunk_parity, repair_chunk};
+use crate::{CodeSpec, Hamming, HammingCode, HammingError, RepairReport};
+
+// Record length plus one, little-endian; an all-zero slot is empty
+const LEN_BYTES: usize = 4;
+
+/// Fixed-size slots with their own parity bits in a byte region, such as a
+/// memory-mapped file
+///
+/// Each slot holds a length, up to `payload` bytes of record data kept as
+/// they are, and the parity bits a [`Sidecar`](crate::Sidecar) would store
+/// for those bytes. Reads correct a single-bit error per block, and
+/// [`SlotLayout::repair`] corrects the whole region in place, e.g. after
+/// opening the file. With a SEC-DED code, a slot torn by a crash mid-write
+/// usually reads as uncorrectable; add a checksum to the record (as
+/// [`EccRecord`] does) to rule out the rest.
+///
+/// [`EccRecord`]: crate::EccRecord
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotLayout {
+    code: Hamming,
+    payload: usize,
+}
+
+impl SlotLayout {
+    /// Slots for records of up to `payload` bytes, protected with `spec`
+    ///
+    /// The length field stores the record length plus one in 4 bytes, so
+    /// `payload` must be below `u32::MAX`; larger ones fail with
+    /// [`HammingError::InvalidLength`].
+    pub fn new(spec: &CodeSpec, payload: usize) -> Result<Self, HammingError> {
+        if u32::try_from(payload).map_or(true, |payload| payload == u32::MAX) {
+            return Err(HammingError::InvalidLength);
+        }
+        Ok(Self {
+            code: spec.general_equivalent().0,
+            payload,
+        })
+    }
+
+    /// Largest record a slot holds
+    pub fn payload(&self) -> usize {
+        self.payload
+    }
+
+    /// Bytes per slot, length and parity bits included
+    pub fn slot_len(&self) -> usize {
+        self.data_len() + self.parity_len()
+    }
+
+    /// Whole slots that fit in `region_len` bytes
+    pub fn slots(&self, region_len: usize) -> usize {
+        region_len / self.slot_len()
+    }
+
+    /// Store `record` in slot `index` of `region`
+    pub fn write(
+        &self,
+        region: &mut [u8],
+        index: usize,
+        record: &[u8],
+    ) -> Result<(), HammingError> {
+        if record.len() > self.payload {
+            return Err(HammingError::InvalidLength);
+        }
+        let mut data = vec![0u8; self.data_len()];
+        let (len, payload) = data.split_at_mut(LEN_BYTES);
+        // `new` keeps the payload, and so the record, below u32::MAX
+        let stored = u32::try_from(record.len() + 1).map_err(|_| HammingError::InvalidLength)?;
+        len.copy_from_slice(&stored.to_le_bytes());
+        payload
+            .get_mut(..record.len())
+            .unwrap_or_default()
+            .copy_from_slice(record);
+        data.extend(chunk_parity(&self.code, &data));
+
+        self.slot_mut(region, index)?.copy_from_slice(&data);
+        Ok(())
+    }
+
+    /// The corrected record in slot `index`, or `None` if it is empty
+    pub fn read(&self, region: &[u8], index: usize) -> Result<Option<Vec<u8>>, HammingError> {
+        let slot = self.slot(region, index)?;
+        let mut slot = slot.to_vec();
+        if self.repair_slot(&mut slot).uncorrectable_blocks > 0 {
+            return Err(HammingError::UncorrectableErrors);
+        }
+
+        let (len, payload) = slot.split_at(LEN_BYTES);
+        let len = u32::from_le_bytes(len.try_into().unwrap_or_default()) as usize;
+        match len.checked_sub(1) {
+            None => Ok(None),
+            Some(len) => payload
+                .get(..len)
+                .filter(|_| len <= self.payload)
+                .map(|record| Some(record.to_vec()))
+                .ok_or(HammingError::UncorrectableErrors),
+        }
+    }
+
+    /// Mark slot `index` empty
+    pub fn clear(&self, region: &mut [u8], index: usize) -> Result<(), HammingError> {
+        self.slot_mut(region, index)?.fill(0);
+        Ok(())
+    }
+
+    /// Correct every slot of `region` in place; slots with uncorrectable
+    /// blocks are left as they are
+    pub fn repair(&self, region: &mut [u8]) -> RepairReport {
+        let mut report = RepairReport::default();
+        for slot in region.chunks_exact_mut(self.slot_len()) {
+            let slot_report = self.repair_slot(slot);
+            report.blocks += slot_report.blocks;
+            report.data_bits += slot_report.data_bits;
+            report.parity_bits += slot_report.parity_bits;
+            report.uncorrectable_blocks += slot_report.uncorrectable_blocks;
+        }
+        report
+    }
+
+    fn repair_slot(&self, slot: &mut [u8]) -> RepairReport {
+        let mut report = RepairReport::default();
+        let (data, parity) = slot.split_at_mut(self.data_len().min(slot.len()));
+        repair_chunk(&self.code, data, parity, &mut report);
+        report
+    }
+
+    fn slot<'a>(&self, region: &'a [u8], index: usize) -> Result<&'a [u8], HammingError> {
+        self.range(index)
+            .and_then(|range| region.get(range))
+            .ok_or(HammingError::InvalidLength)
+    }
+
+    fn slot_mut<'a>(
+        &self,
+        region: &'a mut [u8],
+        index: usize,
+    ) -> Result<&'a mut [u8], HammingError> {
+        self.range(index)
+            .and_then(|range| region.get_mut(range))
+            .ok_or(HammingError::InvalidLength)
+    }
+
+    fn range(&self, index: usize) -> Option<std::ops::Range<usize>> {
+        let start = index.checked_mul(self.slot_len())?;
+        Some(start..start.checked_add(self.slot_len())?)
+    }
+
+    // Length and payload bytes
+    fn data_len(&self) -> usize {
+        LEN_BYTES + self.payload
+    }
+
+    fn parity_len(&self) -> usize {
+        let (k, n) = (self.code.data_bits(), self.code.block_size());
+        ((self.data_len() * 8).div_ceil(k) * (n - k)).div_ceil(8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slots_read_write() {
+        let layout = SlotLayout::new(&CodeSpec::Secded { data_bits: 64 }, 28).unwrap();
+        // 32 data bytes in 4 blocks of 8 check bits
+        assert_eq!(layout.slot_len(), 36);
+        let mut region = vec![0u8; 4 * layout.slot_len() + 10];
+        assert_eq!(layout.slots(region.len()), 4);
+
+        layout.write(&mut region, 1, b"first").unwrap();
+        layout.write(&mut region, 3, &[0xAB; 28]).unwrap();
+        layout.write(&mut region, 2, b"").unwrap();
+        assert_eq!(layout.read(&region, 0), Ok(None));
+        assert_eq!(layout.read(&region, 1), Ok(Some(b"first".to_vec())));
+        assert_eq!(layout.read(&region, 2), Ok(Some(Vec::new())));
+        assert_eq!(layout.read(&region, 3), Ok(Some(vec![0xAB; 28])));
+
+        assert_eq!(
+            layout.write(&mut region, 0, &[0; 29]),
+            Err(HammingError::InvalidLength)
+        );
+        assert_eq!(layout.read(&region, 4), Err(HammingError::InvalidLength));
+        assert_eq!(
+            layout.read(&region, usize::MAX),
+            Err(HammingError::InvalidLength)
+        );
+        layout.clear(&mut region, 1).unwrap();
+        assert_eq!(layout.read(&region, 1), Ok(None));
+
+        // A torn write: the new record landed, its parity did not
+        let mut new = vec![0; 36];
+        layout.write(&mut new, 0, b"second").unwrap();
+        region[3 * 36..3 * 36 + 32].copy_from_slice(&new[..32]);
+        assert_eq!(
+            layout.read(&region, 3),
+            Err(HammingError::UncorrectableErrors)
+        );
+    }
+
+    #[test]
+    fn test_slots_payload_limit() {
+        let spec = CodeSpec::Hamming1511;
+        assert!(SlotLayout::new(&spec, u32::MAX as usize - 1).is_ok());
+        assert_eq!(
+            SlotLayout::new(&spec, u32::MAX as usize),
+            Err(HammingError::InvalidLength)
+        );
+        assert_eq!(
+            SlotLayout::new(&spec, usize::MAX),
+            Err(HammingError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn test_slots_repair() {
+        let layout = SlotLayout::new(&CodeSpec::Hamming1511, 20).unwrap();
+        let mut region = vec![0u8; 3 * layout.slot_len()];
+        for (i, record) in [&b"alpha"[..], b"beta", b"gamma"].iter().enumerate() {
+            layout.write(&mut region, i, record).unwrap();
+        }
+        let clean = region.clone();
+
+        let slot_len = layout.slot_len();
+        region[6] ^= 0x01;
+        region[slot_len + 2] ^= 0x80;
+        region[3 * slot_len - 1] ^= 0x04;
+        // The reads correct a copy
+        assert_eq!(layout.read(&region, 0), Ok(Some(b"alpha".to_vec())));
+        let report = layout.repair(&mut region);
+        assert_eq!((report.data_bits, report.parity_bits), (2, 1));
+        assert_eq!(region, clean);
+    }
+}
@@ -0,0 +1,230 @@
+use alloc::borrow::Cow;
+use alloc::{string::String, vec::Vec};
+
+/// Input accepted by [`HammingCode::encode_from`](crate::HammingCode::encode_from)
+///
+/// Byte slices, strings (their UTF-8 bytes) and vectors and arrays of them
+/// are taken as they are; integers are laid out little-endian, like
+/// [`AsBytes`](crate::AsBytes), unless wrapped in [`Words`] to pick the
+/// byte order.
+pub trait EncodeSource {
+    /// The bytes to encode
+    fn source_bytes(&self) -> Cow<'_, [u8]>;
+}
+
+impl EncodeSource for [u8] {
+    fn source_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl EncodeSource for str {
+    fn source_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(self.as_bytes())
+    }
+}
+
+impl EncodeSource for String {
+    fn source_bytes(&self) -> Cow<'_, [u8]> {
+        self.as_str().source_bytes()
+    }
+}
+
+impl<T> EncodeSource for Vec<T>
+where
+    [T]: EncodeSource,
+{
+    fn source_bytes(&self) -> Cow<'_, [u8]> {
+        self.as_slice().source_bytes()
+    }
+}
+
+impl<T, const N: usize> EncodeSource for [T; N]
+where
+    [T]: EncodeSource,
+{
+    fn source_bytes(&self) -> Cow<'_, [u8]> {
+        self.as_slice().source_bytes()
+    }
+}
+
+impl<T: EncodeSource + ?Sized> EncodeSource for &T {
+    fn source_bytes(&self) -> Cow<'_, [u8]> {
+        (**self).source_bytes()
+    }
+}
+
+/// Byte order of the words in [`Words`] and
+/// [`HammingCode::decode_words`](crate::HammingCode::decode_words)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Endian {
+    /// Least significant byte first, as unwrapped integer slices are laid out
+    #[default]
+    Little,
+    Big,
+}
+
+/// Integers that are encoded and decoded as words of [`Word::BYTES`] bytes
+pub trait Word: Copy {
+    const BYTES: usize;
+
+    /// Append the bytes of `self` in `endian` order
+    fn write_bytes(self, endian: Endian, out: &mut Vec<u8>);
+
+    /// The word in `bytes`, which holds exactly [`Word::BYTES`] bytes
+    fn read_bytes(bytes: &[u8], endian: Endian) -> Self;
+}
+
+/// A slice of words with an explicit byte order, for data that is word- rather
+/// than byte-granular, e.g. `Words::big_endian(&registers)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Words<'a, T> {
+    words: &'a [T],
+    endian: Endian,
+}
+
+impl<'a, T: Word> Words<'a, T> {
+    pub fn new(words: &'a [T], endian: Endian) -> Self {
+        Self { words, endian }
+    }
+
+    pub fn little_endian(words: &'a [T]) -> Self {
+        Self::new(words, Endian::Little)
+    }
+
+    pub fn big_endian(words: &'a [T]) -> Self {
+        Self::new(words, Endian::Big)
+    }
+}
+
+impl<T: Word> EncodeSource for Words<'_, T> {
+    fn source_bytes(&self) -> Cow<'_, [u8]> {
+        let mut bytes = Vec::with_capacity(self.words.len().saturating_mul(T::BYTES));
+        for &word in self.words {
+            word.write_bytes(self.endian, &mut bytes);
+        }
+        Cow::Owned(bytes)
+    }
+}
+
+/// Words of `bytes` in `endian` order; a trailing partial word is dropped
+pub(crate) fn read_words<T: Word>(bytes: &[u8], endian: Endian) -> Vec<T> {
+    bytes
+        .chunks_exact(T::BYTES)
+        .map(|word| T::read_bytes(word, endian))
+        .collect()
+}
+
+macro_rules! impl_word {
+    ($($t:ty),*) => {$(
+        impl EncodeSource for [$t] {
+            fn source_bytes(&self) -> Cow<'_, [u8]> {
+                Cow::Owned(Words::little_endian(self).source_bytes().into_owned())
+            }
+        }
+
+        impl Word for $t {
+            const BYTES: usize = size_of::<$t>();
+
+            fn write_bytes(self, endian: Endian, out: &mut Vec<u8>) {
+                match endian {
+                    Endian::Little => out.extend_from_slice(&self.to_le_bytes()),
+                    Endian::Big => out.extend_from_slice(&self.to_be_bytes()),
+                }
+            }
+
+            fn read_bytes(bytes: &[u8], endian: Endian) -> Self {
+                let bytes = bytes.try_into().unwrap_or_default();
+                match endian {
+                    Endian::Little => <$t>::from_le_bytes(bytes),
+                    Endian::Big => <$t>::from_be_bytes(bytes),
+                }
+            }
+        }
+    )*};
+}
+
+impl_word!(i8, u16, i16, u32, i32, u64, i64, u128, i128);
+
+impl Word for u8 {
+    const BYTES: usize = 1;
+
+    fn write_bytes(self, _: Endian, out: &mut Vec<u8>) {
+        out.push(self);
+    }
+
+    fn read_bytes(bytes: &[u8], _: Endian) -> Self {
+        bytes.first().copied().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Hamming, Hamming74, Hamming1511, HammingCode};
+    use alloc::{boxed::Box, vec};
+
+    #[test]
+    fn test_encode_source() {
+        let expected = Hamming74.encode(b"hello");
+        assert_eq!(Hamming74.encode_from("hello"), expected);
+        assert_eq!(Hamming74.encode_from(&String::from("hello")), expected);
+        assert_eq!(Hamming74.encode_from(b"hello"), expected);
+        assert_eq!(Hamming74.encode_from(&b"hello".to_vec()), expected);
+        assert_eq!(Hamming74.encode_from(&b"hello"[..]), expected);
+
+        // Integers are little-endian
+        let bytes = [1, 0, 0, 0, 2, 0, 0, 0, 0xFF, 0xFF, 0xFF, 0xFF];
+        assert_eq!(
+            Hamming1511.encode_from(&[1u32, 2, u32::MAX]),
+            Hamming1511.encode(&bytes)
+        );
+        assert_eq!(
+            Hamming1511.encode_from(&vec![0x0201i16, -1]),
+            Hamming1511.encode(&[1, 2, 0xFF, 0xFF])
+        );
+    }
+
+    #[test]
+    fn test_words_endian() {
+        let words = [0x0102_0304u32, 0x0A0B_0C0D];
+        assert_eq!(
+            Hamming1511.encode_from(&Words::big_endian(&words)),
+            Hamming1511.encode(&[1, 2, 3, 4, 0x0A, 0x0B, 0x0C, 0x0D])
+        );
+        assert_eq!(
+            Hamming1511.encode_from(&Words::little_endian(&words)),
+            Hamming1511.encode_from(&words)
+        );
+
+        // Padding past the last whole word is dropped
+        for endian in [Endian::Little, Endian::Big] {
+            let encoded = Hamming74.encode_from(&Words::new(&[0xBEEFu16, 7], endian));
+            assert_eq!(
+                Hamming74.decode_words::<u16>(&encoded, endian).unwrap(),
+                [0xBEEF, 7]
+            );
+            let encoded = Hamming1511.encode_from(&Words::new(&[u64::MAX - 5], endian));
+            assert_eq!(
+                Hamming1511.decode_words::<u64>(&encoded, endian).unwrap(),
+                [u64::MAX - 5]
+            );
+        }
+    }
+
+    #[test]
+    fn test_encode_source_boxed() {
+        let codec: Box<dyn HammingCode> = Box::new(Hamming::new(26));
+        assert_eq!(
+            codec.encode_from("boxed"),
+            Hamming::new(26).encode(b"boxed")
+        );
+        assert_eq!(codec.data_bits(), 26);
+    }
+}
@@ -0,0 +1,200 @@
+use crate::{Hamming, Hamming74, Hamming1511, HammingCode, HammingError};
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+};
+use core::fmt;
+use core::str::FromStr;
+
+/// Codec selection, suitable for configuration files
+///
+/// Also parsed from and displayed as the short form used on the command
+/// line: `74`, `1511`, `general:<data bits>` or `secded:<data bits>`.
+/// Parsing and deserializing reject data bits [`Hamming::try_new`] rejects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "code", rename_all = "lowercase", try_from = "SpecParams")
+)]
+pub enum CodeSpec {
+    Hamming74,
+    Hamming1511,
+    General {
+        data_bits: usize,
+    },
+    /// Extended general code with an overall parity bit, see
+    /// [`Hamming::with_extended`]
+    Secded {
+        data_bits: usize,
+    },
+}
+
+// The serialized form, checked by `TryFrom` on load
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(tag = "code", rename_all = "lowercase")]
+enum SpecParams {
+    Hamming74,
+    Hamming1511,
+    General { data_bits: usize },
+    Secded { data_bits: usize },
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SpecParams> for CodeSpec {
+    type Error = HammingError;
+
+    fn try_from(params: SpecParams) -> Result<Self, Self::Error> {
+        Ok(match params {
+            SpecParams::Hamming74 => CodeSpec::Hamming74,
+            SpecParams::Hamming1511 => CodeSpec::Hamming1511,
+            SpecParams::General { data_bits } => CodeSpec::General {
+                data_bits: checked_data_bits(data_bits)?,
+            },
+            SpecParams::Secded { data_bits } => CodeSpec::Secded {
+                data_bits: checked_data_bits(data_bits)?,
+            },
+        })
+    }
+}
+
+// Data bits of a general code, as `Hamming::try_new` accepts them
+fn checked_data_bits(data_bits: usize) -> Result<usize, HammingError> {
+    Hamming::try_new(data_bits).map(|_| data_bits)
+}
+
+impl CodeSpec {
+    /// Instantiate the selected codec; it can be shared across threads
+    ///
+    /// Specs from parsing or deserializing are valid; data bits set by hand
+    /// are not checked, and out-of-range ones fail on first use as with
+    /// [`Hamming::new`].
+    pub fn build(&self) -> Box<dyn HammingCode + Send + Sync> {
+        match *self {
+            CodeSpec::Hamming74 => Box::new(Hamming74),
+            CodeSpec::Hamming1511 => Box::new(Hamming1511),
+            CodeSpec::General { .. } | CodeSpec::Secded { .. } => Box::new(self.general_code()),
+        }
+    }
+
+    // The general code with the same block layout as the selected codec
+    fn general_code(&self) -> Hamming {
+        match *self {
+            CodeSpec::Hamming74 => Hamming::new(4),
+            CodeSpec::Hamming1511 => Hamming::new(11),
+            CodeSpec::General { data_bits } => Hamming::new(data_bits),
+            CodeSpec::Secded { data_bits } => Hamming::new(data_bits).with_extended(true),
+        }
+    }
+
+    // The general code with the same block layout as the selected codec,
+    // and an identifier for generated sources
+    pub(crate) fn general_equivalent(&self) -> (Hamming, String) {
+        let code = self.general_code();
+        let name = match *self {
+            CodeSpec::Hamming74 => "hamming74".to_string(),
+            CodeSpec::Hamming1511 => "hamming1511".to_string(),
+            CodeSpec::General { data_bits } => {
+                format!("hamming_{}_{}", code.block_size(), data_bits)
+            }
+            CodeSpec::Secded { data_bits } => format!("secded_{}_{}", code.block_size(), data_bits),
+        };
+        (code, name)
+    }
+}
+
+impl FromStr for CodeSpec {
+    type Err = HammingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let data_bits =
+            |bits: &str| checked_data_bits(bits.parse().map_err(|_| HammingError::Unsupported)?);
+        match s.split_once(':') {
+            None if s == "74" => Ok(CodeSpec::Hamming74),
+            None if s == "1511" => Ok(CodeSpec::Hamming1511),
+            Some(("general", bits)) => Ok(CodeSpec::General {
+                data_bits: data_bits(bits)?,
+            }),
+            Some(("secded", bits)) => Ok(CodeSpec::Secded {
+                data_bits: data_bits(bits)?,
+            }),
+            _ => Err(HammingError::Unsupported),
+        }
+    }
+}
+
+impl fmt::Display for CodeSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodeSpec::Hamming74 => f.write_str("74"),
+            CodeSpec::Hamming1511 => f.write_str("1511"),
+            CodeSpec::General { data_bits } => write!(f, "general:{data_bits}"),
+            CodeSpec::Secded { data_bits } => write!(f, "secded:{data_bits}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SAMPLE_SPECS;
+
+    #[test]
+    fn test_spec_build() {
+        assert_eq!(CodeSpec::Hamming74.build().block_size(), 7);
+        assert_eq!(CodeSpec::Hamming1511.build().block_size(), 15);
+        assert_eq!(CodeSpec::General { data_bits: 26 }.build().block_size(), 31);
+        assert_eq!(CodeSpec::Secded { data_bits: 64 }.build().block_size(), 72);
+
+        // Unchecked by hand, but building does not overflow
+        let huge = CodeSpec::General {
+            data_bits: usize::MAX,
+        };
+        assert!(huge.build().data_bits() == usize::MAX);
+        assert_eq!(huge.build().decode(&[0]), Err(HammingError::Unsupported));
+    }
+
+    #[test]
+    fn test_spec_from_str() {
+        for s in SAMPLE_SPECS {
+            let spec: CodeSpec = s.parse().unwrap();
+            assert_eq!(spec.to_string(), s);
+        }
+        assert_eq!(
+            "secded:64".parse::<CodeSpec>(),
+            Ok(CodeSpec::Secded { data_bits: 64 })
+        );
+        for s in [
+            "",
+            "7",
+            "general",
+            "general:0",
+            "secded:99999999",
+            "general:x",
+            "golay:12",
+        ] {
+            assert_eq!(s.parse::<CodeSpec>(), Err(HammingError::Unsupported));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_spec_serde() {
+        let spec: CodeSpec = serde_json::from_str(r#"{"code":"general","data_bits":26}"#).unwrap();
+        assert_eq!(spec, CodeSpec::General { data_bits: 26 });
+
+        let json = serde_json::to_string(&CodeSpec::Hamming1511).unwrap();
+        assert_eq!(json, r#"{"code":"hamming1511"}"#);
+
+        for json in [
+            r#"{"code":"general","data_bits":0}"#,
+            r#"{"code":"general","data_bits":18446744073709551615}"#,
+            r#"{"code":"secded","data_bits":0}"#,
+        ] {
+            assert!(serde_json::from_str::<CodeSpec>(json).is_err(), "{json}");
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! proptest strategies for property-testing code built on this crate
+
+use crate::CodeSpec;
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+/// Random codec selections, with general and SEC-DED codes of 1 to 64 data
+/// bits
+pub fn code_specs() -> impl Strategy<Value = CodeSpec> {
+    prop_oneof![
+        Just(CodeSpec::Hamming74),
+        Just(CodeSpec::Hamming1511),
+        (1usize..=64).prop_map(|data_bits| CodeSpec::General { data_bits }),
+        (1usize..=64).prop_map(|data_bits| CodeSpec::Secded { data_bits }),
+    ]
+}
+
+/// `(data, encoded)` pairs of up to `max_len` data bytes
+pub fn codewords(spec: CodeSpec, max_len: usize) -> impl Strategy<Value = (Vec<u8>, Vec<u8>)> {
+    vec(any::<u8>(), 0..=max_len).prop_map(move |data| {
+        let encoded = spec.build().encode(&data);
+        (data, encoded)
+    })
+}
+
+/// `(data, encoded)` pairs where every block carries at most one bit error,
+/// so decoding must recover `data`
+pub fn corrupted_codewords(
+    spec: CodeSpec,
+    max_len: usize,
+) -> impl Strategy<Value = (Vec<u8>, Vec<u8>)> {
+    let codec = spec.build();
+    let block_size = codec.block_size();
+    let stride = codec.block_stride();
+    let k = codec.data_bits();
+
+    vec(any::<u8>(), 0..=max_len).prop_flat_map(move |data| {
+        let blocks = (data.len() * 8).div_ceil(k);
+        let errors = vec(proptest::option::of(0..block_size), blocks);
+        (Just(data), errors).prop_map(move |(data, errors)| {
+            let mut encoded = spec.build().encode(&data);
+            for (block, error) in errors.into_iter().enumerate() {
+                if let Some(offset) = error {
+                    let bit = block * stride + offset;
+                    if let Some(byte) = encoded.get_mut(bit / 8) {
+                        *byte ^= 1 << (bit % 8);
+                    }
+                }
+            }
+            (data, encoded)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn test_codewords_roundtrip((spec, (data, encoded)) in code_specs()
+            .prop_flat_map(|spec| (Just(spec), codewords(spec, 32))))
+        {
+            let decoded = spec.build().decode(&encoded).unwrap();
+            prop_assert!(decoded.starts_with(&data));
+        }
+
+        #[test]
+        fn test_corrupted_codewords_correctable((spec, (data, encoded)) in code_specs()
+            .prop_flat_map(|spec| (Just(spec), corrupted_codewords(spec, 32))))
+        {
+            let decoded = spec.build().decode(&encoded).unwrap();
+            prop_assert!(decoded.starts_with(&data));
+        }
+    }
+}
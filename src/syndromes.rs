@@ -0,0 +1,161 @@
+use crate::{BlockOutcome, CodeSpec, Hamming, HammingCode, HammingError};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Write;
+
+// One entry per syndrome, so keep tables to 64K entries
+const MAX_SYNDROME_BITS: usize = 16;
+
+/// How the decoder handles one syndrome
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SyndromeEntry {
+    pub syndrome: usize,
+    /// [`BlockOutcome::Clean`] for syndrome 0, the codeword position to flip,
+    /// or [`BlockOutcome::Uncorrectable`] for syndromes no single error
+    /// produces
+    pub outcome: BlockOutcome,
+    /// Whether an uncorrectable syndrome is a detected double error, see
+    /// [`HammingError::DoubleErrorDetected`]
+    pub double_error: bool,
+}
+
+/// Complete syndrome decoding table of a code, for embedding in ROM or
+/// hardware next to an encoder built from [`CodeMatrices`](crate::CodeMatrices)
+///
+/// Entry `i` is syndrome `i`, computed as the parity-check matrix times the
+/// codeword, with the overall parity of extended codes as its top bit.
+/// Positions are codeword positions, the bit order of
+/// [`LookupTables`](crate::LookupTables). Codes are limited to syndromes
+/// of at most 16 bits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyndromeTable {
+    syndrome_bits: usize,
+    pub entries: Vec<SyndromeEntry>,
+}
+
+impl SyndromeTable {
+    /// Table of a codec selection
+    pub fn new(spec: &CodeSpec) -> Result<Self, HammingError> {
+        Self::for_code(&spec.general_equivalent().0)
+    }
+
+    /// Table of a configured general code
+    pub fn for_code(code: &Hamming) -> Result<Self, HammingError> {
+        let syndrome_bits = code.syndrome_bits();
+        if syndrome_bits > MAX_SYNDROME_BITS || code.data_bits() == 0 {
+            return Err(HammingError::Unsupported);
+        }
+
+        let entries = (0..1 << syndrome_bits)
+            .map(|syndrome| {
+                let outcome = code.syndrome_outcome(syndrome);
+                SyndromeEntry {
+                    syndrome,
+                    outcome,
+                    double_error: outcome == BlockOutcome::Uncorrectable
+                        && matches!(
+                            code.uncorrectable(0, syndrome),
+                            HammingError::DoubleErrorDetected { .. }
+                        ),
+                }
+            })
+            .collect();
+        Ok(Self {
+            syndrome_bits,
+            entries,
+        })
+    }
+
+    /// Bits per syndrome
+    pub fn syndrome_bits(&self) -> usize {
+        self.syndrome_bits
+    }
+
+    /// The table as CSV: `syndrome,bits,action,position`, with the syndrome
+    /// in binary (most significant bit first) and the action one of
+    /// `clean`, `flip`, `double` or `uncorrectable`
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("syndrome,bits,action,position\n");
+        let width = self.syndrome_bits;
+        for entry in &self.entries {
+            let (action, position) = match entry.outcome {
+                BlockOutcome::Clean => ("clean", String::new()),
+                BlockOutcome::Corrected { bit } => ("flip", bit.to_string()),
+                BlockOutcome::Uncorrectable if entry.double_error => ("double", String::new()),
+                BlockOutcome::Uncorrectable => ("uncorrectable", String::new()),
+            };
+            let syndrome = entry.syndrome;
+            let _ = writeln!(out, "{syndrome},{syndrome:0width$b},{action},{position}");
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LookupTables;
+
+    #[test]
+    fn test_syndrome_table_matches_lookup_tables() {
+        for spec in [
+            CodeSpec::Hamming74,
+            CodeSpec::Hamming1511,
+            CodeSpec::General { data_bits: 8 },
+        ] {
+            let table = SyndromeTable::new(&spec).unwrap();
+            let lookup = LookupTables::new(&spec).unwrap();
+            let flips: Vec<u8> = table
+                .entries
+                .iter()
+                .map(|entry| match entry.outcome {
+                    BlockOutcome::Corrected { bit } => bit as u8,
+                    _ => LookupTables::NO_FLIP,
+                })
+                .collect();
+            assert_eq!(flips, lookup.syndrome);
+        }
+        assert_eq!(
+            SyndromeTable::new(&CodeSpec::General { data_bits: 1 << 20 }),
+            Err(HammingError::Unsupported)
+        );
+    }
+
+    #[test]
+    fn test_syndrome_table_secded() {
+        let code = Hamming::new(64).with_extended(true);
+        let table = SyndromeTable::for_code(&code).unwrap();
+        assert_eq!(table.syndrome_bits(), 8);
+        assert_eq!(table.entries.len(), 256);
+        // Even overall parity with a nonzero syndrome is a double error
+        assert!(table.entries[3].double_error);
+        assert_eq!(
+            table.entries[0x80].outcome,
+            BlockOutcome::Corrected { bit: 71 }
+        );
+
+        // Every single-bit error of a codeword finds its own entry
+        for bit in 0..72 {
+            let mut encoded = code.encode(&[0; 8]);
+            encoded[bit / 8] ^= 1 << (bit % 8);
+            let mut syndrome = 0;
+            code.decode_observed(&encoded, &mut |event| syndrome = event.syndrome)
+                .unwrap();
+            assert_eq!(
+                table.entries[syndrome].outcome,
+                BlockOutcome::Corrected { bit }
+            );
+        }
+
+        let csv = SyndromeTable::new(&CodeSpec::Hamming74).unwrap().to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[..2],
+            ["syndrome,bits,action,position", "0,000,clean,"]
+        );
+        assert_eq!(lines.len(), 9);
+    }
+}
@@ -0,0 +1,228 @@
+use crate::{BlockOutcome, CodeSpec, Hamming, HammingCode, HammingError};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt::Write;
+
+// Decode tables have one entry per codeword, so keep them to 64K entries
+const MAX_TABLE_BLOCK_BITS: usize = 16;
+
+/// Source language for [`LookupTables::render`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableFormat {
+    /// `static const` arrays for a C header
+    C,
+    /// `pub static` arrays for a Rust module
+    Rust,
+}
+
+/// Per-block lookup tables for table-driven encoders and decoders
+///
+/// Codeword values hold codeword position `i` in bit `i`, i.e. the bits in
+/// the order the codec writes them to the stream. Codes are limited to
+/// blocks of at most 16 bits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LookupTables {
+    name: String,
+    block_size: usize,
+    data_bits: usize,
+    /// Codeword for every data value
+    pub encode: Vec<u16>,
+    /// Codeword bit to flip for every syndrome, or [`LookupTables::NO_FLIP`]
+    /// for syndrome 0 and syndromes no single error produces
+    pub syndrome: Vec<u8>,
+    /// Corrected data for every received codeword, or
+    /// [`LookupTables::UNCORRECTABLE`]
+    pub decode: Vec<u16>,
+}
+
+impl LookupTables {
+    pub const NO_FLIP: u8 = 0xFF;
+    pub const UNCORRECTABLE: u16 = 0xFFFF;
+
+    /// Tables for a codec selection, named after it (`hamming74`,
+    /// `hamming1511`, `hamming_31_26`)
+    pub fn new(spec: &CodeSpec) -> Result<Self, HammingError> {
+        let (code, name) = spec.general_equivalent();
+        Self::for_code(&code, &name)
+    }
+
+    /// Tables for a configured general code, e.g. one using
+    /// [`Layout::Matlab`](crate::Layout::Matlab)
+    pub fn for_code(code: &Hamming, name: &str) -> Result<Self, HammingError> {
+        let n = code.block_size();
+        let k = code.data_bits();
+        if n > MAX_TABLE_BLOCK_BITS || k == 0 {
+            return Err(HammingError::Unsupported);
+        }
+
+        let encode = (0..1u128 << k)
+            .map(|data| code.encode_word(data) as u16)
+            .collect();
+
+        let mut syndrome = vec![Self::NO_FLIP; 1 << (n - k)];
+        for bit in 0..n {
+            let (_, s, _) = code.decode_word(1 << bit);
+            if let Some(entry) = syndrome.get_mut(s) {
+                *entry = bit as u8;
+            }
+        }
+
+        let decode = (0..1u128 << n)
+            .map(|word| match code.decode_word(word) {
+                (_, _, BlockOutcome::Uncorrectable) => Self::UNCORRECTABLE,
+                (data, _, _) => data as u16,
+            })
+            .collect();
+
+        Ok(Self {
+            name: name.to_string(),
+            block_size: n,
+            data_bits: k,
+            encode,
+            syndrome,
+            decode,
+        })
+    }
+
+    /// Source text defining the three tables and their sentinel constants
+    pub fn render(&self, format: TableFormat) -> String {
+        let codeword = if self.block_size <= 8 { 8 } else { 16 };
+        let data = if self.data_bits < 8 { 8 } else { 16 };
+        // Sentinels are all ones in the element type
+        let uncorrectable = Self::UNCORRECTABLE >> (16 - data);
+
+        let mut out = String::new();
+        let title = format!(
+            "Hamming({},{}) lookup tables generated by hamming-rs {}",
+            self.block_size,
+            self.data_bits,
+            env!("CARGO_PKG_VERSION")
+        );
+        let upper = self.name.to_uppercase();
+
+        match format {
+            TableFormat::C => {
+                let _ = writeln!(out, "/* {title} */");
+                let _ = writeln!(out, "#include <stdint.h>\n");
+                let _ = writeln!(out, "#define {upper}_NO_FLIP 0x{:X}", Self::NO_FLIP);
+                let _ = writeln!(out, "#define {upper}_UNCORRECTABLE 0x{uncorrectable:X}");
+            }
+            TableFormat::Rust => {
+                let _ = writeln!(out, "// {title}\n");
+                let _ = writeln!(
+                    out,
+                    "pub const {upper}_NO_FLIP: u8 = 0x{:X};",
+                    Self::NO_FLIP
+                );
+                let _ = writeln!(
+                    out,
+                    "pub const {upper}_UNCORRECTABLE: u{data} = 0x{uncorrectable:X};"
+                );
+            }
+        }
+
+        let encode: Vec<u16> = self.encode.clone();
+        let syndrome: Vec<u16> = self.syndrome.iter().map(|&s| s as u16).collect();
+        let decode: Vec<u16> = self.decode.iter().map(|&d| d.min(uncorrectable)).collect();
+        for (suffix, bits, values) in [
+            ("encode", codeword, &encode),
+            ("syndrome", 8, &syndrome),
+            ("decode", data, &decode),
+        ] {
+            out.push('\n');
+            Self::render_array(&mut out, format, &self.name, suffix, bits, values);
+        }
+        out
+    }
+
+    fn render_array(
+        out: &mut String,
+        format: TableFormat,
+        name: &str,
+        suffix: &str,
+        bits: usize,
+        values: &[u16],
+    ) {
+        let len = values.len();
+        let _ = match format {
+            TableFormat::C => {
+                writeln!(out, "static const uint{bits}_t {name}_{suffix}[{len}] = {{")
+            }
+            TableFormat::Rust => {
+                let ident = format!("{name}_{suffix}").to_uppercase();
+                writeln!(out, "pub static {ident}: [u{bits}; {len}] = [")
+            }
+        };
+
+        let width = bits / 4;
+        for row in values.chunks(8) {
+            let items: Vec<String> = row.iter().map(|v| format!("0x{v:0width$X},")).collect();
+            let _ = writeln!(out, "    {}", items.join(" "));
+        }
+
+        out.push_str(match format {
+            TableFormat::C => "};\n",
+            TableFormat::Rust => "];\n",
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Hamming74, Hamming1511};
+
+    #[test]
+    fn test_tables_match_fixed_codecs() {
+        let t74 = LookupTables::new(&CodeSpec::Hamming74).unwrap();
+        for nibble in 0..16u8 {
+            assert_eq!(
+                t74.encode[nibble as usize],
+                Hamming74::encode_nibble(nibble) as u16
+            );
+        }
+        for word in 0..128u8 {
+            let (data, _, _) = Hamming74::decode_block(word);
+            assert_eq!(t74.decode[word as usize], data as u16);
+        }
+
+        let t1511 = LookupTables::new(&CodeSpec::Hamming1511).unwrap();
+        for data in 0..2048u16 {
+            assert_eq!(t1511.encode[data as usize], Hamming1511::encode_block(data));
+        }
+        // Every syndrome of a perfect code points at a bit
+        assert_eq!(t1511.syndrome[0], LookupTables::NO_FLIP);
+        assert!(t1511.syndrome[1..].iter().all(|&s| s < 15));
+    }
+
+    #[test]
+    fn test_tables_shortened_code() {
+        let tables = LookupTables::new(&CodeSpec::General { data_bits: 8 }).unwrap();
+        assert_eq!(tables.syndrome.len(), 16);
+        // Syndromes 13..=15 point past the 12-bit block
+        assert_eq!(tables.syndrome[15], LookupTables::NO_FLIP);
+        assert!(tables.decode.contains(&LookupTables::UNCORRECTABLE));
+
+        assert_eq!(
+            LookupTables::new(&CodeSpec::General { data_bits: 26 }),
+            Err(HammingError::Unsupported)
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let tables = LookupTables::new(&CodeSpec::Hamming74).unwrap();
+
+        let c = tables.render(TableFormat::C);
+        assert!(c.contains("static const uint8_t hamming74_encode[16] = {\n    0x00, 0x07,"));
+        assert!(c.contains("#define HAMMING74_UNCORRECTABLE 0xFF"));
+
+        let rust = tables.render(TableFormat::Rust);
+        assert!(rust.contains("pub static HAMMING74_DECODE: [u8; 128] = ["));
+        assert!(rust.contains("    0x00, 0x07, 0x19, 0x1E, 0x2A, 0x2D, 0x33, 0x34,\n"));
+    }
+}
@@ -0,0 +1,73 @@
+use crate::sidecar::{PREFIX_LEN, header, parse_header};
+use crate::{CodeSpec, HammingError};
+
+const MAGIC: &[u8; 4] = b"HENC";
+
+/// Encode `data` with the code `spec` selects, behind a header naming the
+/// code and the data length
+///
+/// The header is the one of [`Sidecar`](crate::Sidecar) files with its own
+/// magic (`HENC`), Hamming(7,4) protected, so [`decode_auto`] needs no
+/// out-of-band hint and returns exactly `data.len()` bytes.
+pub fn encode_tagged(spec: &CodeSpec, data: &[u8]) -> Vec<u8> {
+    let mut out = header(MAGIC, spec, data.len() as u64);
+    out.extend(spec.build().encode(data));
+    out
+}
+
+/// Decode a stream produced by [`encode_tagged`] with the code its header
+/// names, returning that code and the data
+///
+/// Streams without the header fail with [`HammingError::Unsupported`].
+pub fn decode_auto(encoded: &[u8]) -> Result<(CodeSpec, Vec<u8>), HammingError> {
+    let (spec, data_len) = parse_header(MAGIC, encoded)?;
+    let payload = encoded.get(PREFIX_LEN..).unwrap_or_default();
+    let mut data = spec.build().decode(payload)?;
+
+    let data_len = usize::try_from(data_len).map_err(|_| HammingError::InvalidLength)?;
+    if data.len() < data_len {
+        return Err(HammingError::InvalidLength);
+    }
+    data.truncate(data_len);
+    Ok((spec, data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Hamming74, HammingCode};
+
+    #[test]
+    fn test_decode_auto() {
+        let specs = [
+            CodeSpec::Hamming74,
+            CodeSpec::Hamming1511,
+            CodeSpec::General { data_bits: 26 },
+            CodeSpec::Secded { data_bits: 64 },
+        ];
+        for spec in specs {
+            for data in [&b""[..], b"x", b"self-describing"] {
+                let mut encoded = encode_tagged(&spec, data);
+                // One flip in the header and one in the payload
+                encoded[6] ^= 0x01;
+                let last = encoded.len() - 1;
+                encoded[last] ^= 0x02;
+                assert_eq!(decode_auto(&encoded), Ok((spec, data.to_vec())));
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_auto_rejects() {
+        let encoded = encode_tagged(&CodeSpec::Hamming1511, b"truncated payload");
+        assert_eq!(decode_auto(b"HENC"), Err(HammingError::InvalidLength));
+        assert_eq!(
+            decode_auto(&Hamming74.encode(b"no header at all, just data")),
+            Err(HammingError::Unsupported)
+        );
+        assert_eq!(
+            decode_auto(&encoded[..encoded.len() - 4]),
+            Err(HammingError::InvalidLength)
+        );
+    }
+}
@@ -0,0 +1,154 @@
+//! Decode counters published through the `metrics` facade
+//!
+//! With the `metrics` feature every decode increments
+//! `hamming_blocks_decoded`, `hamming_bits_corrected` and
+//! `hamming_uncorrectable_blocks` on the installed recorder, and every
+//! [`Scrubber`](crate::Scrubber) pass `hamming_scrub_corrections` and
+//! `hamming_scrub_failures`. Without it the wrappers are plain
+//! pass-throughs.
+
+#[cfg(feature = "std")]
+use crate::ScrubReport;
+use crate::{BlockEvent, HammingError};
+
+#[cfg(feature = "metrics")]
+pub(crate) const BLOCKS_DECODED: &str = "hamming_blocks_decoded";
+#[cfg(feature = "metrics")]
+pub(crate) const BITS_CORRECTED: &str = "hamming_bits_corrected";
+#[cfg(feature = "metrics")]
+pub(crate) const UNCORRECTABLE_BLOCKS: &str = "hamming_uncorrectable_blocks";
+#[cfg(feature = "metrics")]
+pub(crate) const SCRUB_CORRECTIONS: &str = "hamming_scrub_corrections";
+#[cfg(feature = "metrics")]
+pub(crate) const SCRUB_FAILURES: &str = "hamming_scrub_failures";
+
+/// Run `decode` with `observer`, counting its block events
+#[cfg(feature = "metrics")]
+pub(crate) fn instrumented<T>(
+    observer: &mut dyn FnMut(&BlockEvent),
+    decode: impl FnOnce(&mut dyn FnMut(&BlockEvent)) -> Result<T, HammingError>,
+) -> Result<T, HammingError> {
+    use crate::BlockOutcome;
+
+    let (mut blocks, mut corrected, mut uncorrectable) = (0u64, 0u64, 0u64);
+    let result = decode(&mut |event| {
+        match event.outcome {
+            BlockOutcome::Clean => blocks += 1,
+            BlockOutcome::Corrected { .. } => {
+                blocks += 1;
+                corrected += 1;
+            }
+            BlockOutcome::Uncorrectable => uncorrectable += 1,
+        }
+        observer(event);
+    });
+
+    // One update per call rather than per block
+    metrics::counter!(BLOCKS_DECODED).increment(blocks);
+    metrics::counter!(BITS_CORRECTED).increment(corrected);
+    metrics::counter!(UNCORRECTABLE_BLOCKS).increment(uncorrectable);
+
+    result
+}
+
+/// Run `decode` with `observer`, counting its block events
+#[cfg(not(feature = "metrics"))]
+#[inline]
+pub(crate) fn instrumented<T>(
+    observer: &mut dyn FnMut(&BlockEvent),
+    decode: impl FnOnce(&mut dyn FnMut(&BlockEvent)) -> Result<T, HammingError>,
+) -> Result<T, HammingError> {
+    decode(observer)
+}
+
+/// Count the corrections and failures of a scrub pass
+#[cfg(feature = "metrics")]
+pub(crate) fn scrubbed(report: &ScrubReport) {
+    metrics::counter!(SCRUB_CORRECTIONS).increment(report.corrected);
+    metrics::counter!(SCRUB_FAILURES).increment(report.uncorrectable);
+}
+
+/// Count the corrections and failures of a scrub pass
+#[cfg(all(feature = "std", not(feature = "metrics")))]
+#[inline]
+pub(crate) fn scrubbed(_report: &ScrubReport) {}
+
+#[cfg(all(test, feature = "metrics"))]
+mod tests {
+    use super::*;
+    use crate::{Hamming, HammingCode, sample_codecs};
+    use metrics_util::MetricKind;
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+    fn counters(decode: impl FnOnce()) -> Vec<(String, u64)> {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        metrics::with_local_recorder(&recorder, decode);
+
+        let mut counters: Vec<_> = snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .filter_map(|(key, _, _, value)| match (key.kind(), value) {
+                (MetricKind::Counter, DebugValue::Counter(n)) => {
+                    Some((key.key().name().to_string(), n))
+                }
+                _ => None,
+            })
+            .collect();
+        counters.sort();
+        counters
+    }
+
+    #[test]
+    fn test_decode_counters() {
+        let codecs = sample_codecs();
+
+        for codec in &codecs {
+            let mut encoded = codec.encode(&[0x12, 0x34, 0x56, 0x78]);
+            let blocks = (encoded.len() * 8 / codec.block_stride()) as u64;
+            encoded[0] ^= 0x04;
+
+            let counters = counters(|| {
+                codec.decode(&encoded).unwrap();
+            });
+            assert_eq!(
+                counters,
+                vec![
+                    (BITS_CORRECTED.to_string(), 1),
+                    (BLOCKS_DECODED.to_string(), blocks),
+                    (UNCORRECTABLE_BLOCKS.to_string(), 0),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_uncorrectable_counter() {
+        // Errors at positions 3 and 12 give syndrome 15, past the 12-bit block
+        let h = Hamming::new(8);
+        let counters = counters(|| {
+            assert_eq!(
+                h.decode(&[0x04, 0x08]),
+                Err(HammingError::UncorrectableErrors)
+            );
+        });
+        assert!(counters.contains(&(UNCORRECTABLE_BLOCKS.to_string(), 1)));
+    }
+
+    #[test]
+    fn test_scrub_counters() {
+        let report = ScrubReport {
+            checked: 10,
+            corrected: 3,
+            uncorrectable: 1,
+        };
+        assert_eq!(
+            counters(|| scrubbed(&report)),
+            vec![
+                (SCRUB_CORRECTIONS.to_string(), 3),
+                (SCRUB_FAILURES.to_string(), 1),
+            ]
+        );
+    }
+}
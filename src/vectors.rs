@@ -0,0 +1,312 @@
+//! JSON conformance test vectors
+//!
+//! A corpus is a JSON object `{"version": 1, "vectors": [...]}`. Each vector
+//! names the code in the [`CodeSpec`] form, gives the data and its clean
+//! codeword as hex strings, lists the codeword bits to flip (LSB-first bit
+//! offsets into the codeword) and the result a decoder must produce:
+//!
+//! ```json
+//! {
+//!   "code": {"code": "hamming74"},
+//!   "data": "4142",
+//!   "codeword": "072a192a",
+//!   "errors": [3],
+//!   "expected": {"result": "ok", "data": "4142"}
+//! }
+//! ```
+//!
+//! Vectors of the general codes may add `"layout": "matlab"` and
+//! `"bit_order": "msbfirst"`, see [`Layout`] and [`BitOrder`].
+//!
+//! Failures are `{"result": "error", "error": "uncorrectable"}` (or
+//! `"invalid_length"`). Expected results are what this crate produces,
+//! including miscorrections when a vector injects more errors than the code
+//! can handle.
+//!
+//! [`GOLDEN_CORPUS`] is [`TestCorpus::standard`] as published; this crate's
+//! own tests check against it, so an implementation validated with it is not
+//! only validated against itself.
+
+use crate::hamming::{is_lsb_first, is_positional};
+use crate::{BitOrder, CodeSpec, Hamming, HammingCode, HammingError, Layout};
+use serde::{Deserialize, Serialize};
+
+/// Schema version written by [`TestCorpus::to_json`]
+pub const VECTOR_FORMAT_VERSION: u32 = 1;
+
+/// The published standard corpus, as JSON
+pub const GOLDEN_CORPUS: &str = include_str!("../vectors/golden.json");
+
+/// A set of test vectors
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TestCorpus {
+    pub version: u32,
+    pub vectors: Vec<TestVector>,
+}
+
+/// One encode/corrupt/decode case
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TestVector {
+    pub code: CodeSpec,
+    #[serde(with = "hex")]
+    pub data: Vec<u8>,
+    #[serde(with = "hex")]
+    pub codeword: Vec<u8>,
+    #[serde(default)]
+    pub errors: Vec<usize>,
+    pub expected: Expected,
+    /// Layout of a general code
+    #[serde(default, skip_serializing_if = "is_positional")]
+    pub layout: Layout,
+    /// Bit order of a general code
+    #[serde(default, skip_serializing_if = "is_lsb_first")]
+    pub bit_order: BitOrder,
+}
+
+/// Decoder outcome for a [`TestVector`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "result", rename_all = "lowercase")]
+pub enum Expected {
+    Ok {
+        #[serde(with = "hex")]
+        data: Vec<u8>,
+    },
+    Error {
+        error: String,
+    },
+}
+
+impl TestVector {
+    /// Build a vector from this crate's behaviour
+    pub fn generate(code: CodeSpec, data: &[u8], errors: &[usize]) -> Self {
+        TestVector {
+            code,
+            data: data.to_vec(),
+            codeword: Vec::new(),
+            errors: errors.to_vec(),
+            expected: Expected::Error {
+                error: String::new(),
+            },
+            layout: Layout::Positional,
+            bit_order: BitOrder::LsbFirst,
+        }
+        .regenerate()
+    }
+
+    /// The vector for the general code in `layout`
+    pub fn with_layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self.regenerate()
+    }
+
+    /// The vector for the general code in `bit_order`
+    pub fn with_bit_order(mut self, bit_order: BitOrder) -> Self {
+        self.bit_order = bit_order;
+        self.regenerate()
+    }
+
+    /// The codeword with the listed errors applied
+    pub fn corrupted(&self) -> Vec<u8> {
+        let mut corrupted = self.codeword.clone();
+        for &bit in &self.errors {
+            if let Some(byte) = corrupted.get_mut(bit / 8) {
+                *byte ^= 1 << (bit % 8);
+            }
+        }
+        corrupted
+    }
+
+    /// Whether this crate reproduces the codeword and the expected result
+    pub fn verify(&self) -> bool {
+        self.codec().encode(&self.data) == self.codeword && self.decode() == self.expected
+    }
+
+    // Layout and bit order only apply to the general codes
+    fn codec(&self) -> Box<dyn HammingCode> {
+        match self.code {
+            CodeSpec::General { data_bits } | CodeSpec::Secded { data_bits } => Box::new(
+                Hamming::new(data_bits)
+                    .with_extended(matches!(self.code, CodeSpec::Secded { .. }))
+                    .with_layout(self.layout)
+                    .with_bit_order(self.bit_order),
+            ),
+            _ => self.code.build(),
+        }
+    }
+
+    fn regenerate(mut self) -> Self {
+        self.codeword = self.codec().encode(&self.data);
+        self.expected = self.decode();
+        self
+    }
+
+    fn decode(&self) -> Expected {
+        match self.codec().decode(&self.corrupted()) {
+            Ok(data) => Expected::Ok { data },
+            Err(err) => Expected::Error {
+                error: error_name(&err).to_string(),
+            },
+        }
+    }
+}
+
+impl TestCorpus {
+    /// Vectors for every built-in code: clean codewords, every single-bit
+    /// error in the first block and a double error
+    pub fn standard() -> Self {
+        let codes = [
+            CodeSpec::Hamming74,
+            CodeSpec::Hamming1511,
+            CodeSpec::General { data_bits: 4 },
+            CodeSpec::General { data_bits: 11 },
+            CodeSpec::General { data_bits: 26 },
+            CodeSpec::Secded { data_bits: 64 },
+        ];
+        let payloads: [&[u8]; 3] = [b"", b"\x00\xff", b"Hamming"];
+
+        let mut vectors = Vec::new();
+        for code in codes {
+            let stride = code.build().block_stride();
+            let block_size = code.build().block_size();
+            for data in payloads {
+                vectors.push(TestVector::generate(code, data, &[]));
+            }
+            for bit in 0..block_size {
+                vectors.push(TestVector::generate(code, b"Hamming", &[bit]));
+            }
+            vectors.push(TestVector::generate(code, b"Hamming", &[0, stride + 1]));
+            vectors.push(TestVector::generate(code, b"Hamming", &[1, 2]));
+        }
+
+        // The other layouts, with textbook Hamming(7,4) as MSB-first
+        for (data_bits, layout, bit_order) in [
+            (4, Layout::Matlab, BitOrder::LsbFirst),
+            (11, Layout::Matlab, BitOrder::LsbFirst),
+            (4, Layout::Positional, BitOrder::MsbFirst),
+            (26, Layout::Positional, BitOrder::MsbFirst),
+        ] {
+            let code = CodeSpec::General { data_bits };
+            let block_size = code.build().block_size();
+            for errors in (0..=block_size).map(|bit| bit.checked_sub(1)) {
+                let errors: Vec<usize> = errors.into_iter().collect();
+                vectors.push(
+                    TestVector::generate(code, b"Hamming", &errors)
+                        .with_layout(layout)
+                        .with_bit_order(bit_order),
+                );
+            }
+        }
+
+        TestCorpus {
+            version: VECTOR_FORMAT_VERSION,
+            vectors,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, HammingError> {
+        serde_json::from_str(json).map_err(|_| HammingError::Serialization)
+    }
+
+    /// Indices of the vectors this crate does not reproduce
+    pub fn failures(&self) -> Vec<usize> {
+        (0..self.vectors.len())
+            .filter(|&i| !self.vectors.get(i).is_some_and(TestVector::verify))
+            .collect()
+    }
+}
+
+// Stable names for the errors a decoder can report
+fn error_name(err: &HammingError) -> &'static str {
+    match err {
+        HammingError::InvalidLength => "invalid_length",
+        // Kept apart from `uncorrectable` only in this crate's error type
+        HammingError::UncorrectableErrors | HammingError::DoubleErrorDetected { .. } => {
+            "uncorrectable"
+        }
+        _ => "other",
+    }
+}
+
+// Byte strings as lowercase hex
+mod hex {
+    use serde::{Deserialize, Deserializer, Serializer, de};
+    use std::fmt::Write;
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        let hex = bytes.iter().fold(String::new(), |mut hex, b| {
+            let _ = write!(hex, "{b:02x}");
+            hex
+        });
+        serializer.serialize_str(&hex)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        if !hex.len().is_multiple_of(2) {
+            return Err(de::Error::custom("odd number of hex digits"));
+        }
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| {
+                hex.get(i..i + 2)
+                    .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                    .ok_or_else(|| de::Error::custom("invalid hex digit"))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standard_corpus_roundtrip() {
+        let corpus = TestCorpus::standard();
+        assert!(corpus.failures().is_empty());
+
+        let parsed = TestCorpus::from_json(&corpus.to_json()).unwrap();
+        assert_eq!(parsed, corpus);
+    }
+
+    #[test]
+    fn test_golden_corpus() {
+        let golden = TestCorpus::from_json(GOLDEN_CORPUS).unwrap();
+        assert!(golden.failures().is_empty());
+        // Regenerate the file with `TestCorpus::standard().to_json()` only
+        // for intended changes
+        assert_eq!(golden, TestCorpus::standard());
+
+        // 'H' is 0100 1000, and textbook 0100 -> 1001100 comes first
+        let textbook = golden
+            .vectors
+            .iter()
+            .find(|v| v.bit_order == BitOrder::MsbFirst && v.code.build().data_bits() == 4)
+            .unwrap();
+        assert_eq!(textbook.codeword[0] >> 1, 0b1001100);
+    }
+
+    #[test]
+    fn test_vector_json() {
+        let vector = TestVector::generate(CodeSpec::Hamming74, b"AB", &[3]);
+        let json = serde_json::to_string(&vector).unwrap();
+        assert_eq!(
+            json,
+            r#"{"code":{"code":"hamming74"},"data":"4142","codeword":"072a192a","errors":[3],"expected":{"result":"ok","data":"4142"}}"#
+        );
+
+        // A vector this crate disagrees with is reported
+        let mut corpus = TestCorpus {
+            version: VECTOR_FORMAT_VERSION,
+            vectors: vec![vector],
+        };
+        corpus.vectors[0].expected = Expected::Error {
+            error: "uncorrectable".into(),
+        };
+        assert_eq!(corpus.failures(), vec![0]);
+    }
+}
@@ -0,0 +1,99 @@
+//! WebAssembly bindings
+//!
+//! Byte buffers cross the boundary as `Uint8Array`; failed decodes throw an
+//! [`ErrorCode`] number.
+
+use crate::{Hamming, Hamming74, Hamming1511, HammingCode, HammingError};
+use wasm_bindgen::prelude::*;
+
+/// Error codes thrown to JavaScript
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorCode {
+    InvalidLength = 1,
+    UncorrectableErrors = 2,
+    Other = 3,
+}
+
+impl From<&HammingError> for ErrorCode {
+    fn from(err: &HammingError) -> Self {
+        match err {
+            HammingError::InvalidLength => ErrorCode::InvalidLength,
+            HammingError::UncorrectableErrors | HammingError::DoubleErrorDetected { .. } => {
+                ErrorCode::UncorrectableErrors
+            }
+            _ => ErrorCode::Other,
+        }
+    }
+}
+
+/// A codec handle usable from JavaScript
+#[wasm_bindgen]
+pub struct Codec {
+    inner: Box<dyn HammingCode>,
+}
+
+#[wasm_bindgen]
+impl Codec {
+    pub fn hamming74() -> Codec {
+        Codec {
+            inner: Box::new(Hamming74),
+        }
+    }
+
+    pub fn hamming1511() -> Codec {
+        Codec {
+            inner: Box::new(Hamming1511),
+        }
+    }
+
+    /// Throws [`ErrorCode::Other`] for unsupported `data_bits`
+    pub fn general(data_bits: usize) -> Result<Codec, ErrorCode> {
+        let code = Hamming::try_new(data_bits).map_err(|e| ErrorCode::from(&e))?;
+        Ok(Codec {
+            inner: Box::new(code),
+        })
+    }
+
+    pub fn encode(&self, data: &[u8]) -> Vec<u8> {
+        self.inner.encode(data)
+    }
+
+    pub fn decode(&self, encoded: &[u8]) -> Result<Vec<u8>, ErrorCode> {
+        self.inner.decode(encoded).map_err(|e| ErrorCode::from(&e))
+    }
+
+    #[wasm_bindgen(js_name = blockSize)]
+    pub fn block_size(&self) -> usize {
+        self.inner.block_size()
+    }
+
+    #[wasm_bindgen(js_name = dataBits)]
+    pub fn data_bits(&self) -> usize {
+        self.inner.data_bits()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wasm_codec_roundtrip() {
+        for codec in [
+            Codec::hamming74(),
+            Codec::hamming1511(),
+            Codec::general(26).unwrap(),
+        ] {
+            let encoded = codec.encode(b"wasm");
+            assert!(codec.decode(&encoded).unwrap().starts_with(b"wasm"));
+        }
+    }
+
+    #[test]
+    fn test_wasm_error_code() {
+        let codec = Codec::hamming74();
+        assert_eq!(codec.decode(&[0x00]), Err(ErrorCode::InvalidLength));
+        assert!(matches!(Codec::general(0), Err(ErrorCode::Other)));
+    }
+}
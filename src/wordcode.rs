@@ -0,0 +1,211 @@
+//! Encoding and decoding of blocks of up to 128 bits as `u128` words, with
+//! masks worked out once per call instead of per bit
+
+use crate::bits::{BitReader, BitWriter, read_word, write_word};
+use crate::{BitOrder, BlockOutcome, Hamming, HammingCode};
+use alloc::{vec, vec::Vec};
+
+// No single error gives this syndrome
+const NO_POSITION: u8 = u8::MAX;
+
+/// A [`Hamming`] code of at most 128 bits per block, as masks
+#[derive(Debug, Clone)]
+pub(crate) struct WordCode {
+    data_bits: u32,
+    block_bits: u32,
+    parity_bits: u32,
+    /// Codeword position of the overall parity bit of extended codes
+    overall: Option<u32>,
+    /// Runs of consecutive data bits: data bit, codeword position, length
+    runs: Vec<(u32, u32, u32)>,
+    /// Codeword positions covered by each syndrome bit
+    checks: Vec<u128>,
+    /// Codeword position of the parity bit of each syndrome bit
+    parity_positions: Vec<u32>,
+    /// Codeword position for each Hamming syndrome
+    error_positions: Vec<u8>,
+}
+
+impl WordCode {
+    /// Masks for `code`, if its blocks fit a `u128`
+    pub(crate) fn new(code: &Hamming) -> Option<Self> {
+        let block_bits = u32::try_from(code.block_size()).ok()?;
+        if block_bits > 128 || code.data_bits() == 0 {
+            return None;
+        }
+        let hamming_bits = block_bits - u32::from(code.extended());
+        let parity_bits = hamming_bits - code.data_bits() as u32;
+
+        let mut word = Self {
+            data_bits: code.data_bits() as u32,
+            block_bits,
+            parity_bits,
+            overall: code.extended().then_some(hamming_bits),
+            runs: Vec::new(),
+            checks: vec![0; parity_bits as usize],
+            parity_positions: vec![0; parity_bits as usize],
+            error_positions: vec![NO_POSITION; 1 << parity_bits],
+        };
+        let mut data_bit = 0;
+        for (position, column) in (0..hamming_bits).zip(code.columns()) {
+            for (bit, check) in word.checks.iter_mut().enumerate() {
+                if column >> bit & 1 == 1 {
+                    *check |= 1 << position;
+                }
+            }
+            if let Some(entry) = word.error_positions.get_mut(column) {
+                *entry = position as u8;
+            }
+
+            if column.is_power_of_two() {
+                let bit = column.trailing_zeros() as usize;
+                if let Some(slot) = word.parity_positions.get_mut(bit) {
+                    *slot = position;
+                }
+            } else {
+                match word.runs.last_mut() {
+                    Some((_, start, len)) if *start + *len == position => *len += 1,
+                    _ => word.runs.push((data_bit, position, 1)),
+                }
+                data_bit += 1;
+            }
+        }
+        Some(word)
+    }
+
+    /// Codeword of the low data bits of `data`
+    pub(crate) fn encode_word(&self, data: u128) -> u128 {
+        let mut word = 0;
+        for &(data_bit, position, len) in &self.runs {
+            word |= (data >> data_bit & mask(len)) << position;
+        }
+        for (&check, &position) in self.checks.iter().zip(&self.parity_positions) {
+            word |= u128::from(parity(word & check)) << position;
+        }
+        if let Some(overall) = self.overall {
+            word |= u128::from(parity(word)) << overall;
+        }
+        word
+    }
+
+    /// Data bits, syndrome and outcome of a received codeword; the data of
+    /// an uncorrectable block is as received
+    pub(crate) fn decode_word(&self, mut word: u128) -> (u128, usize, BlockOutcome) {
+        let mut syndrome = self
+            .checks
+            .iter()
+            .enumerate()
+            .fold(0, |syndrome, (bit, &check)| {
+                syndrome | usize::from(parity(word & check)) << bit
+            });
+        let hamming = syndrome;
+        if self.overall.is_some() {
+            syndrome |= usize::from(parity(word)) << self.parity_bits;
+        }
+
+        let position = match (syndrome, self.overall) {
+            (0, _) => None,
+            // Even overall parity: a double error
+            (_, Some(_)) if syndrome == hamming => Some(NO_POSITION),
+            (_, Some(overall)) if hamming == 0 => Some(overall as u8),
+            _ => self.error_positions.get(hamming).copied(),
+        };
+        let outcome = match position {
+            None => BlockOutcome::Clean,
+            Some(position) if position != NO_POSITION => {
+                word ^= 1 << position;
+                BlockOutcome::Corrected {
+                    bit: usize::from(position),
+                }
+            }
+            Some(_) => BlockOutcome::Uncorrectable,
+        };
+
+        let data = self
+            .runs
+            .iter()
+            .fold(0, |data, &(data_bit, position, len)| {
+                data | (word >> position & mask(len)) << data_bit
+            });
+        (data, syndrome, outcome)
+    }
+
+    /// Encode `data`, packed in `order`, as [`Hamming`] does, appending to
+    /// `out`
+    pub(crate) fn encode<B: Extend<u8>>(&self, data: &[u8], order: BitOrder, out: B) -> B {
+        let blocks = (data.len() * 8).div_ceil(self.data_bits as usize);
+        let mut reader = BitReader::new(data).with_order(order);
+        let mut writer = BitWriter::with_sink(out).with_order(order);
+        for _ in 0..blocks {
+            let word = self.encode_word(read_word(&mut reader, self.data_bits));
+            write_word(&mut writer, word, self.block_bits);
+        }
+        writer.finish()
+    }
+
+    /// Decode `blocks` blocks of `encoded`, appending to `out` and calling
+    /// `block` with each block's index, syndrome and outcome; an error from
+    /// it stops the decode
+    pub(crate) fn decode<B: Extend<u8>, E>(
+        &self,
+        encoded: &[u8],
+        order: BitOrder,
+        blocks: usize,
+        out: B,
+        mut block: impl FnMut(usize, usize, BlockOutcome) -> Result<(), E>,
+    ) -> Result<B, E> {
+        let mut reader = BitReader::new(encoded).with_order(order);
+        let mut writer = BitWriter::with_sink(out).with_order(order);
+        for index in 0..blocks {
+            let (data, syndrome, outcome) =
+                self.decode_word(read_word(&mut reader, self.block_bits));
+            block(index, syndrome, outcome)?;
+            write_word(&mut writer, data, self.data_bits);
+        }
+        Ok(writer.finish())
+    }
+}
+
+// Low `bits` bits set
+fn mask(bits: u32) -> u128 {
+    u128::MAX.checked_shr(128 - bits).unwrap_or_default()
+}
+
+fn parity(word: u128) -> bool {
+    word.count_ones() & 1 == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Layout;
+
+    #[test]
+    fn test_word_code_matches_bit_path() {
+        for data_bits in [1, 4, 11, 26, 57, 64, 100, 119, 120] {
+            for code in [
+                Hamming::new(data_bits),
+                Hamming::new(data_bits).with_extended(true),
+                Hamming::new(data_bits).with_layout(Layout::Matlab),
+            ] {
+                let Some(word) = WordCode::new(&code) else {
+                    assert!(code.block_size() > 128);
+                    continue;
+                };
+                let data = 0x0123_4567_89AB_CDEF_FEDC_BA98_7654_3210u128 & mask(data_bits as u32);
+                let encoded = word.encode_word(data);
+                assert_eq!(encoded, code.encode_word(data), "{code:?}");
+                for flips in [0, 1 << 0, 1 << (code.block_size() - 1), 0b101 << 1] {
+                    let (data, syndrome, outcome) = word.decode_word(encoded ^ flips);
+                    let reference = code.decode_word(encoded ^ flips);
+                    assert_eq!((syndrome, outcome), (reference.1, reference.2), "{code:?}");
+                    if outcome != BlockOutcome::Uncorrectable {
+                        assert_eq!(data, reference.0);
+                    }
+                }
+            }
+        }
+        assert!(WordCode::new(&Hamming::new(121)).is_none());
+        assert!(WordCode::new(&Hamming::new(120).with_extended(true)).is_some());
+    }
+}
@@ -0,0 +1,157 @@
+use crate::HammingCode;
+use std::io::{self, Write};
+use std::mem::{self, ManuallyDrop};
+use std::ptr;
+
+/// [`Write`] adapter encoding everything written to it with a
+/// [`HammingCode`] on the way to the inner writer
+///
+/// Data is encoded in whole [`HammingCode::chunk_sizes`] units as soon as
+/// they are complete; the bytes of an unfinished unit are held back across
+/// `write` calls. [`Write::flush`] only flushes the inner writer and
+/// leaves them held back; [`HammingWriter::finish`] encodes them as a final
+/// zero-padded unit, so the whole output is exactly `code.encode` of
+/// everything written. Dropping the writer does the same but ignores errors.
+///
+/// If the inner writer fails during `write`, none of `buf` counts as
+/// written and the held-back bytes are left as they were, but the inner
+/// writer may already have taken part of the encoded units. The encoded
+/// stream is then cut at an arbitrary byte, so the writer should not be
+/// used further.
+///
+/// ```
+/// use hamming_rs::{Hamming1511, HammingCode, HammingWriter};
+/// use std::io::Write;
+///
+/// let mut encoded = Vec::new();
+/// let mut writer = HammingWriter::new(&mut encoded, Hamming1511);
+/// writer.write_all(b"protected ")?;
+/// writer.write_all(b"on the way out")?;
+/// writer.finish()?;
+/// assert_eq!(encoded, Hamming1511.encode(b"protected on the way out"));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct HammingWriter<W: Write, C: HammingCode> {
+    inner: W,
+    code: C,
+    pending: Vec<u8>,
+}
+
+impl<W: Write, C: HammingCode> HammingWriter<W, C> {
+    pub fn new(inner: W, code: C) -> Self {
+        Self {
+            inner,
+            code,
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn code(&self) -> &C {
+        &self.code
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// The inner writer; writing to it directly interleaves with the
+    /// encoded stream
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Data bytes held back until their unit is complete or finished
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Write the held-back bytes as a final zero-padded unit, flush, and
+    /// return the inner writer
+    pub fn finish(mut self) -> io::Result<W> {
+        // Taken up front so a failure here is not retried by `drop`
+        let pending = mem::take(&mut self.pending);
+        if !pending.is_empty() {
+            self.inner.write_all(&self.code.encode(&pending))?;
+        }
+        self.inner.flush()?;
+
+        let this = ManuallyDrop::new(self);
+        // SAFETY: `this` is never dropped or touched again, so `inner` and
+        // `code` are each moved out exactly once
+        let (inner, _code) = unsafe { (ptr::read(&this.inner), ptr::read(&this.code)) };
+        Ok(inner)
+    }
+}
+
+impl<W: Write, C: HammingCode> Write for HammingWriter<W, C> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let (unit, _) = self.code.chunk_sizes();
+        let held = self.pending.len();
+        self.pending.extend_from_slice(buf);
+
+        let whole = self.pending.len() / unit.max(1) * unit;
+        if let Some(units) = self.pending.get(..whole).filter(|units| !units.is_empty()) {
+            if let Err(e) = self.inner.write_all(&self.code.encode(units)) {
+                // Nothing of `buf` counts as written, though `inner` may
+                // have taken part of the units; see the type docs
+                self.pending.truncate(held);
+                return Err(e);
+            }
+            self.pending.drain(..whole);
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W: Write, C: HammingCode> Drop for HammingWriter<W, C> {
+    fn drop(&mut self) {
+        if !self.pending.is_empty() {
+            let _ = self.inner.write_all(&self.code.encode(&self.pending));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Hamming1511, sample_codecs};
+
+    #[test]
+    fn test_writer_matches_slice_api() {
+        let data: Vec<u8> = (0..1000).map(|i| (i * 13 + 5) as u8).collect();
+        let codecs = sample_codecs();
+
+        for codec in codecs {
+            let expected = codec.encode(&data);
+            let mut encoded = Vec::new();
+            let mut writer = HammingWriter::new(&mut encoded, codec);
+            // Uneven pieces, so units straddle write calls
+            for piece in data.chunks(7) {
+                writer.write_all(piece).unwrap();
+                assert!(writer.pending() < writer.code().chunk_sizes().0);
+            }
+            drop(writer);
+            assert_eq!(encoded, expected);
+        }
+    }
+
+    #[test]
+    fn test_writer_finish_pads_block() {
+        let mut writer = HammingWriter::new(Vec::new(), Hamming1511);
+        writer.write_all(b"abc").unwrap();
+        assert_eq!(writer.pending(), 3);
+        writer.flush().unwrap();
+        // Flushing must not pad out the unit in the middle of the stream
+        assert_eq!(writer.pending(), 3);
+        assert!(writer.get_ref().is_empty());
+        writer.write_all(b"defghijklmn").unwrap();
+
+        let encoded = writer.finish().unwrap();
+        assert_eq!(encoded, Hamming1511.encode(b"abcdefghijklmn"));
+    }
+}